@@ -1,11 +1,25 @@
+#[cfg(feature = "cw-codegen")]
+use std::env::current_dir;
+
 use cosmwasm_schema::write_api;
 
-use bidding_platform::msg::{ExecuteMsg, InstantiateMsg, QueryMsg};
+use bidding_platform::msg::{ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg};
 
 fn main() {
     write_api! {
         instantiate: InstantiateMsg,
         execute: ExecuteMsg,
         query: QueryMsg,
+        migrate: MigrateMsg,
     }
+
+    // `cargo schema --features cw-codegen -- --client` also writes a typed TypeScript client
+    #[cfg(feature = "cw-codegen")]
+    cw_codegen::Client::new(env!("CARGO_PKG_NAME"))
+        .message::<InstantiateMsg>("InstantiateMsg")
+        .message::<ExecuteMsg>("ExecuteMsg")
+        .query::<QueryMsg>("QueryMsg")
+        .message::<MigrateMsg>("MigrateMsg")
+        .write_if_requested(&current_dir().unwrap().join("schema"))
+        .unwrap();
 }
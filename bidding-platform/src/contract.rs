@@ -1,88 +1,251 @@
 #[cfg(not(feature = "library"))]
 use cosmwasm_std::entry_point;
-use cosmwasm_std::{to_binary, Binary, Deps, DepsMut, Env, MessageInfo, Response, StdResult};
+use cosmwasm_std::{
+    to_json_binary, Binary, Decimal, Deps, DepsMut, Env, MessageInfo, Response, StdResult,
+};
 use cw2::set_contract_version;
+use cw_utils::ensure_from_older_version;
+use semver::Version;
 
 use crate::error::ContractError;
-use crate::msg::{ExecuteMsg, InstantiateMsg, QueryMsg};
-use crate::state::{BidStatus, Config, BIDS, CONFIG, WINNER};
-use crate::{execute, query};
+use crate::msg::{ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg};
+use crate::state::{Commission, CommissionMode, Config, CONFIG, OWNER};
+use crate::{execute, migrate, query};
 
 // version info for migration info
 const CONTRACT_NAME: &str = "crates.io:bidding-platform";
 const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
 
-// commission rate is 0.1% assuming we're using 6 decimals
-const COMMISSION_RATE: u128 = 1000u128;
+// commission rate is 0.1%
+const COMMISSION_RATE: Decimal = Decimal::permille(1);
 
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn instantiate(
-    deps: DepsMut,
-    _env: Env,
+    mut deps: DepsMut,
+    env: Env,
     info: MessageInfo,
     msg: InstantiateMsg,
 ) -> Result<Response, ContractError> {
+    msg.validate()?;
     set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
 
-    // bid must be instantiated with only one kind of native tokens
-    if info.funds.len() != 1 {
-        return Err(ContractError::TooManyOrLittleNativeTokensSent {});
-    }
-
-    let contract_owner = msg.contract_owner.unwrap_or(info.sender.to_string());
-    let commission = msg.commision.unwrap_or(COMMISSION_RATE);
+    let contract_owner = msg
+        .contract_owner
+        .clone()
+        .unwrap_or(info.sender.to_string());
+    let commission = msg.commission.clone().unwrap_or(Commission {
+        rate: COMMISSION_RATE,
+        tiers: vec![],
+        mode: CommissionMode::EveryBid,
+    });
+    let fee_collector = msg
+        .fee_collector
+        .as_ref()
+        .map(|fee_collector| deps.api.addr_validate(fee_collector))
+        .transpose()?;
     let val_contract_owner = deps.api.addr_validate(&contract_owner)?;
+    let sellers = msg
+        .sellers
+        .as_ref()
+        .map(|sellers| {
+            sellers
+                .iter()
+                .map(|seller| deps.api.addr_validate(seller))
+                .collect::<StdResult<Vec<_>>>()
+        })
+        .transpose()?;
 
     let cfg = Config {
-        commodity: msg.commodity,
-        contract_owner: val_contract_owner.clone(),
-        commission: commission,
-        denom: info.funds[0].denom.clone(),
-        status: BidStatus::Opened,
+        commission,
+        fee_collector,
+        sellers,
     };
 
     CONFIG.save(deps.storage, &cfg)?;
+    OWNER.set(deps.branch(), Some(val_contract_owner.clone()))?;
 
-    WINNER.save(
-        deps.storage,
-        &(cfg.contract_owner.clone(), info.funds[0].amount),
-    )?;
-
-    // contract owner is always the initial bidder and not the sender when they're different
-    BIDS.save(
-        deps.storage,
-        cfg.contract_owner.clone(),
-        &info.funds[0].amount.into(),
+    // contract owner opens the first auction and is its initial bidder, not the sender when they're different
+    let (auction_id, auction) = execute::open_auction(
+        deps.branch(),
+        &env,
+        val_contract_owner.clone(),
+        &info.funds,
+        msg.auction(),
+        None,
     )?;
 
     Ok(Response::new()
         .add_attribute("action", "instantiate")
-        .add_attribute("commodity", cfg.commodity)
-        .add_attribute("contract_owner", cfg.contract_owner)
-        .add_attribute("commission", cfg.commission.to_string())
-        .add_attribute("denom", cfg.denom))
+        .add_attribute("auction_id", auction_id.to_string())
+        .add_attribute("commodity", auction.commodity)
+        .add_attribute("contract_owner", val_contract_owner)
+        .add_attribute("commission", cfg.commission.rate.to_string())
+        .add_attribute("denom", execute::denom_to_string(&auction.denom))
+        .add_attribute("expiration", auction.expiration.to_string()))
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn execute(
     deps: DepsMut,
-    _env: Env,
+    env: Env,
     info: MessageInfo,
     msg: ExecuteMsg,
 ) -> Result<Response, ContractError> {
     match msg {
-        ExecuteMsg::Bid {} => execute::bid(deps, _env, info),
-        ExecuteMsg::Close {} => execute::close(deps, _env, info),
-        ExecuteMsg::Retract { receiver } => execute::retract(deps, _env, info, receiver),
+        ExecuteMsg::CreateAuction(msg) => execute::create_auction(deps, env, info, msg),
+        ExecuteMsg::Bid { auction_id } => execute::bid(deps, env, info, auction_id),
+        ExecuteMsg::Close { auction_id } => execute::close(deps, env, info, auction_id),
+        ExecuteMsg::Retract {
+            auction_id,
+            receiver,
+        } => execute::retract(deps, env, info, auction_id, receiver),
+        ExecuteMsg::Withdraw {
+            auction_id,
+            amount,
+            receiver,
+        } => execute::withdraw(deps, env, info, auction_id, amount, receiver),
+        ExecuteMsg::Finalize { auction_id } => execute::finalize(deps, env, auction_id),
+        ExecuteMsg::Commit {
+            auction_id,
+            commitment,
+        } => execute::commit(deps, env, info, auction_id, commitment),
+        ExecuteMsg::Reveal {
+            auction_id,
+            amount,
+            salt,
+        } => execute::reveal(deps, env, info, auction_id, amount, salt),
+        ExecuteMsg::Buy { auction_id } => execute::buy(deps, env, info, auction_id),
+        ExecuteMsg::BuyNow { auction_id } => execute::buy_now(deps, env, info, auction_id),
+        ExecuteMsg::SetMaxBid { auction_id, max } => {
+            execute::set_max_bid(deps, env, info, auction_id, max)
+        }
+        ExecuteMsg::UpdateBidders {
+            auction_id,
+            add,
+            remove,
+        } => execute::update_bidders(deps, info, auction_id, add, remove),
+        ExecuteMsg::RefundAll { auction_id, limit } => {
+            execute::refund_all(deps, env, auction_id, limit)
+        }
+        ExecuteMsg::ProposeOwner { new_owner } => execute::propose_owner(deps, info, new_owner),
+        ExecuteMsg::AcceptOwnership {} => execute::accept_ownership(deps, info),
+        ExecuteMsg::Cancel { auction_id } => execute::cancel(deps, env, info, auction_id),
+        ExecuteMsg::UpdateConfig {
+            fee_collector,
+            sellers,
+        } => execute::update_config(deps, info, fee_collector, sellers),
+        ExecuteMsg::ClearConfig {
+            fee_collector,
+            sellers,
+        } => execute::clear_config(deps, info, fee_collector, sellers),
+        ExecuteMsg::SetRate {
+            denom,
+            reference,
+            rate,
+        } => execute::set_rate(deps, info, denom, reference, rate),
+        ExecuteMsg::Receive(msg) => execute::receive(deps, env, info, msg),
+        ExecuteMsg::ReceiveNft(msg) => execute::receive_nft(deps, env, info, msg),
+    }
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn migrate(deps: DepsMut, _env: Env, _msg: MigrateMsg) -> Result<Response, ContractError> {
+    let version = ensure_from_older_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    // v0.1 contracts ran a single auction, which becomes the first one of the auction house
+    if version < Version::new(0, 2, 0) {
+        migrate::from_v0_1(deps)?;
     }
+
+    Ok(Response::new()
+        .add_attribute("action", "migrate")
+        .add_attribute("from_version", version.to_string())
+        .add_attribute("to_version", CONTRACT_VERSION))
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
-pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
     match msg {
-        QueryMsg::HighestBidder {} => to_binary(&query::highest_bid(deps)?),
-        QueryMsg::TotalBid { address: addr } => to_binary(&query::total_bid(deps, addr)?),
-        QueryMsg::IsBidClosed {} => to_binary(&query::is_closed(deps)?),
-        QueryMsg::BidWinner {} => to_binary(&query::bid_winner(deps)?),
+        QueryMsg::Config {} => to_json_binary(&query::config(deps)?),
+        QueryMsg::Rate { auction_id, denom } => {
+            to_json_binary(&query::rate(deps, auction_id, denom)?)
+        }
+        QueryMsg::Deposits {
+            auction_id,
+            address,
+        } => to_json_binary(&query::deposits(deps, auction_id, address)?),
+        QueryMsg::Eligibility {
+            auction_id,
+            address,
+        } => to_json_binary(&query::eligibility(deps, auction_id, address)?),
+        QueryMsg::ListBidders {
+            auction_id,
+            start_after,
+            limit,
+        } => to_json_binary(&query::list_bidders(deps, auction_id, start_after, limit)?),
+        QueryMsg::HighestBidder { auction_id } => {
+            to_json_binary(&query::highest_bid(deps, auction_id)?)
+        }
+        QueryMsg::TotalBid {
+            auction_id,
+            address: addr,
+        } => to_json_binary(&query::total_bid(deps, auction_id, addr)?),
+        QueryMsg::IsBidClosed { auction_id } => {
+            to_json_binary(&query::is_closed(deps, env, auction_id)?)
+        }
+        QueryMsg::BidWinner { auction_id } => {
+            to_json_binary(&query::bid_winner(deps, env, auction_id)?)
+        }
+        QueryMsg::Expiration { auction_id } => {
+            to_json_binary(&query::expiration(deps, env, auction_id)?)
+        }
+        QueryMsg::Auction { auction_id } => to_json_binary(&query::auction(deps, env, auction_id)?),
+        QueryMsg::ListAuctions {
+            status,
+            seller,
+            start_after,
+            limit,
+        } => to_json_binary(&query::list_auctions(
+            deps,
+            env,
+            status,
+            seller,
+            start_after,
+            limit,
+        )?),
+        QueryMsg::Commitment {
+            auction_id,
+            address,
+        } => to_json_binary(&query::commitment(deps, auction_id, address)?),
+        QueryMsg::CurrentPrice { auction_id } => {
+            to_json_binary(&query::current_price(deps, env, auction_id)?)
+        }
+        QueryMsg::OutstandingRefunds {
+            auction_id,
+            start_after,
+            limit,
+        } => to_json_binary(&query::outstanding_refunds(
+            deps,
+            auction_id,
+            start_after,
+            limit,
+        )?),
+        QueryMsg::ListBids {
+            auction_id,
+            start_after,
+            limit,
+            order,
+        } => to_json_binary(&query::list_bids(
+            deps,
+            auction_id,
+            start_after,
+            limit,
+            order,
+        )?),
+        QueryMsg::BidHistory {
+            auction_id,
+            start_after,
+            limit,
+        } => to_json_binary(&query::bid_history(deps, auction_id, start_after, limit)?),
     }
 }
@@ -1,4 +1,5 @@
-use cosmwasm_std::StdError;
+use cosmwasm_std::{StdError, Uint128};
+use cw_controllers::AdminError;
 use thiserror::Error;
 
 #[derive(Error, Debug, PartialEq)]
@@ -6,6 +7,9 @@ pub enum ContractError {
     #[error("{0}")]
     Std(#[from] StdError),
 
+    #[error("{0}")]
+    Admin(#[from] AdminError),
+
     #[error("Unauthorized")]
     Unauthorized {},
 
@@ -18,12 +22,21 @@ pub enum ContractError {
     #[error("Bid is too low")]
     BidTooLow {},
 
+    #[error("Total bid must be at least {min_bid}")]
+    BidBelowMinIncrement { min_bid: Uint128 },
+
     #[error("You are the highest bidder")]
     YouAreTheHighestBidder {},
 
     #[error("This bid is closed")]
     BidClosed {},
 
+    #[error("This bid has expired")]
+    BidExpired {},
+
+    #[error("Cannot finalize a bid before it expires")]
+    BidNotExpired {},
+
     #[error("Cannot retrieve funds while the bid is still open")]
     BidStillOpen {},
 
@@ -32,6 +45,57 @@ pub enum ContractError {
 
     #[error("The winner cannot retract bid")]
     WinnerCannotRetractBid {},
+
+    #[error("Cannot withdraw more than the total bid of {total_bid}")]
+    WithdrawAboveBid { total_bid: Uint128 },
+
+    #[error("Bids made in several denoms can only be withdrawn in full")]
+    PartialWithdrawMultiDenom {},
+
+    #[error("Bids raised by a proxy can only be withdrawn in full")]
+    PartialWithdrawProxy {},
+
+    #[error("Bids on sealed auctions must be committed and revealed")]
+    SealedBid {},
+
+    #[error("This auction does not take sealed bids")]
+    NotSealedBid {},
+
+    #[error("Bids can only be revealed once the commit phase is over")]
+    RevealTooEarly {},
+
+    #[error("There's nothing to reveal")]
+    NothingToReveal {},
+
+    #[error("Revealed bid does not match its commitment or exceeds its deposit")]
+    InvalidReveal {},
+
+    #[error("Dutch auctions are bought at their current price")]
+    DutchAuction {},
+
+    #[error("This auction is not a dutch auction")]
+    NotDutchAuction {},
+
+    #[error("This auction has no buy now price")]
+    NoBuyNowPrice {},
+
+    #[error("Bids are too close to the buy now price to buy now")]
+    BuyNowDisabled {},
+
+    #[error("Not allowed to bid on this auction")]
+    NotAllowedToBid {},
+
+    #[error("Total bid cannot be above {max_bid}")]
+    BidAboveMax { max_bid: Uint128 },
+
+    #[error("Max bid is not covered by the escrow")]
+    MaxBidNotCovered {},
+
+    #[error("Max bids cannot be set on auctions taking several denoms")]
+    MaxBidMultiDenom {},
+
+    #[error("This auction has no bidder allowlist")]
+    NoAllowlist {},
     // Add any other custom errors you like here.
     // Look at https://docs.rs/thiserror/1.0.21/thiserror/ for details.
 }
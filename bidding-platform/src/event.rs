@@ -0,0 +1,82 @@
+use cosmwasm_std::{Addr, Response, Uint128};
+use cw_utils::Event;
+
+/// Tracks bids placed on open auctions, by bidders, their proxies or buyers, emitted as
+/// `wasm-auction_bid`. Every amount is in the auction denom, bids made in other denoms being
+/// valued at their price.
+pub struct BidEvent<'a> {
+    pub auction_id: u64,
+    pub bidder: &'a Addr,
+    pub gross: Uint128,
+    pub commission: Uint128,
+    pub net: Uint128,
+    pub total_bid: Uint128,
+    pub denom: &'a str,
+    pub previous_leader: &'a str,
+}
+
+impl<'a> Event for BidEvent<'a> {
+    fn add_attributes(&self, rsp: &mut Response) {
+        rsp.events.push(
+            cosmwasm_std::Event::new("auction_bid")
+                .add_attribute("auction_id", self.auction_id.to_string())
+                .add_attribute("bidder", self.bidder)
+                .add_attribute("gross", self.gross)
+                .add_attribute("commission", self.commission)
+                .add_attribute("net", self.net)
+                .add_attribute("total_bid", self.total_bid)
+                .add_attribute("denom", self.denom)
+                .add_attribute("previous_leader", self.previous_leader),
+        );
+    }
+}
+
+/// Tracks auctions being settled, emitted as `wasm-auction_closed`. There is no winner when
+/// the item was not sold, in which case every amount is zero.
+pub struct ClosedEvent<'a> {
+    pub auction_id: u64,
+    pub winner: Option<&'a Addr>,
+    pub gross: Uint128,
+    pub commission: Uint128,
+    pub net: Uint128,
+    pub denom: &'a str,
+}
+
+impl<'a> Event for ClosedEvent<'a> {
+    fn add_attributes(&self, rsp: &mut Response) {
+        let mut event = cosmwasm_std::Event::new("auction_closed")
+            .add_attribute("auction_id", self.auction_id.to_string());
+        if let Some(winner) = self.winner {
+            event = event.add_attribute("winner", winner);
+        }
+        rsp.events.push(
+            event
+                .add_attribute("gross", self.gross)
+                .add_attribute("commission", self.commission)
+                .add_attribute("net", self.net)
+                .add_attribute("denom", self.denom),
+        );
+    }
+}
+
+/// Tracks bids and deposits sent back to bidders, emitted as `wasm-auction_refund`
+pub struct RefundEvent<'a> {
+    pub auction_id: u64,
+    pub bidder: &'a Addr,
+    pub recipient: &'a str,
+    pub amount: Uint128,
+    pub denom: &'a str,
+}
+
+impl<'a> Event for RefundEvent<'a> {
+    fn add_attributes(&self, rsp: &mut Response) {
+        rsp.events.push(
+            cosmwasm_std::Event::new("auction_refund")
+                .add_attribute("auction_id", self.auction_id.to_string())
+                .add_attribute("bidder", self.bidder)
+                .add_attribute("recipient", self.recipient)
+                .add_attribute("amount", self.amount)
+                .add_attribute("denom", self.denom),
+        );
+    }
+}
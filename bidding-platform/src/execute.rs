@@ -1,25 +1,233 @@
-use cosmwasm_std::{coins, BankMsg, DepsMut, Env, MessageInfo, Response, Uint128};
+use cosmwasm_std::{
+    coins, from_json, to_json_binary, Addr, BankMsg, Binary, Coin, CosmosMsg, Decimal, Deps,
+    DepsMut, Empty, Env, Event, MessageInfo, Order, Response, StdError, StdResult, Storage,
+    Uint128, WasmMsg,
+};
+use cw1155::{Cw1155ExecuteMsg, Cw1155ReceiveMsg};
+use cw20::{Cw20ExecuteMsg, Cw20ReceiveMsg, Denom};
+use cw4::Cw4Contract;
+use cw_utils::{Duration, Event as _, Expiration};
 
 use crate::{
+    event::{BidEvent, ClosedEvent, RefundEvent},
+    helpers::bid_commitment,
+    msg::{AuctionMsg, Cw721ExecuteMsg, Cw721ReceiveMsg, DepositMsg, ReceiveHook, ReceiveMsg},
     query,
-    state::{BidStatus, BIDS, CONFIG, WINNER},
+    state::{
+        auctions, bids, proxy_bids, AcceptedDenoms, Auction, AuctionItem, BidRecord, BidStatus,
+        Bidders, BuyNow, CommissionMode, Commitment, DutchAuction, Forfeit, ProxyBid, SealedBid,
+        Winner, ALLOWED_BIDDERS, AUCTION_COUNT, BID_COUNT, BID_HISTORY, COMMITMENTS, CONFIG,
+        DEPOSITS, OWNER, PENDING_OWNER, RATES, RUNNER_UP, UNREVEALED, WINNER,
+    },
     ContractError,
 };
 
-const DECIMALS: u32 = 6;
+// settings for batch refunds
+const MAX_REFUND_LIMIT: u32 = 30;
+const DEFAULT_REFUND_LIMIT: u32 = 10;
 
-pub fn bid(deps: DepsMut, _env: Env, info: MessageInfo) -> Result<Response, ContractError> {
+pub fn create_auction(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: AuctionMsg,
+) -> Result<Response, ContractError> {
     let cfg = CONFIG.load(deps.storage)?;
+    let contract_owner = query::contract_owner(deps.as_ref())?;
 
-    // contract owner cannot bid
-    if info.sender == cfg.contract_owner {
+    // only whitelisted sellers can create auctions when a whitelist is set
+    if !cfg.can_sell(&contract_owner, &info.sender) {
         return Err(ContractError::Unauthorized {});
     }
 
-    // can only bid on open bids
-    if cfg.status != BidStatus::Opened {
-        return Err(ContractError::BidClosed {});
+    let (auction_id, auction) = open_auction(deps, &env, info.sender, &info.funds, msg, None)?;
+
+    Ok(auction_created(auction_id, auction))
+}
+
+// opens an auction escrowing the cw721 or cw1155 item sent along
+fn deposit_item(
+    deps: DepsMut,
+    env: Env,
+    seller: Addr,
+    item: AuctionItem,
+    msg: &Binary,
+) -> Result<Response, ContractError> {
+    let cfg = CONFIG.load(deps.storage)?;
+    let contract_owner = query::contract_owner(deps.as_ref())?;
+
+    // only whitelisted sellers can create auctions when a whitelist is set
+    if !cfg.can_sell(&contract_owner, &seller) {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    match from_json(msg)? {
+        DepositMsg::CreateAuction(msg) => {
+            let (auction_id, auction) = open_auction(deps, &env, seller, &[], msg, Some(item))?;
+            Ok(auction_created(auction_id, auction))
+        }
     }
+}
+
+fn auction_created(auction_id: u64, auction: Auction) -> Response {
+    Response::new()
+        .add_attribute("action", "create_auction")
+        .add_attribute("auction_id", auction_id.to_string())
+        .add_attribute("commodity", auction.commodity)
+        .add_attribute("seller", auction.seller)
+        .add_attribute("denom", denom_to_string(&auction.denom))
+        .add_attribute("expiration", auction.expiration.to_string())
+}
+
+// saves a new auction, the seller is always its initial bidder
+pub fn open_auction(
+    deps: DepsMut,
+    env: &Env,
+    seller: Addr,
+    funds: &[Coin],
+    msg: AuctionMsg,
+    item: Option<AuctionItem>,
+) -> Result<(u64, Auction), ContractError> {
+    msg.validate()?;
+
+    let (denom, initial_bid) = match (msg.cw20_addr, msg.native_denom, funds) {
+        // cw20 tokens cannot be sent along, so the seller starts without a deposit
+        (Some(cw20_addr), None, []) => (
+            Denom::Cw20(deps.api.addr_validate(&cw20_addr)?),
+            Uint128::zero(),
+        ),
+        // auction must be opened with only one kind of native tokens
+        (None, None, [deposit]) => (Denom::Native(deposit.denom.clone()), deposit.amount),
+        // the escrowed item stands in for the deposit, so the seller starts without one
+        (None, Some(denom), []) if item.is_some() => (Denom::Native(denom), Uint128::zero()),
+        _ => return Err(ContractError::TooManyOrLittleNativeTokensSent {}),
+    };
+
+    let expiration = msg
+        .duration
+        .map(|duration| duration.after(&env.block))
+        .unwrap_or_default();
+
+    // sealed auctions start by committing bids, which are revealed after the expiration
+    let sealed = msg
+        .sealed
+        .map(|sealed| -> StdResult<_> {
+            Ok(SealedBid {
+                reveal_deadline: (expiration + sealed.reveal_period)?,
+                pricing: sealed.pricing,
+                forfeit: sealed.forfeit,
+            })
+        })
+        .transpose()?;
+
+    // dutch auctions start dropping their price right away, in the unit of their duration
+    let dutch = msg.dutch.map(|dutch| DutchAuction {
+        start_price: dutch.start_price,
+        floor_price: dutch.floor_price,
+        decay: dutch.decay,
+        start: match msg.duration {
+            Some(Duration::Height(_)) => Expiration::AtHeight(env.block.height),
+            _ => Expiration::AtTime(env.block.time),
+        },
+    });
+
+    let auction = Auction {
+        commodity: msg.commodity,
+        seller,
+        denom,
+        status: if sealed.is_some() {
+            BidStatus::Commit
+        } else {
+            BidStatus::Opened
+        },
+        expiration,
+        soft_close: msg.soft_close,
+        reserve_price: msg.reserve_price,
+        min_increment: msg.min_increment,
+        item,
+        sealed,
+        dutch,
+        buy_now: msg.buy_now_price.map(|price| BuyNow {
+            price,
+            cutoff: msg.buy_now_cutoff.unwrap_or(Decimal::one()),
+        }),
+        bidders: match (&msg.allowlist, &msg.bidder_group) {
+            (Some(_), _) => Some(Bidders::Allowlist),
+            (None, Some(group)) => {
+                Some(Bidders::Group(Cw4Contract(deps.api.addr_validate(group)?)))
+            }
+            (None, None) => None,
+        },
+        max_bid: msg.max_bid,
+        accepted_denoms: msg
+            .accepted_denoms
+            .map(|denoms| -> StdResult<_> {
+                Ok(AcceptedDenoms {
+                    denoms,
+                    oracle: msg
+                        .price_oracle
+                        .map(|oracle| deps.api.addr_validate(&oracle))
+                        .transpose()?,
+                })
+            })
+            .transpose()?,
+    };
+
+    // soft close extensions can never shorten the auction
+    if let Some(deadline) = auction.soft_close.as_ref().and_then(|sc| sc.hard_deadline) {
+        if deadline < auction.expiration {
+            return Err(
+                StdError::generic_err("Hard deadline cannot be before the bid expires").into(),
+            );
+        }
+    }
+
+    let auction_id = AUCTION_COUNT.may_load(deps.storage)?.unwrap_or_default() + 1;
+    AUCTION_COUNT.save(deps.storage, &auction_id)?;
+
+    auctions().save(deps.storage, auction_id, &auction)?;
+
+    for bidder in msg.allowlist.iter().flatten() {
+        let bidder = deps.api.addr_validate(bidder)?;
+        ALLOWED_BIDDERS.save(deps.storage, (auction_id, bidder), &Empty {})?;
+    }
+
+    WINNER.save(
+        deps.storage,
+        auction_id,
+        &Winner {
+            bidder: auction.seller.clone(),
+            amount: initial_bid,
+        },
+    )?;
+
+    bids().save(
+        deps.storage,
+        (auction_id, auction.seller.clone()),
+        &initial_bid,
+    )?;
+    if auction.accepted_denoms.is_some() && !initial_bid.is_zero() {
+        DEPOSITS.save(
+            deps.storage,
+            (
+                auction_id,
+                auction.seller.clone(),
+                denom_to_string(&auction.denom),
+            ),
+            &initial_bid,
+        )?;
+    }
+
+    Ok((auction_id, auction))
+}
+
+pub fn bid(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    auction_id: u64,
+) -> Result<Response, ContractError> {
+    let auction = auctions().load(deps.storage, auction_id)?;
 
     let funds = info.funds.clone();
 
@@ -28,117 +236,1778 @@ pub fn bid(deps: DepsMut, _env: Env, info: MessageInfo) -> Result<Response, Cont
         return Err(ContractError::TooManyOrLittleNativeTokensSent {});
     }
 
-    let highest_bid = query::highest_bid(deps.as_ref())?;
-
-    // need to bid in the initial bidder denom
-    if funds[0].denom != cfg.denom {
+    // need to bid in the initial bidder denom, or one the auction accepts besides it
+    if !auction.accepts(&funds[0].denom) {
         return Err(ContractError::WrongDenom {});
     }
 
+    let denom = Denom::Native(funds[0].denom.clone());
+    place_bid(
+        deps,
+        env,
+        auction_id,
+        auction,
+        info.sender,
+        denom,
+        funds[0].amount,
+    )
+}
+
+pub fn receive(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    hook: ReceiveHook,
+) -> Result<Response, ContractError> {
+    match hook {
+        ReceiveHook::Cw20(wrapper) => receive_cw20(deps, env, info, wrapper),
+        ReceiveHook::Cw1155(wrapper) => receive_cw1155(deps, env, info, wrapper),
+    }
+}
+
+pub fn receive_nft(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    wrapper: Cw721ReceiveMsg,
+) -> Result<Response, ContractError> {
+    let seller = deps.api.addr_validate(&wrapper.sender)?;
+    let item = AuctionItem::Cw721 {
+        contract: info.sender,
+        token_id: wrapper.token_id,
+    };
+
+    deposit_item(deps, env, seller, item, &wrapper.msg)
+}
+
+fn receive_cw1155(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    wrapper: Cw1155ReceiveMsg,
+) -> Result<Response, ContractError> {
+    // tokens minted straight to the contract have no owner, the minter is the seller then
+    let seller = deps
+        .api
+        .addr_validate(wrapper.from.as_ref().unwrap_or(&wrapper.operator))?;
+    let item = AuctionItem::Cw1155 {
+        contract: info.sender,
+        token_id: wrapper.token_id,
+        amount: wrapper.amount,
+    };
+
+    deposit_item(deps, env, seller, item, &wrapper.msg)
+}
+
+fn receive_cw20(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    wrapper: Cw20ReceiveMsg,
+) -> Result<Response, ContractError> {
+    let bidder = deps.api.addr_validate(&wrapper.sender)?;
+
+    match from_json(&wrapper.msg)? {
+        ReceiveMsg::Bid { auction_id } => {
+            let auction = auctions().load(deps.storage, auction_id)?;
+
+            // only the cw20 token the auction is held in can send bids
+            if auction.denom != Denom::Cw20(info.sender) {
+                return Err(ContractError::WrongDenom {});
+            }
+
+            let denom = auction.denom.clone();
+            place_bid(
+                deps,
+                env,
+                auction_id,
+                auction,
+                bidder,
+                denom,
+                wrapper.amount,
+            )
+        }
+        ReceiveMsg::Commit {
+            auction_id,
+            commitment,
+        } => {
+            let auction = auctions().load(deps.storage, auction_id)?;
+
+            // only the cw20 token the auction is held in can send deposits
+            if auction.denom != Denom::Cw20(info.sender) {
+                return Err(ContractError::WrongDenom {});
+            }
+
+            place_commitment(
+                deps,
+                env,
+                auction_id,
+                auction,
+                bidder,
+                commitment,
+                wrapper.amount,
+            )
+        }
+        ReceiveMsg::Buy { auction_id } => {
+            let auction = auctions().load(deps.storage, auction_id)?;
+
+            // only the cw20 token the auction is held in can buy it
+            if auction.denom != Denom::Cw20(info.sender) {
+                return Err(ContractError::WrongDenom {});
+            }
+
+            buy_dutch(deps, env, auction_id, auction, bidder, wrapper.amount)
+        }
+        ReceiveMsg::SetMaxBid { auction_id, max } => {
+            let auction = auctions().load(deps.storage, auction_id)?;
+
+            // only the cw20 token the auction is held in can send escrow
+            if auction.denom != Denom::Cw20(info.sender) {
+                return Err(ContractError::WrongDenom {});
+            }
+
+            place_max_bid(deps, env, auction_id, auction, bidder, max, wrapper.amount)
+        }
+        ReceiveMsg::BuyNow { auction_id } => {
+            let auction = auctions().load(deps.storage, auction_id)?;
+
+            // only the cw20 token the auction is held in can buy it
+            if auction.denom != Denom::Cw20(info.sender) {
+                return Err(ContractError::WrongDenom {});
+            }
+
+            buy_at_fixed_price(deps, env, auction_id, auction, bidder, wrapper.amount)
+        }
+    }
+}
+
+fn place_bid(
+    mut deps: DepsMut,
+    env: Env,
+    auction_id: u64,
+    mut auction: Auction,
+    bidder: Addr,
+    denom: Denom,
+    gross_bid: Uint128,
+) -> Result<Response, ContractError> {
+    let cfg = CONFIG.load(deps.storage)?;
+    let contract_owner = query::contract_owner(deps.as_ref())?;
+
+    ensure_biddable(deps.as_ref(), &env, auction_id, &auction, &bidder)?;
+
+    let highest_bid = query::highest_bid(deps.as_ref(), auction_id)?;
+
     // retrieve old bid amount for user
-    let old_bid = BIDS
-        .may_load(deps.storage, info.sender.clone())?
-        .unwrap_or(Uint128::zero());
+    let old_bid = bids()
+        .may_load(deps.storage, (auction_id, bidder.clone()))?
+        .unwrap_or_default();
 
-    // new bid without commision
-    let gross_bid = funds[0].amount;
+    // bids in other denoms are valued in the auction denom at the current price
+    let price = query::price(deps.as_ref(), &auction, &denom)?;
+    let gross_value = gross_bid.mul_floor(price);
 
-    // commission
-    let commission =
-        gross_bid * Uint128::from(cfg.commission) / (Uint128::from(10u128.pow(DECIMALS)));
+    // commission, unless only the winning bid pays it at close
+    let commission = match cfg.commission.mode {
+        CommissionMode::EveryBid => gross_bid.mul_floor(cfg.commission.rate(gross_value)),
+        CommissionMode::WinningBid => Uint128::zero(),
+    };
 
     // bid including commission
     let net_bid = gross_bid - commission;
+    let net_value = net_bid.mul_floor(price);
 
-    let total_bid = old_bid + net_bid;
+    let total_bid = old_bid + net_value;
 
     // old_bid + new_bid (minus commision) has to be higher than highest bid
     if total_bid <= highest_bid.total_bid {
         return Err(ContractError::BidTooLow {});
     }
 
-    // create bank message to be sent to contract owner with the commision paid by the bidder
-    let bank_msg = BankMsg::Send {
-        to_address: cfg.contract_owner.to_string(),
-        amount: coins(commission.into(), cfg.denom),
-    };
+    // but cannot go above the maximum bid of the auction
+    if let Some(max_bid) = auction.max_bid {
+        if total_bid > max_bid {
+            return Err(ContractError::BidAboveMax { max_bid });
+        }
+    }
+
+    // and raise it by at least the minimum increment
+    let min_bid = auction.min_bid(highest_bid.total_bid);
+    if total_bid < min_bid {
+        return Err(ContractError::BidBelowMinIncrement { min_bid });
+    }
 
     // save new winner to state
-    WINNER.save(deps.storage, &(info.sender.clone(), total_bid))?;
+    WINNER.save(
+        deps.storage,
+        auction_id,
+        &Winner {
+            bidder: bidder.clone(),
+            amount: total_bid,
+        },
+    )?;
 
     // add new bid to bids map
-    BIDS.save(deps.storage, info.sender.clone(), &total_bid)?;
+    bids().save(deps.storage, (auction_id, bidder.clone()), &total_bid)?;
 
-    Ok(Response::new()
+    // keeping track of the coins it was made in when the auction takes several denoms
+    if auction.accepted_denoms.is_some() {
+        DEPOSITS.update(
+            deps.storage,
+            (auction_id, bidder.clone(), denom_to_string(&denom)),
+            |deposit| -> StdResult<_> { Ok(deposit.unwrap_or_default() + net_bid) },
+        )?;
+    }
+
+    // and to the bid history, in the auction denom
+    record_bid(
+        deps.storage,
+        auction_id,
+        &BidRecord {
+            bidder: bidder.clone(),
+            gross: gross_value,
+            commission: gross_value - net_value,
+            net: net_value,
+            height: env.block.height,
+            time: env.block.time,
+            proxy: false,
+        },
+    )?;
+
+    let mut res = Response::new()
         .add_attribute("action", "bid")
-        .add_attribute("highest_bidder", info.sender)
-        .add_attribute("highest_bid", total_bid)
-        .add_message(bank_msg))
+        .add_attribute("auction_id", auction_id.to_string())
+        .add_attribute("highest_bidder", &bidder)
+        .add_attribute("highest_bid", total_bid);
+    BidEvent {
+        auction_id,
+        bidder: &bidder,
+        gross: gross_value,
+        commission: gross_value - net_value,
+        net: net_value,
+        total_bid,
+        denom: &denom_to_string(&auction.denom),
+        previous_leader: &highest_bid.addr,
+    }
+    .add_attributes(&mut res);
+
+    // send the commision paid by the bidder to the fee collector
+    if !commission.is_zero() {
+        res = res.add_message(send_tokens(
+            &denom,
+            cfg.fee_collector(&contract_owner),
+            commission,
+        )?);
+    }
+
+    // proxies of the bidders just outbid raise their bid right away if they can
+    res = resolve_proxies(deps.branch(), &env, auction_id, &auction, res)?;
+
+    Ok(extend_soft_close(
+        deps.storage,
+        &env,
+        auction_id,
+        &mut auction,
+        res,
+    )?)
 }
 
-pub fn close(deps: DepsMut, _env: Env, info: MessageInfo) -> Result<Response, ContractError> {
-    let mut cfg = CONFIG.load(deps.storage)?;
+pub fn set_max_bid(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    auction_id: u64,
+    max: Uint128,
+) -> Result<Response, ContractError> {
+    let auction = auctions().load(deps.storage, auction_id)?;
+
+    // the escrow can be topped up along, or the max bid moved within what is escrowed already
+    let deposit = match info.funds.as_slice() {
+        [] => Uint128::zero(),
+        [coin] if auction.denom == Denom::Native(coin.denom.clone()) => coin.amount,
+        [_] => return Err(ContractError::WrongDenom {}),
+        _ => return Err(ContractError::TooManyOrLittleNativeTokensSent {}),
+    };
+
+    place_max_bid(deps, env, auction_id, auction, info.sender, max, deposit)
+}
+
+fn place_max_bid(
+    mut deps: DepsMut,
+    env: Env,
+    auction_id: u64,
+    mut auction: Auction,
+    bidder: Addr,
+    max: Uint128,
+    deposit: Uint128,
+) -> Result<Response, ContractError> {
+    let cfg = CONFIG.load(deps.storage)?;
+    let contract_owner = query::contract_owner(deps.as_ref())?;
+
+    ensure_biddable(deps.as_ref(), &env, auction_id, &auction, &bidder)?;
+
+    // proxies raise bids out of an escrow held in the auction denom only
+    if auction.accepted_denoms.is_some() {
+        return Err(ContractError::MaxBidMultiDenom {});
+    }
+
+    // the escrow pays its commission once, as it is deposited, like any bid
+    let commission = match cfg.commission.mode {
+        CommissionMode::EveryBid => cfg.commission.of(deposit),
+        CommissionMode::WinningBid => Uint128::zero(),
+    };
+
+    let bid = bids()
+        .may_load(deps.storage, (auction_id, bidder.clone()))?
+        .unwrap_or_default();
+    let reserve = proxy_bids()
+        .may_load(deps.storage, (auction_id, bidder.clone()))?
+        .map_or(Uint128::zero(), |proxy| proxy.reserve)
+        + deposit
+        - commission;
+
+    // the escrow has to cover the max bid, which can never go below the current bid
+    if max > bid + reserve {
+        return Err(ContractError::MaxBidNotCovered {});
+    }
+    if max < bid {
+        return Err(ContractError::BidTooLow {});
+    }
+    if let Some(max_bid) = auction.max_bid {
+        if max > max_bid {
+            return Err(ContractError::BidAboveMax { max_bid });
+        }
+    }
+
+    // a new max bid has to be able to outbid the highest bid right away
+    let winner = WINNER.load(deps.storage, auction_id)?;
+    let min_bid = auction.min_bid(winner.amount);
+    if winner.bidder != bidder && max < min_bid {
+        return Err(ContractError::BidBelowMinIncrement { min_bid });
+    }
+
+    proxy_bids().save(
+        deps.storage,
+        (auction_id, bidder.clone()),
+        &ProxyBid { max, reserve },
+    )?;
+
+    let mut res = Response::new()
+        .add_attribute("action", "set_max_bid")
+        .add_attribute("auction_id", auction_id.to_string())
+        .add_attribute("bidder", bidder)
+        .add_attribute("max_bid", max);
+
+    if !commission.is_zero() {
+        res = res.add_message(send_tokens(
+            &auction.denom,
+            cfg.fee_collector(&contract_owner),
+            commission,
+        )?);
+    }
+
+    res = resolve_proxies(deps.branch(), &env, auction_id, &auction, res)?;
+
+    Ok(extend_soft_close(
+        deps.storage,
+        &env,
+        auction_id,
+        &mut auction,
+        res,
+    )?)
+}
 
-    // only contract owner can close bid
-    if info.sender != cfg.contract_owner {
+fn ensure_biddable(
+    deps: Deps,
+    env: &Env,
+    auction_id: u64,
+    auction: &Auction,
+    bidder: &Addr,
+) -> Result<(), ContractError> {
+    // seller cannot bid
+    if *bidder == auction.seller {
         return Err(ContractError::Unauthorized {});
     }
 
-    // cannot close a bid that's not open
-    if cfg.status != BidStatus::Opened {
+    // sealed bids go through commit and reveal instead
+    if auction.sealed.is_some() {
+        return Err(ContractError::SealedBid {});
+    }
+
+    // dutch auctions are bought instead
+    if auction.dutch.is_some() {
+        return Err(ContractError::DutchAuction {});
+    }
+
+    // can only bid on open bids
+    if auction.status != BidStatus::Opened {
         return Err(ContractError::BidClosed {});
     }
 
-    // update status to Closed and save to storage
-    cfg.status = BidStatus::Closed;
-    CONFIG.save(deps.storage, &cfg)?;
+    // cannot bid once the bid has expired, even if nobody finalized it yet
+    if auction.expiration.is_expired(&env.block) {
+        return Err(ContractError::BidExpired {});
+    }
+
+    ensure_eligible(deps, auction_id, auction, bidder)
+}
 
-    let winner = WINNER.load(deps.storage)?;
+// saves the bid to the bid history under the next bid id of the auction
+fn record_bid(storage: &mut dyn Storage, auction_id: u64, record: &BidRecord) -> StdResult<u64> {
+    let bid_id = BID_COUNT.may_load(storage, auction_id)?.unwrap_or_default() + 1;
+    BID_COUNT.save(storage, auction_id, &bid_id)?;
+    BID_HISTORY.save(storage, (auction_id, bid_id), record)?;
+    Ok(bid_id)
+}
 
-    // create bank message to send contract owner tokens to their address
-    let bank_msg = BankMsg::Send {
-        to_address: cfg.contract_owner.to_string(),
-        amount: coins(winner.1.into(), cfg.denom),
+// the proxy with the highest max bid outbids the highest bidder by the minimum increment, or
+// goes all in and gets outbid by the highest bidder's own proxy, until no proxy can outbid the
+// highest bidder anymore; every round exhausts a proxy, and only the top proxies by max bid are
+// looked at, so this stays cheap however many proxies there are
+fn resolve_proxies(
+    deps: DepsMut,
+    env: &Env,
+    auction_id: u64,
+    auction: &Auction,
+    mut res: Response,
+) -> StdResult<Response> {
+    // proxies of bidders removed from the allowlist or the group do not raise their bids anymore,
+    // and each bidder is only checked once
+    let mut eligibility: Vec<(Addr, bool)> = vec![];
+    let mut is_eligible = |deps: Deps, bidder: &Addr| -> StdResult<bool> {
+        if let Some((_, eligible)) = eligibility.iter().find(|(addr, _)| addr == bidder) {
+            return Ok(*eligible);
+        }
+        let eligible = query::is_eligible(deps, auction_id, auction, bidder)?;
+        eligibility.push((bidder.clone(), eligible));
+        Ok(eligible)
     };
 
-    Ok(Response::new().add_message(bank_msg))
+    loop {
+        let winner = WINNER.load(deps.storage, auction_id)?;
+        let min_bid = auction.min_bid(winner.amount);
+
+        // the highest bidder can go up to their own max bid, if they have a proxy
+        let winner_max =
+            match proxy_bids().may_load(deps.storage, (auction_id, winner.bidder.clone()))? {
+                Some(proxy) if is_eligible(deps.as_ref(), &winner.bidder)? => {
+                    proxy.max.max(winner.amount)
+                }
+                _ => winner.amount,
+            };
+
+        // the challenger is the eligible proxy with the highest max bid, the first set, by
+        // address, on equal max bids; the search ends at proxies that cannot outbid anyone
+        let mut challenger: Option<(Addr, ProxyBid)> = None;
+        let proxies = proxy_bids().idx.max.sub_prefix(auction_id).range(
+            deps.storage,
+            None,
+            None,
+            Order::Descending,
+        );
+        for proxy in proxies {
+            let ((_, bidder), proxy) = proxy?;
+            if proxy.max < min_bid
+                || challenger
+                    .as_ref()
+                    .is_some_and(|best| proxy.max < best.1.max)
+            {
+                break;
+            }
+            if bidder != winner.bidder && is_eligible(deps.as_ref(), &bidder)? {
+                challenger = Some((bidder, proxy));
+            }
+        }
+        let Some((challenger, proxy)) = challenger else {
+            return Ok(res);
+        };
+
+        if proxy.max > winner_max {
+            // the highest bidder goes all in, and the challenger still outbids them
+            if winner_max > winner.amount {
+                res = raise_bid(
+                    deps.storage,
+                    env,
+                    auction_id,
+                    auction,
+                    &winner.bidder,
+                    winner_max,
+                    res,
+                )?;
+            }
+            let amount = proxy.max.min(auction.min_bid(winner_max));
+            res = raise_bid(
+                deps.storage,
+                env,
+                auction_id,
+                auction,
+                &challenger,
+                amount,
+                res,
+            )?;
+        } else {
+            // the challenger goes all in, and the highest bidder's proxy outbids them
+            res = raise_bid(
+                deps.storage,
+                env,
+                auction_id,
+                auction,
+                &challenger,
+                proxy.max,
+                res,
+            )?;
+            let amount = winner_max.min(auction.min_bid(proxy.max));
+            res = raise_bid(
+                deps.storage,
+                env,
+                auction_id,
+                auction,
+                &winner.bidder,
+                amount,
+                res,
+            )?;
+        }
+    }
 }
 
-pub fn retract(
+// moves escrow from the bidder's proxy into their bid, up to a total bid of `amount`
+fn raise_bid(
+    storage: &mut dyn Storage,
+    env: &Env,
+    auction_id: u64,
+    auction: &Auction,
+    bidder: &Addr,
+    amount: Uint128,
+    mut res: Response,
+) -> StdResult<Response> {
+    let bid = bids()
+        .may_load(storage, (auction_id, bidder.clone()))?
+        .unwrap_or_default();
+    if amount <= bid {
+        return Ok(res);
+    }
+    let raise = amount - bid;
+
+    let mut proxy = proxy_bids().load(storage, (auction_id, bidder.clone()))?;
+    proxy.reserve = proxy.reserve.checked_sub(raise)?;
+    // used up proxies are dropped, so they are not looked at again
+    if amount >= proxy.max && proxy.reserve.is_zero() {
+        proxy_bids().remove(storage, (auction_id, bidder.clone()))?;
+    } else {
+        proxy_bids().save(storage, (auction_id, bidder.clone()), &proxy)?;
+    }
+    bids().save(storage, (auction_id, bidder.clone()), &amount)?;
+
+    // every raise makes the bidder the highest one, if only until the next raise
+    let previous_leader = WINNER.load(storage, auction_id)?.bidder;
+    WINNER.save(
+        storage,
+        auction_id,
+        &Winner {
+            bidder: bidder.clone(),
+            amount,
+        },
+    )?;
+
+    // commission was paid when the escrow was deposited
+    let bid_id = record_bid(
+        storage,
+        auction_id,
+        &BidRecord {
+            bidder: bidder.clone(),
+            gross: raise,
+            commission: Uint128::zero(),
+            net: raise,
+            height: env.block.height,
+            time: env.block.time,
+            proxy: true,
+        },
+    )?;
+
+    BidEvent {
+        auction_id,
+        bidder,
+        gross: raise,
+        commission: Uint128::zero(),
+        net: raise,
+        total_bid: amount,
+        denom: &denom_to_string(&auction.denom),
+        previous_leader: previous_leader.as_str(),
+    }
+    .add_attributes(&mut res);
+    Ok(res.add_event(
+        Event::new("auction_proxy_bid")
+            .add_attribute("auction_id", auction_id.to_string())
+            .add_attribute("bid_id", bid_id.to_string())
+            .add_attribute("bidder", bidder)
+            .add_attribute("raise", raise)
+            .add_attribute("total_bid", amount),
+    ))
+}
+
+// bids landing in the soft close window push the expiration out, up to the hard deadline
+fn extend_soft_close(
+    storage: &mut dyn Storage,
+    env: &Env,
+    auction_id: u64,
+    auction: &mut Auction,
+    mut res: Response,
+) -> StdResult<Response> {
+    if let Some(soft_close) = &auction.soft_close {
+        if auction.expiration <= soft_close.window.after(&env.block) {
+            let mut extended = (auction.expiration + soft_close.extension)?;
+            if let Some(deadline) = soft_close.hard_deadline {
+                if extended > deadline {
+                    extended = deadline;
+                }
+            }
+            if extended != auction.expiration {
+                auction.expiration = extended;
+                auctions().save(storage, auction_id, auction)?;
+                res = res.add_attribute("extended_expiration", auction.expiration.to_string());
+            }
+        }
+    }
+
+    Ok(res)
+}
+
+pub fn commit(
     deps: DepsMut,
-    _env: Env,
+    env: Env,
     info: MessageInfo,
-    receiver: Option<String>,
+    auction_id: u64,
+    commitment: Binary,
+) -> Result<Response, ContractError> {
+    let auction = auctions().load(deps.storage, auction_id)?;
+
+    // sent too little or too many tokens
+    if info.funds.len() != 1 {
+        return Err(ContractError::TooManyOrLittleNativeTokensSent {});
+    }
+
+    // need to deposit in the initial bidder denom
+    if auction.denom != Denom::Native(info.funds[0].denom.clone()) {
+        return Err(ContractError::WrongDenom {});
+    }
+
+    let deposit = info.funds[0].amount;
+    place_commitment(
+        deps,
+        env,
+        auction_id,
+        auction,
+        info.sender,
+        commitment,
+        deposit,
+    )
+}
+
+fn place_commitment(
+    deps: DepsMut,
+    env: Env,
+    auction_id: u64,
+    auction: Auction,
+    bidder: Addr,
+    hash: Binary,
+    deposit: Uint128,
+) -> Result<Response, ContractError> {
+    // seller cannot bid
+    if bidder == auction.seller {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    if auction.sealed.is_none() {
+        return Err(ContractError::NotSealedBid {});
+    }
+
+    match auction.phase(&env.block) {
+        BidStatus::Commit => {}
+        BidStatus::Closed | BidStatus::Cancelled => return Err(ContractError::BidClosed {}),
+        _ => return Err(ContractError::BidExpired {}),
+    }
+
+    ensure_eligible(deps.as_ref(), auction_id, &auction, &bidder)?;
+
+    // committing again replaces the hash and tops up the deposit
+    let old_deposit = COMMITMENTS
+        .may_load(deps.storage, (auction_id, bidder.clone()))?
+        .map(|commitment| commitment.deposit)
+        .unwrap_or_default();
+    let commitment = Commitment {
+        hash,
+        deposit: old_deposit + deposit,
+    };
+
+    // deposits cover the bid, so they cannot go above the maximum bid either
+    if let Some(max_bid) = auction.max_bid {
+        if commitment.deposit > max_bid {
+            return Err(ContractError::BidAboveMax { max_bid });
+        }
+    }
+    COMMITMENTS.save(deps.storage, (auction_id, bidder.clone()), &commitment)?;
+
+    UNREVEALED.update(deps.storage, auction_id, |unrevealed| -> StdResult<_> {
+        Ok(unrevealed.unwrap_or_default() + deposit)
+    })?;
+
+    Ok(Response::new()
+        .add_attribute("action", "commit")
+        .add_attribute("auction_id", auction_id.to_string())
+        .add_attribute("bidder", bidder)
+        .add_attribute("deposit", commitment.deposit))
+}
+
+pub fn reveal(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    auction_id: u64,
+    amount: Uint128,
+    salt: String,
+) -> Result<Response, ContractError> {
+    let auction = auctions().load(deps.storage, auction_id)?;
+
+    if auction.sealed.is_none() {
+        return Err(ContractError::NotSealedBid {});
+    }
+
+    match auction.phase(&env.block) {
+        BidStatus::Reveal => {}
+        BidStatus::Closed | BidStatus::Cancelled => return Err(ContractError::BidClosed {}),
+        _ => return Err(ContractError::RevealTooEarly {}),
+    }
+
+    let commitment = COMMITMENTS
+        .may_load(deps.storage, (auction_id, info.sender.clone()))?
+        .ok_or(ContractError::NothingToReveal {})?;
+
+    // the deposit has to cover the bid, otherwise it is forfeited like an unrevealed one
+    if bid_commitment(auction_id, &info.sender, amount, &salt) != commitment.hash
+        || amount > commitment.deposit
+    {
+        return Err(ContractError::InvalidReveal {});
+    }
+
+    // the whole deposit is refundable until the auction is settled
+    COMMITMENTS.remove(deps.storage, (auction_id, info.sender.clone()));
+    UNREVEALED.update(deps.storage, auction_id, |unrevealed| -> StdResult<_> {
+        Ok(unrevealed.unwrap_or_default() - commitment.deposit)
+    })?;
+    bids().save(
+        deps.storage,
+        (auction_id, info.sender.clone()),
+        &commitment.deposit,
+    )?;
+
+    // keep track of the two highest bids, ties go to whoever revealed first
+    let winner = WINNER.load(deps.storage, auction_id)?;
+    if amount > winner.amount {
+        WINNER.save(
+            deps.storage,
+            auction_id,
+            &Winner {
+                bidder: info.sender.clone(),
+                amount,
+            },
+        )?;
+        RUNNER_UP.save(deps.storage, auction_id, &winner.amount)?;
+    } else if amount
+        > RUNNER_UP
+            .may_load(deps.storage, auction_id)?
+            .unwrap_or_default()
+    {
+        RUNNER_UP.save(deps.storage, auction_id, &amount)?;
+    }
+
+    Ok(Response::new()
+        .add_attribute("action", "reveal")
+        .add_attribute("auction_id", auction_id.to_string())
+        .add_attribute("bidder", info.sender)
+        .add_attribute("amount", amount))
+}
+
+pub fn buy(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    auction_id: u64,
+) -> Result<Response, ContractError> {
+    let auction = auctions().load(deps.storage, auction_id)?;
+
+    // sent too little or too many tokens
+    if info.funds.len() != 1 {
+        return Err(ContractError::TooManyOrLittleNativeTokensSent {});
+    }
+
+    // need to pay in the initial bidder denom
+    if auction.denom != Denom::Native(info.funds[0].denom.clone()) {
+        return Err(ContractError::WrongDenom {});
+    }
+
+    let paid = info.funds[0].amount;
+    buy_dutch(deps, env, auction_id, auction, info.sender, paid)
+}
+
+// closes a dutch auction at its current price
+fn buy_dutch(
+    deps: DepsMut,
+    env: Env,
+    auction_id: u64,
+    auction: Auction,
+    buyer: Addr,
+    paid: Uint128,
+) -> Result<Response, ContractError> {
+    let Some(dutch) = &auction.dutch else {
+        return Err(ContractError::NotDutchAuction {});
+    };
+
+    ensure_buyable(&env, &auction, &buyer)?;
+
+    let price = dutch.price(&auction.expiration, &env.block);
+    sell(deps, env, auction_id, auction, buyer, paid, price)
+}
+
+pub fn buy_now(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    auction_id: u64,
+) -> Result<Response, ContractError> {
+    let auction = auctions().load(deps.storage, auction_id)?;
+
+    // sent too little or too many tokens
+    if info.funds.len() != 1 {
+        return Err(ContractError::TooManyOrLittleNativeTokensSent {});
+    }
+
+    // need to pay in the initial bidder denom
+    if auction.denom != Denom::Native(info.funds[0].denom.clone()) {
+        return Err(ContractError::WrongDenom {});
+    }
+
+    let paid = info.funds[0].amount;
+    buy_at_fixed_price(deps, env, auction_id, auction, info.sender, paid)
+}
+
+// closes an auction at its buy now price, as long as bids did not get too close to it
+fn buy_at_fixed_price(
+    deps: DepsMut,
+    env: Env,
+    auction_id: u64,
+    auction: Auction,
+    buyer: Addr,
+    paid: Uint128,
+) -> Result<Response, ContractError> {
+    let Some(buy_now) = &auction.buy_now else {
+        return Err(ContractError::NoBuyNowPrice {});
+    };
+
+    ensure_buyable(&env, &auction, &buyer)?;
+
+    let winner = WINNER.load(deps.storage, auction_id)?;
+    if !buy_now.available(winner.amount) {
+        return Err(ContractError::BuyNowDisabled {});
+    }
+
+    let price = buy_now.price;
+    sell(deps, env, auction_id, auction, buyer, paid, price)
+}
+
+fn ensure_buyable(env: &Env, auction: &Auction, buyer: &Addr) -> Result<(), ContractError> {
+    // seller cannot buy
+    if *buyer == auction.seller {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    // can only buy open auctions
+    if auction.status != BidStatus::Opened {
+        return Err(ContractError::BidClosed {});
+    }
+
+    // cannot buy once the auction has expired, even if nobody finalized it yet
+    if auction.expiration.is_expired(&env.block) {
+        return Err(ContractError::BidExpired {});
+    }
+
+    Ok(())
+}
+
+// closes the auction right away, paying the seller minus commission, and every other bidder
+// can retract their bid like any outbid bidder
+fn sell(
+    deps: DepsMut,
+    env: Env,
+    auction_id: u64,
+    mut auction: Auction,
+    buyer: Addr,
+    paid: Uint128,
+    price: Uint128,
 ) -> Result<Response, ContractError> {
     let cfg = CONFIG.load(deps.storage)?;
+    let contract_owner = query::contract_owner(deps.as_ref())?;
+
+    ensure_eligible(deps.as_ref(), auction_id, &auction, &buyer)?;
+
+    if paid < price {
+        return Err(ContractError::BidTooLow {});
+    }
+
+    auction.status = BidStatus::Closed;
+    auctions().save(deps.storage, auction_id, &auction)?;
+
+    // the buyer pays the whole price, so whatever they bid before is sent back with the change
+    let previous_bid = take_refund(deps.storage, auction_id, &auction, &buyer)?;
+
+    let previous_leader = WINNER.load(deps.storage, auction_id)?.bidder;
+    WINNER.save(
+        deps.storage,
+        auction_id,
+        &Winner {
+            bidder: buyer.clone(),
+            amount: price,
+        },
+    )?;
+
+    let mut res = Response::new()
+        .add_attribute("action", "buy")
+        .add_attribute("auction_id", auction_id.to_string())
+        .add_attribute("buyer", &buyer)
+        .add_attribute("price", price);
+
+    // buying is bidding the whole price at once
+    let commission = cfg.commission.of(price);
+    BidEvent {
+        auction_id,
+        bidder: &buyer,
+        gross: price,
+        commission,
+        net: price - commission,
+        total_bid: price,
+        denom: &denom_to_string(&auction.denom),
+        previous_leader: previous_leader.as_str(),
+    }
+    .add_attributes(&mut res);
 
-    // users cannot retract any bids until the bid is closed by the owner
-    if cfg.status != BidStatus::Closed {
+    if let Some(item) = &auction.item {
+        res = res.add_message(send_item(&env, item, &buyer)?);
+    }
+
+    // send the commission to the fee collector and the rest of the price to the seller
+    if !commission.is_zero() {
+        res = res.add_message(send_tokens(
+            &auction.denom,
+            cfg.fee_collector(&contract_owner),
+            commission,
+        )?);
+    }
+    if !(price - commission).is_zero() {
+        res = res.add_message(send_tokens(
+            &auction.denom,
+            &auction.seller,
+            price - commission,
+        )?);
+    }
+
+    // send the change back to the buyer
+    let change = paid - price + previous_bid;
+    if !change.is_zero() {
+        res = res.add_message(send_tokens(&auction.denom, &buyer, change)?);
+    }
+
+    Ok(auction_closed(
+        res,
+        auction_id,
+        &auction,
+        Some(&buyer),
+        price,
+        commission,
+    ))
+}
+
+pub fn close(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    auction_id: u64,
+) -> Result<Response, ContractError> {
+    let auction = auctions().load(deps.storage, auction_id)?;
+
+    // only the seller can close their auction
+    if info.sender != auction.seller {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    // cannot close a bid that's not open
+    if auction.is_settled() {
+        return Err(ContractError::BidClosed {});
+    }
+
+    // timed auctions run until they expire, so the seller cannot end them early while a friend is
+    // winning, and sealed bids have to be revealed before the auction can be closed
+    let timed = auction.expiration != Expiration::Never {} || auction.sealed.is_some();
+    if timed && !auction.is_closed(&env.block) {
         return Err(ContractError::BidStillOpen {});
     }
 
-    let winner = WINNER.load(deps.storage)?;
-    if winner.0 == info.sender {
-        return Err(ContractError::WinnerCannotRetractBid {});
+    let res = settle(deps, env, auction_id, auction)?;
+    Ok(res
+        .add_attribute("action", "close")
+        .add_attribute("auction_id", auction_id.to_string()))
+}
+
+pub fn finalize(deps: DepsMut, env: Env, auction_id: u64) -> Result<Response, ContractError> {
+    let auction = auctions().load(deps.storage, auction_id)?;
+
+    // cannot finalize a bid that's not open
+    if auction.is_settled() {
+        return Err(ContractError::BidClosed {});
     }
 
-    let bid = BIDS
-        .may_load(deps.storage, info.sender.clone())?
-        .unwrap_or(Uint128::zero());
+    // anyone can finalize, but only once the bid has expired or its bids were revealed
+    if !auction.is_closed(&env.block) {
+        return Err(ContractError::BidNotExpired {});
+    }
+
+    let res = settle(deps, env, auction_id, auction)?;
+    Ok(res
+        .add_attribute("action", "finalize")
+        .add_attribute("auction_id", auction_id.to_string()))
+}
+
+// closes the auction and sends the winning bid to the seller if the reserve price was met
+fn settle(
+    deps: DepsMut,
+    env: Env,
+    auction_id: u64,
+    mut auction: Auction,
+) -> Result<Response, ContractError> {
+    // update status to Closed and save to storage
+    auction.status = BidStatus::Closed;
+    auctions().save(deps.storage, auction_id, &auction)?;
+
+    let winner = WINNER.load(deps.storage, auction_id)?;
+    let price = query::winning_price(deps.as_ref(), auction_id, &auction)?;
+    // the item and the payment both follow this single decision
+    let sold = auction.is_sold(&winner.bidder, price);
+
+    let mut res = Response::new();
+
+    // the escrowed item goes to the winner, which is still the seller if nobody outbid them,
+    // and back to the seller if the auction did not sell
+    if let Some(item) = &auction.item {
+        let recipient = if sold {
+            &winner.bidder
+        } else {
+            &auction.seller
+        };
+        res = res.add_message(send_item(&env, item, recipient)?);
+    }
+
+    if let Some(sealed) = &auction.sealed {
+        return settle_sealed(
+            deps,
+            auction_id,
+            &auction,
+            &sealed.forfeit,
+            sold.then_some(winner.bidder),
+            price,
+            res,
+        );
+    }
+
+    // no sale, every bidder including the highest one can retract their bid
+    if !sold {
+        return Ok(auction_closed(
+            res,
+            auction_id,
+            &auction,
+            None,
+            Uint128::zero(),
+            Uint128::zero(),
+        ));
+    }
 
-    if bid == Uint128::zero() {
+    // auctions may start without a deposit from the seller, so there may be nothing to pay
+    if winner.amount.is_zero() {
+        return Ok(auction_closed(
+            res,
+            auction_id,
+            &auction,
+            Some(&winner.bidder),
+            Uint128::zero(),
+            Uint128::zero(),
+        ));
+    }
+
+    // the seller getting their own initial bid back does not pay commission
+    let cfg = CONFIG.load(deps.storage)?;
+    let contract_owner = query::contract_owner(deps.as_ref())?;
+    let rate = match cfg.commission.mode {
+        CommissionMode::WinningBid if winner.bidder != auction.seller => {
+            cfg.commission.rate(winner.amount)
+        }
+        _ => Decimal::zero(),
+    };
+
+    // send the winning bid to the seller, in whatever it was bid in
+    let coins = bid_coins(
+        deps.storage,
+        auction_id,
+        &auction,
+        &winner.bidder,
+        winner.amount,
+    )?;
+    let mut commissions = Uint128::zero();
+    for (denom, amount) in coins {
+        let commission = amount.mul_floor(rate);
+        if !commission.is_zero() {
+            // reported in the auction denom, at the current price of the denom it was charged in
+            let price = query::price(deps.as_ref(), &auction, &denom)?;
+            commissions += commission.mul_floor(price);
+            res = res.add_message(send_tokens(
+                &denom,
+                cfg.fee_collector(&contract_owner),
+                commission,
+            )?);
+        }
+        let proceeds = amount - commission;
+        if !proceeds.is_zero() {
+            res = res.add_message(send_tokens(&denom, &auction.seller, proceeds)?);
+        }
+    }
+
+    // prices may have moved since the bids were valued, the commission never exceeds the bid
+    Ok(auction_closed(
+        res,
+        auction_id,
+        &auction,
+        Some(&winner.bidder),
+        winner.amount,
+        commissions.min(winner.amount),
+    ))
+}
+
+// what the winner paid, if the item was sold, and how it was split between fees and the seller
+fn auction_closed(
+    mut res: Response,
+    auction_id: u64,
+    auction: &Auction,
+    winner: Option<&Addr>,
+    price: Uint128,
+    commission: Uint128,
+) -> Response {
+    ClosedEvent {
+        auction_id,
+        winner,
+        gross: price,
+        commission,
+        net: price - commission,
+        denom: &denom_to_string(&auction.denom),
+    }
+    .add_attributes(&mut res);
+    res
+}
+
+// sealed bids are only charged once settled: the winner pays the price out of their deposit,
+// commission included, and unrevealed deposits go to whoever the forfeit rule says
+fn settle_sealed(
+    deps: DepsMut,
+    auction_id: u64,
+    auction: &Auction,
+    forfeit: &Forfeit,
+    winner: Option<Addr>,
+    price: Uint128,
+    mut res: Response,
+) -> Result<Response, ContractError> {
+    let cfg = CONFIG.load(deps.storage)?;
+    let contract_owner = query::contract_owner(deps.as_ref())?;
+
+    let unrevealed = UNREVEALED
+        .may_load(deps.storage, auction_id)?
+        .unwrap_or_default();
+    let beneficiary = match forfeit {
+        // refunded through retract like any other bid
+        Forfeit::Bidder => None,
+        Forfeit::Seller => Some(&auction.seller),
+        Forfeit::ContractOwner => Some(&contract_owner),
+    };
+    if let Some(beneficiary) = beneficiary {
+        if !unrevealed.is_zero() {
+            res = res.add_message(send_tokens(&auction.denom, beneficiary, unrevealed)?);
+        }
+    }
+
+    // no sale, every revealed bidder and the seller get their deposits back
+    let Some(winner) = winner else {
+        return Ok(auction_closed(
+            res,
+            auction_id,
+            auction,
+            None,
+            Uint128::zero(),
+            Uint128::zero(),
+        ));
+    };
+
+    // the rest of the winner's deposit stays refundable
+    bids().update(
+        deps.storage,
+        (auction_id, winner.clone()),
+        |deposit| -> StdResult<_> { Ok(deposit.unwrap_or_default().checked_sub(price)?) },
+    )?;
+
+    // send the commission to the fee collector and the rest of the price to the seller
+    let commission = cfg.commission.of(price);
+    if !commission.is_zero() {
+        res = res.add_message(send_tokens(
+            &auction.denom,
+            cfg.fee_collector(&contract_owner),
+            commission,
+        )?);
+    }
+    res = res.add_message(send_tokens(
+        &auction.denom,
+        &auction.seller,
+        price - commission,
+    )?);
+
+    Ok(auction_closed(
+        res,
+        auction_id,
+        auction,
+        Some(&winner),
+        price,
+        commission,
+    ))
+}
+
+pub fn retract(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    auction_id: u64,
+    receiver: Option<String>,
+) -> Result<Response, ContractError> {
+    let auction = auctions().load(deps.storage, auction_id)?;
+
+    ensure_refundable(&auction, &env)?;
+
+    // the winner's bid has been paid to the seller, unless the reserve price was not met,
+    // so they only get back what their proxy did not use
+    let winner = WINNER.load(deps.storage, auction_id)?;
+    let bid = if auction.is_winning_bid(&winner, &info.sender) {
+        let reserve = take_proxy_reserve(deps.storage, auction_id, &info.sender)?;
+        if reserve.is_zero() {
+            return Err(ContractError::WinnerCannotRetractBid {});
+        }
+        reserve
+    } else {
+        take_refund(deps.storage, auction_id, &auction, &info.sender)?
+    };
+
+    if bid.is_zero() {
         return Err(ContractError::NothingToRetract {});
     };
     let recipient = receiver.unwrap_or(info.sender.to_string());
 
-    // create bank message to send funds to losing bidders (minus commissions)
-    let bank_msg = BankMsg::Send {
-        to_address: recipient,
-        amount: coins(bid.into(), cfg.denom),
+    let mut res = Response::new()
+        .add_attribute("action", "retract")
+        .add_attribute("auction_id", auction_id.to_string())
+        .add_attribute("bidder", &info.sender)
+        .add_attribute("amount", bid);
+
+    // send funds to losing bidders (minus commissions)
+    for (denom, amount) in bid_coins(deps.storage, auction_id, &auction, &info.sender, bid)? {
+        res = send_refund(res, auction_id, &info.sender, &recipient, &denom, amount)?;
+    }
+
+    Ok(res)
+}
+
+// the highest bidder cannot withdraw, so withdrawals never change who leads the auction
+pub fn withdraw(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    auction_id: u64,
+    amount: Option<Uint128>,
+    receiver: Option<String>,
+) -> Result<Response, ContractError> {
+    let auction = auctions().load(deps.storage, auction_id)?;
+
+    // sealed deposits can only be refunded once the auction is settled
+    if auction.sealed.is_some() {
+        return Err(ContractError::SealedBid {});
+    }
+
+    // bids on closed auctions are retracted instead
+    if auction.is_closed(&env.block) {
+        return Err(ContractError::BidClosed {});
+    }
+
+    let winner = WINNER.load(deps.storage, auction_id)?;
+    if winner.bidder == info.sender {
+        return Err(ContractError::YouAreTheHighestBidder {});
+    }
+
+    let total_bid = bids()
+        .may_load(deps.storage, (auction_id, info.sender.clone()))?
+        .unwrap_or_default();
+    let amount = amount.unwrap_or(total_bid);
+    if amount > total_bid {
+        return Err(ContractError::WithdrawAboveBid { total_bid });
+    }
+
+    // deposits in several denoms cannot be split by value
+    if auction.accepted_denoms.is_some() && amount != total_bid {
+        return Err(ContractError::PartialWithdrawMultiDenom {});
+    }
+
+    // the proxy escrow has to keep covering the max bid, so it only goes along with the whole bid
+    let proxy = proxy_bids().has(deps.storage, (auction_id, info.sender.clone()));
+    if proxy && amount != total_bid {
+        return Err(ContractError::PartialWithdrawProxy {});
+    }
+
+    let refund = if amount == total_bid {
+        bids().remove(deps.storage, (auction_id, info.sender.clone()))?;
+        amount + take_proxy_reserve(deps.storage, auction_id, &info.sender)?
+    } else {
+        bids().save(
+            deps.storage,
+            (auction_id, info.sender.clone()),
+            &(total_bid - amount),
+        )?;
+        amount
     };
 
-    // set bid balance to 0 so users cannot retract funds more than once
-    BIDS.save(deps.storage, info.sender, &Uint128::zero())?;
+    if refund.is_zero() {
+        return Err(ContractError::NothingToRetract {});
+    }
+    let recipient = receiver.unwrap_or(info.sender.to_string());
 
-    Ok(Response::new().add_message(bank_msg))
+    let mut res = Response::new()
+        .add_attribute("action", "withdraw")
+        .add_attribute("auction_id", auction_id.to_string())
+        .add_attribute("bidder", &info.sender)
+        .add_attribute("amount", refund);
+    for (denom, amount) in bid_coins(deps.storage, auction_id, &auction, &info.sender, refund)? {
+        res = send_refund(res, auction_id, &info.sender, &recipient, &denom, amount)?;
+    }
+
+    Ok(res)
+}
+
+pub fn refund_all(
+    deps: DepsMut,
+    env: Env,
+    auction_id: u64,
+    limit: Option<u32>,
+) -> Result<Response, ContractError> {
+    let auction = auctions().load(deps.storage, auction_id)?;
+
+    ensure_refundable(&auction, &env)?;
+
+    let limit = limit.unwrap_or(DEFAULT_REFUND_LIMIT).min(MAX_REFUND_LIMIT) as usize;
+    let winner = WINNER.load(deps.storage, auction_id)?;
+
+    // refunded entries are removed, so every batch starts over from the first bidder left,
+    // and the winner is only refunded what their proxy did not use
+    let winner_reserve = proxy_bids().has(deps.storage, (auction_id, winner.bidder.clone()));
+    let mut bidders = bids()
+        .prefix(auction_id)
+        .keys(deps.storage, None, None, Order::Ascending)
+        .filter(|bidder| {
+            bidder.as_ref().map_or(true, |bidder| {
+                winner_reserve || !auction.is_winning_bid(&winner, bidder)
+            })
+        })
+        .take(limit)
+        .collect::<StdResult<Vec<_>>>()?;
+
+    if auction.refunds_unrevealed() {
+        let unrevealed = COMMITMENTS
+            .prefix(auction_id)
+            .keys(deps.storage, None, None, Order::Ascending)
+            .take(limit - bidders.len())
+            .collect::<StdResult<Vec<_>>>()?;
+        bidders.extend(unrevealed);
+    }
+
+    let mut res = Response::new()
+        .add_attribute("action", "refund_all")
+        .add_attribute("auction_id", auction_id.to_string())
+        .add_attribute("refunded", bidders.len().to_string());
+
+    for bidder in bidders {
+        let refund = if auction.is_winning_bid(&winner, &bidder) {
+            take_proxy_reserve(deps.storage, auction_id, &bidder)?
+        } else {
+            take_refund(deps.storage, auction_id, &auction, &bidder)?
+        };
+        for (denom, amount) in bid_coins(deps.storage, auction_id, &auction, &bidder, refund)? {
+            res = send_refund(res, auction_id, &bidder, bidder.as_str(), &denom, amount)?;
+        }
+    }
+
+    Ok(res)
+}
+
+pub fn propose_owner(
+    deps: DepsMut,
+    info: MessageInfo,
+    new_owner: String,
+) -> Result<Response, ContractError> {
+    OWNER.assert_admin(deps.as_ref(), &info.sender)?;
+
+    // the new owner only takes over once they accept, proposing again replaces the proposal
+    let new_owner = deps.api.addr_validate(&new_owner)?;
+    PENDING_OWNER.save(deps.storage, &new_owner)?;
+
+    Ok(Response::new().add_event(
+        Event::new("auction_ownership_proposed")
+            .add_attribute("owner", info.sender)
+            .add_attribute("pending_owner", new_owner),
+    ))
+}
+
+pub fn accept_ownership(deps: DepsMut, info: MessageInfo) -> Result<Response, ContractError> {
+    // only the proposed owner can accept the ownership
+    let pending_owner = PENDING_OWNER.may_load(deps.storage)?;
+    if pending_owner.as_ref() != Some(&info.sender) {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let previous_owner = query::contract_owner(deps.as_ref())?;
+    PENDING_OWNER.remove(deps.storage);
+    OWNER.set(deps, Some(info.sender.clone()))?;
+
+    Ok(Response::new().add_event(
+        Event::new("auction_ownership_accepted")
+            .add_attribute("previous_owner", previous_owner)
+            .add_attribute("owner", info.sender),
+    ))
+}
+
+// the auction ends without a sale: the item goes back to the seller and every bidder, the
+// highest one and the seller included, can retract their bid, minus commissions already paid
+pub fn cancel(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    auction_id: u64,
+) -> Result<Response, ContractError> {
+    OWNER.assert_admin(deps.as_ref(), &info.sender)?;
+
+    let mut auction = auctions().load(deps.storage, auction_id)?;
+
+    // cannot cancel an auction that was already settled
+    if auction.is_settled() {
+        return Err(ContractError::BidClosed {});
+    }
+
+    auction.status = BidStatus::Cancelled;
+    auctions().save(deps.storage, auction_id, &auction)?;
+
+    let mut res = Response::new().add_event(
+        Event::new("auction_cancelled")
+            .add_attribute("auction_id", auction_id.to_string())
+            .add_attribute("seller", &auction.seller),
+    );
+
+    if let Some(item) = &auction.item {
+        res = res.add_message(send_item(&env, item, &auction.seller)?);
+    }
+
+    Ok(res)
+}
+
+// only settings that do not change what open auctions owe anyone can be updated,
+// fields left unset keep their current value
+pub fn update_config(
+    deps: DepsMut,
+    info: MessageInfo,
+    fee_collector: Option<String>,
+    sellers: Option<Vec<String>>,
+) -> Result<Response, ContractError> {
+    OWNER.assert_admin(deps.as_ref(), &info.sender)?;
+
+    let mut cfg = CONFIG.load(deps.storage)?;
+    let mut event = Event::new("auction_config_updated");
+
+    if let Some(fee_collector) = fee_collector {
+        let fee_collector = deps.api.addr_validate(&fee_collector)?;
+        event = event.add_attribute("fee_collector", &fee_collector);
+        cfg.fee_collector = Some(fee_collector);
+    }
+    if let Some(sellers) = sellers {
+        let sellers = sellers
+            .iter()
+            .map(|seller| deps.api.addr_validate(seller))
+            .collect::<StdResult<Vec<_>>>()?;
+        let list = sellers.iter().map(Addr::as_str).collect::<Vec<_>>();
+        event = event.add_attribute("sellers", list.join(","));
+        cfg.sellers = Some(sellers);
+    }
+
+    CONFIG.save(deps.storage, &cfg)?;
+
+    Ok(Response::new().add_event(event))
+}
+
+pub fn clear_config(
+    deps: DepsMut,
+    info: MessageInfo,
+    fee_collector: bool,
+    sellers: bool,
+) -> Result<Response, ContractError> {
+    OWNER.assert_admin(deps.as_ref(), &info.sender)?;
+
+    let mut cfg = CONFIG.load(deps.storage)?;
+    let mut event = Event::new("auction_config_cleared");
+
+    if fee_collector {
+        event = event.add_attribute("cleared", "fee_collector");
+        cfg.fee_collector = None;
+    }
+    if sellers {
+        event = event.add_attribute("cleared", "sellers");
+        cfg.sellers = None;
+    }
+
+    CONFIG.save(deps.storage, &cfg)?;
+
+    Ok(Response::new().add_event(event))
+}
+
+pub fn set_rate(
+    deps: DepsMut,
+    info: MessageInfo,
+    denom: String,
+    reference: String,
+    rate: Option<Decimal>,
+) -> Result<Response, ContractError> {
+    OWNER.assert_admin(deps.as_ref(), &info.sender)?;
+
+    let mut event = Event::new("auction_rate_updated")
+        .add_attribute("denom", &denom)
+        .add_attribute("reference", &reference);
+
+    match rate {
+        Some(rate) => {
+            RATES.save(deps.storage, (&denom, &reference), &rate)?;
+            event = event.add_attribute("rate", rate.to_string());
+        }
+        None => RATES.remove(deps.storage, (&denom, &reference)),
+    }
+
+    Ok(Response::new().add_event(event))
+}
+
+pub fn update_bidders(
+    deps: DepsMut,
+    info: MessageInfo,
+    auction_id: u64,
+    add: Vec<String>,
+    remove: Vec<String>,
+) -> Result<Response, ContractError> {
+    let auction = auctions().load(deps.storage, auction_id)?;
+
+    // only the seller and the contract owner can manage who bids
+    if info.sender != auction.seller && !OWNER.is_admin(deps.as_ref(), &info.sender)? {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    // group auctions are managed through their cw4 group
+    if auction.bidders != Some(Bidders::Allowlist) {
+        return Err(ContractError::NoAllowlist {});
+    }
+
+    for bidder in &add {
+        let bidder = deps.api.addr_validate(bidder)?;
+        ALLOWED_BIDDERS.save(deps.storage, (auction_id, bidder), &Empty {})?;
+    }
+    // removed bidders keep their bids, they just cannot raise them anymore
+    for bidder in &remove {
+        let bidder = deps.api.addr_validate(bidder)?;
+        ALLOWED_BIDDERS.remove(deps.storage, (auction_id, bidder));
+    }
+
+    Ok(Response::new().add_event(
+        Event::new("auction_bidders_updated")
+            .add_attribute("auction_id", auction_id.to_string())
+            .add_attribute("added", add.join(","))
+            .add_attribute("removed", remove.join(",")),
+    ))
+}
+
+fn ensure_eligible(
+    deps: Deps,
+    auction_id: u64,
+    auction: &Auction,
+    bidder: &Addr,
+) -> Result<(), ContractError> {
+    if !query::is_eligible(deps, auction_id, auction, bidder)? {
+        return Err(ContractError::NotAllowedToBid {});
+    }
+    Ok(())
+}
+
+// users cannot get any bids back until the bid is closed by the seller or has expired,
+// and sealed auctions only know what is left to refund once settled
+fn ensure_refundable(auction: &Auction, env: &Env) -> Result<(), ContractError> {
+    if !auction.is_closed(&env.block) || (auction.sealed.is_some() && !auction.is_settled()) {
+        return Err(ContractError::BidStillOpen {});
+    }
+    Ok(())
+}
+
+// removes whatever the bidder has left in the auction so it cannot be refunded twice
+fn take_refund(
+    storage: &mut dyn Storage,
+    auction_id: u64,
+    auction: &Auction,
+    bidder: &Addr,
+) -> StdResult<Uint128> {
+    let mut refund = bids()
+        .may_load(storage, (auction_id, bidder.clone()))?
+        .unwrap_or_default();
+    bids().remove(storage, (auction_id, bidder.clone()))?;
+    refund += take_proxy_reserve(storage, auction_id, bidder)?;
+
+    if auction.refunds_unrevealed() {
+        if let Some(commitment) = COMMITMENTS.may_load(storage, (auction_id, bidder.clone()))? {
+            COMMITMENTS.remove(storage, (auction_id, bidder.clone()));
+            refund += commitment.deposit;
+        }
+    }
+
+    Ok(refund)
+}
+
+// removes what the bidder escrowed for their proxy and did not use
+fn take_proxy_reserve(
+    storage: &mut dyn Storage,
+    auction_id: u64,
+    bidder: &Addr,
+) -> StdResult<Uint128> {
+    let reserve = proxy_bids()
+        .may_load(storage, (auction_id, bidder.clone()))?
+        .map_or(Uint128::zero(), |proxy| proxy.reserve);
+    proxy_bids().remove(storage, (auction_id, bidder.clone()))?;
+    Ok(reserve)
+}
+
+// coins a bid of `amount` in the auction denom is held in, which are the bidder's deposits on
+// auctions taking several denoms; those are removed so they cannot be paid out twice
+fn bid_coins(
+    storage: &mut dyn Storage,
+    auction_id: u64,
+    auction: &Auction,
+    bidder: &Addr,
+    amount: Uint128,
+) -> StdResult<Vec<(Denom, Uint128)>> {
+    if auction.accepted_denoms.is_none() {
+        return Ok(vec![(auction.denom.clone(), amount)]);
+    }
+
+    let deposits = DEPOSITS
+        .prefix((auction_id, bidder.clone()))
+        .range(storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<_>>>()?;
+    for (denom, _) in &deposits {
+        DEPOSITS.remove(storage, (auction_id, bidder.clone(), denom.clone()));
+    }
+
+    Ok(deposits
+        .into_iter()
+        .map(|(denom, amount)| (Denom::Native(denom), amount))
+        .collect())
+}
+
+// sends back part of a bid, or of a deposit, and records it for indexers
+fn send_refund(
+    mut res: Response,
+    auction_id: u64,
+    bidder: &Addr,
+    recipient: &str,
+    denom: &Denom,
+    amount: Uint128,
+) -> StdResult<Response> {
+    if amount.is_zero() {
+        return Ok(res);
+    }
+
+    res = res.add_message(send_tokens(denom, recipient, amount)?);
+    RefundEvent {
+        auction_id,
+        bidder,
+        recipient,
+        amount,
+        denom: &denom_to_string(denom),
+    }
+    .add_attributes(&mut res);
+    Ok(res)
+}
+
+pub fn denom_to_string(denom: &Denom) -> String {
+    match denom {
+        Denom::Native(denom) => denom.to_string(),
+        Denom::Cw20(contract_addr) => contract_addr.to_string(),
+    }
+}
+
+// bank send for native bids, cw20 transfer for cw20 bids
+fn send_tokens(
+    denom: &Denom,
+    recipient: impl Into<String>,
+    amount: Uint128,
+) -> StdResult<CosmosMsg> {
+    let msg = match denom {
+        Denom::Native(denom) => BankMsg::Send {
+            to_address: recipient.into(),
+            amount: coins(amount.u128(), denom),
+        }
+        .into(),
+        Denom::Cw20(contract_addr) => WasmMsg::Execute {
+            contract_addr: contract_addr.to_string(),
+            msg: to_json_binary(&Cw20ExecuteMsg::Transfer {
+                recipient: recipient.into(),
+                amount,
+            })?,
+            funds: vec![],
+        }
+        .into(),
+    };
+    Ok(msg)
+}
+
+// cw721 transfer or cw1155 send out of the contract escrow
+fn send_item(env: &Env, item: &AuctionItem, recipient: &Addr) -> StdResult<CosmosMsg> {
+    let msg = match item {
+        AuctionItem::Cw721 { contract, token_id } => WasmMsg::Execute {
+            contract_addr: contract.to_string(),
+            msg: to_json_binary(&Cw721ExecuteMsg::TransferNft {
+                recipient: recipient.to_string(),
+                token_id: token_id.clone(),
+            })?,
+            funds: vec![],
+        },
+        AuctionItem::Cw1155 {
+            contract,
+            token_id,
+            amount,
+        } => WasmMsg::Execute {
+            contract_addr: contract.to_string(),
+            msg: to_json_binary(&Cw1155ExecuteMsg::SendFrom {
+                from: env.contract.address.to_string(),
+                to: recipient.to_string(),
+                token_id: token_id.clone(),
+                value: *amount,
+                msg: None,
+            })?,
+            funds: vec![],
+        },
+    };
+    Ok(msg.into())
 }
@@ -1,27 +1,208 @@
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
-use cosmwasm_std::{to_binary, Addr, CosmosMsg, StdResult, WasmMsg};
+use cosmwasm_std::{
+    to_json_binary, Addr, Binary, Coin, CosmosMsg, QuerierWrapper, StdResult, Uint128, WasmMsg,
+};
+use sha2::{Digest, Sha256};
 
-use crate::msg::ExecuteMsg;
+use crate::msg::{
+    BidWinnerResponse, ExecuteMsg, HighestBidderResponse, IsBidClosedResponse, QueryMsg,
+    TotalBidResponse,
+};
 
-/// CwTemplateContract is a wrapper around Addr that provides a lot of helpers
-/// for working with this.
+/// BiddingContract is a wrapper around Addr that provides typed helpers to build messages for
+/// and query a deployed bidding platform.
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
-pub struct CwTemplateContract(pub Addr);
+pub struct BiddingContract(pub Addr);
+
+impl BiddingContract {
+    pub fn new(addr: Addr) -> Self {
+        BiddingContract(addr)
+    }
 
-impl CwTemplateContract {
     pub fn addr(&self) -> Addr {
         self.0.clone()
     }
 
-    pub fn call<T: Into<ExecuteMsg>>(&self, msg: T) -> StdResult<CosmosMsg> {
-        let msg = to_binary(&msg.into())?;
+    pub fn call<T: Into<ExecuteMsg>>(&self, msg: T, funds: Vec<Coin>) -> StdResult<CosmosMsg> {
+        let msg = to_json_binary(&msg.into())?;
         Ok(WasmMsg::Execute {
             contract_addr: self.addr().into(),
             msg,
-            funds: vec![],
+            funds,
         }
         .into())
     }
+
+    /// Bids `funds` on a native auction, on top of what the sender already bid
+    pub fn bid(&self, auction_id: u64, funds: Vec<Coin>) -> StdResult<CosmosMsg> {
+        self.call(ExecuteMsg::Bid { auction_id }, funds)
+    }
+
+    pub fn close(&self, auction_id: u64) -> StdResult<CosmosMsg> {
+        self.call(ExecuteMsg::Close { auction_id }, vec![])
+    }
+
+    /// Retracts the sender's bid once the auction is closed, to `receiver` or the sender
+    pub fn retract(&self, auction_id: u64, receiver: Option<String>) -> StdResult<CosmosMsg> {
+        self.call(
+            ExecuteMsg::Retract {
+                auction_id,
+                receiver,
+            },
+            vec![],
+        )
+    }
+
+    /// Withdraws `amount` of an outbid bid, or all of it, while the auction is still open
+    pub fn withdraw(
+        &self,
+        auction_id: u64,
+        amount: Option<Uint128>,
+        receiver: Option<String>,
+    ) -> StdResult<CosmosMsg> {
+        self.call(
+            ExecuteMsg::Withdraw {
+                auction_id,
+                amount,
+                receiver,
+            },
+            vec![],
+        )
+    }
+
+    pub fn highest_bidder(
+        &self,
+        querier: &QuerierWrapper,
+        auction_id: u64,
+    ) -> StdResult<HighestBidderResponse> {
+        querier.query_wasm_smart(self.addr(), &QueryMsg::HighestBidder { auction_id })
+    }
+
+    pub fn total_bid(
+        &self,
+        querier: &QuerierWrapper,
+        auction_id: u64,
+        address: impl Into<String>,
+    ) -> StdResult<Uint128> {
+        let res: TotalBidResponse = querier.query_wasm_smart(
+            self.addr(),
+            &QueryMsg::TotalBid {
+                auction_id,
+                address: address.into(),
+            },
+        )?;
+        Ok(res.total_bid)
+    }
+
+    pub fn is_closed(&self, querier: &QuerierWrapper, auction_id: u64) -> StdResult<bool> {
+        let res: IsBidClosedResponse =
+            querier.query_wasm_smart(self.addr(), &QueryMsg::IsBidClosed { auction_id })?;
+        Ok(res.is_closed)
+    }
+
+    /// Fails while the auction is open, and is `None` if it ended without a winner
+    pub fn winner(&self, querier: &QuerierWrapper, auction_id: u64) -> StdResult<Option<String>> {
+        let res: BidWinnerResponse =
+            querier.query_wasm_smart(self.addr(), &QueryMsg::BidWinner { auction_id })?;
+        Ok(res.winner)
+    }
+}
+
+/// Hash a bidder commits to on sealed auctions, binding the bid to the auction and their address
+/// so that commitments cannot be replayed on other auctions nor copied by other bidders.
+pub fn bid_commitment(auction_id: u64, bidder: &Addr, amount: Uint128, salt: &str) -> Binary {
+    let mut hasher = Sha256::new();
+    hasher.update(auction_id.to_be_bytes());
+    hasher.update(bidder.as_bytes());
+    hasher.update(amount.u128().to_be_bytes());
+    hasher.update(salt.as_bytes());
+    Binary::from(hasher.finalize().as_slice())
+}
+
+/// Multi-test harness: an `App` with the bidding platform code uploaded and the given balances.
+#[cfg(test)]
+pub mod multitest {
+    use anyhow::Result as AnyResult;
+    use cosmwasm_std::{Addr, Coin, CosmosMsg, Empty};
+    use cw_multi_test::{App, AppResponse, Contract, ContractWrapper, Executor};
+
+    use super::BiddingContract;
+    use crate::{
+        contract::{execute, instantiate, migrate, query},
+        msg::InstantiateMsg,
+    };
+
+    pub fn bidding_contract() -> Box<dyn Contract<Empty>> {
+        let contract = ContractWrapper::new(execute, instantiate, query).with_migrate(migrate);
+        Box::new(contract)
+    }
+
+    pub struct SuiteBuilder {
+        owner: Addr,
+        balances: Vec<(Addr, Vec<Coin>)>,
+    }
+
+    impl SuiteBuilder {
+        pub fn new(owner: &str) -> Self {
+            SuiteBuilder {
+                owner: Addr::unchecked(owner),
+                balances: vec![],
+            }
+        }
+
+        pub fn with_funds(mut self, addr: &str, funds: Vec<Coin>) -> Self {
+            self.balances.push((Addr::unchecked(addr), funds));
+            self
+        }
+
+        pub fn build(self) -> Suite {
+            let balances = self.balances;
+            let mut app = App::new(|router, _api, storage| {
+                for (addr, funds) in balances {
+                    router.bank.init_balance(storage, &addr, funds).unwrap();
+                }
+            });
+            let code_id = app.store_code(bidding_contract());
+
+            Suite {
+                app,
+                owner: self.owner,
+                code_id,
+            }
+        }
+    }
+
+    pub struct Suite {
+        pub app: App,
+        pub owner: Addr,
+        pub code_id: u64,
+    }
+
+    impl Suite {
+        /// Instantiates the platform as the owner, who is also its admin for migrations
+        pub fn instantiate(&mut self, msg: &InstantiateMsg, funds: &[Coin]) -> BiddingContract {
+            let addr = self
+                .app
+                .instantiate_contract(
+                    self.code_id,
+                    self.owner.clone(),
+                    msg,
+                    funds,
+                    "Bidding Platform",
+                    Some(self.owner.to_string()),
+                )
+                .unwrap();
+            BiddingContract(addr)
+        }
+
+        pub fn execute(&mut self, sender: &str, msg: CosmosMsg) -> AnyResult<AppResponse> {
+            self.app.execute(Addr::unchecked(sender), msg)
+        }
+
+        pub fn balances(&self, addr: &str) -> Vec<Coin> {
+            self.app.wrap().query_all_balances(addr).unwrap()
+        }
+    }
 }
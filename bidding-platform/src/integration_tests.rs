@@ -1,288 +1,340 @@
 #[cfg(test)]
 mod tests {
-    use cosmwasm_std::{coins, Addr, Empty, Uint128};
+    use cosmwasm_std::{
+        coin, coins, to_json_binary, Addr, Binary, Decimal, Deps, DepsMut, Empty, Env, Event,
+        MessageInfo, Response, StdError, StdResult, Uint128,
+    };
+    use cw1155::{Cw1155ExecuteMsg, Cw1155QueryMsg};
+    use cw20::{BalanceResponse, Cw20Coin, Cw20ExecuteMsg, Cw20QueryMsg};
+    use cw4::Member;
     use cw_multi_test::{App, Contract, ContractWrapper, Executor};
+    use cw_utils::Duration;
 
     use crate::{
-        contract::{execute, instantiate, query},
+        helpers::{
+            multitest::{Suite, SuiteBuilder},
+            BiddingContract,
+        },
         msg::{
-            BidWinnerResponse, ExecuteMsg, HighestBidderResponse, InstantiateMsg,
-            IsBidClosedResponse, QueryMsg, TotalBidResponse,
+            AuctionMsg, ConfigResponse, DepositMsg, EligibilityResponse, ExecuteMsg,
+            InstantiateMsg, ListBidsResponse, MigrateMsg, OracleQueryMsg,
+            OutstandingRefundsResponse, PriceResponse, QueryMsg, RateResponse, ReceiveMsg,
         },
+        ContractError,
     };
 
-    // returns an object that can be used with cw-multi-test
-    fn bidding_contract() -> Box<dyn Contract<Empty>> {
-        let contract = ContractWrapper::new(execute, instantiate, query);
-        Box::new(contract)
-    }
+    // the single auction contract as released in v0.1, to test migrations from it
+    mod v0_1 {
+        use cosmwasm_schema::cw_serde;
+        use cosmwasm_std::{
+            coins, to_json_binary, BankMsg, Binary, Deps, DepsMut, Empty, Env, MessageInfo,
+            Response, StdError, StdResult, Uint128,
+        };
+        use cw_multi_test::{Contract, ContractWrapper};
 
-    #[test]
-    fn bidding_process() {
-        let owner = Addr::unchecked("owner");
-        let alex = Addr::unchecked("alex");
-        let ann = Addr::unchecked("ann");
+        use crate::migrate::v0_1::{BidStatus, Config, BIDS, CONFIG, WINNER};
 
-        // an app object is the blockchain simulator. we send initial balance here too!
-        let mut app = App::new(|router, _api, storage| {
-            router
-                .bank
-                .init_balance(storage, &owner, coins(2_000_000u128, "uatom"))
-                .unwrap();
-            router
-                .bank
-                .init_balance(storage, &alex, coins(20_000_000u128, "uatom"))
-                .unwrap();
-            router
-                .bank
-                .init_balance(storage, &ann, coins(20_000_000u128, "uatom"))
-                .unwrap();
-        });
+        #[cw_serde]
+        pub struct InstantiateMsg {
+            pub commodity: String,
+            pub contract_owner: Option<String>,
+            pub commision: Option<u128>,
+        }
 
-        // upload the contract to the blockchain and get back code_id to instantiate the contract
-        let code_id = app.store_code(bidding_contract());
+        #[cw_serde]
+        pub enum ExecuteMsg {
+            Bid {},
+            Close {},
+        }
 
-        let instantiate_msg = InstantiateMsg {
-            commodity: "gold".to_string(),
-            contract_owner: None,
-            commision: None,
-        };
+        fn instantiate(
+            deps: DepsMut,
+            _env: Env,
+            info: MessageInfo,
+            msg: InstantiateMsg,
+        ) -> StdResult<Response> {
+            cw2::set_contract_version(deps.storage, "crates.io:bidding-platform", "0.1.0")?;
 
-        let contract_addr = app
-            .instantiate_contract(
-                code_id,
-                owner.clone(),
-                &instantiate_msg,
-                &coins(2_000_000u128, "uatom"),
-                "Bidding Platform",
-                None,
-            )
-            .unwrap();
+            let contract_owner = msg.contract_owner.unwrap_or(info.sender.to_string());
+            let contract_owner = deps.api.addr_validate(&contract_owner)?;
+            let cfg = Config {
+                commodity: msg.commodity,
+                contract_owner: contract_owner.clone(),
+                commission: msg.commision.unwrap_or(1000),
+                denom: info.funds[0].denom.clone(),
+                status: BidStatus::Opened,
+            };
+            CONFIG.save(deps.storage, &cfg)?;
+            WINNER.save(
+                deps.storage,
+                &(contract_owner.clone(), info.funds[0].amount),
+            )?;
+            BIDS.save(deps.storage, contract_owner, &info.funds[0].amount)?;
 
-        let bid_msg = ExecuteMsg::Bid {};
+            Ok(Response::new())
+        }
 
-        // Alex bids with 15 ATOM
-        app.execute_contract(
-            alex.clone(),
-            contract_addr.clone(),
-            &bid_msg,
-            &coins(15_000_000u128, "uatom"),
+        fn execute(
+            deps: DepsMut,
+            _env: Env,
+            info: MessageInfo,
+            msg: ExecuteMsg,
+        ) -> StdResult<Response> {
+            let mut cfg = CONFIG.load(deps.storage)?;
+            let winner = WINNER.load(deps.storage)?;
+
+            match msg {
+                ExecuteMsg::Bid {} => {
+                    let gross_bid = info.funds[0].amount;
+                    let commission =
+                        gross_bid * Uint128::from(cfg.commission) / Uint128::from(1_000_000u128);
+                    let old_bid = BIDS
+                        .may_load(deps.storage, info.sender.clone())?
+                        .unwrap_or_default();
+                    let total_bid = old_bid + gross_bid - commission;
+                    if total_bid <= winner.1 {
+                        return Err(StdError::generic_err("Bid is too low"));
+                    }
+
+                    WINNER.save(deps.storage, &(info.sender.clone(), total_bid))?;
+                    BIDS.save(deps.storage, info.sender, &total_bid)?;
+
+                    Ok(Response::new().add_message(BankMsg::Send {
+                        to_address: cfg.contract_owner.to_string(),
+                        amount: coins(commission.u128(), cfg.denom),
+                    }))
+                }
+                ExecuteMsg::Close {} => {
+                    cfg.status = BidStatus::Closed;
+                    CONFIG.save(deps.storage, &cfg)?;
+
+                    Ok(Response::new().add_message(BankMsg::Send {
+                        to_address: cfg.contract_owner.to_string(),
+                        amount: coins(winner.1.u128(), cfg.denom),
+                    }))
+                }
+            }
+        }
+
+        fn query(_deps: Deps, _env: Env, _msg: Empty) -> StdResult<Binary> {
+            to_json_binary(&Empty {})
+        }
+
+        pub fn contract() -> Box<dyn Contract<Empty>> {
+            Box::new(ContractWrapper::new(execute, instantiate, query))
+        }
+    }
+
+    fn cw20_contract() -> Box<dyn Contract<Empty>> {
+        let contract = ContractWrapper::new(
+            cw20_base::contract::execute,
+            cw20_base::contract::instantiate,
+            cw20_base::contract::query,
+        );
+        Box::new(contract)
+    }
+
+    fn cw4_group_contract() -> Box<dyn Contract<Empty>> {
+        let contract = ContractWrapper::new(
+            cw4_group::contract::execute,
+            cw4_group::contract::instantiate,
+            cw4_group::contract::query,
+        );
+        Box::new(contract)
+    }
+
+    // a price oracle quoting one uosmo at 0.5 uatom, and nothing else
+    fn oracle_contract() -> Box<dyn Contract<Empty>> {
+        let contract = ContractWrapper::new(
+            |_: DepsMut, _: Env, _: MessageInfo, _: Empty| -> StdResult<Response> {
+                Ok(Response::new())
+            },
+            |_: DepsMut, _: Env, _: MessageInfo, _: Empty| -> StdResult<Response> {
+                Ok(Response::new())
+            },
+            |_: Deps, _: Env, msg: OracleQueryMsg| -> StdResult<Binary> {
+                match msg {
+                    OracleQueryMsg::Price { base, quote }
+                        if base == "uosmo" && quote == "uatom" =>
+                    {
+                        to_json_binary(&PriceResponse {
+                            rate: Decimal::percent(50),
+                        })
+                    }
+                    OracleQueryMsg::Price { .. } => Err(StdError::not_found("price")),
+                }
+            },
+        );
+        Box::new(contract)
+    }
+
+    fn cw1155_contract() -> Box<dyn Contract<Empty>> {
+        let contract = ContractWrapper::new(
+            cw1155_base::contract::execute,
+            cw1155_base::contract::instantiate,
+            cw1155_base::contract::query,
+        );
+        Box::new(contract)
+    }
+
+    // the owner instantiates a native auction of gold with their initial bid
+    fn native_auction(
+        suite: &mut Suite,
+        msg: InstantiateMsg,
+        initial_bid: u128,
+    ) -> BiddingContract {
+        suite.instantiate(
+            &InstantiateMsg {
+                commodity: "gold".to_string(),
+                ..msg
+            },
+            &coins(initial_bid, "uatom"),
         )
-        .unwrap();
+    }
 
-        // Highest bidder should be Alex @ 14.985 ATOM
+    #[test]
+    fn bidding_process() {
+        // the blockchain simulator, with the initial balances of everyone involved
+        let mut suite = SuiteBuilder::new("owner")
+            .with_funds("owner", coins(2_000_000u128, "uatom"))
+            .with_funds("alex", coins(20_000_000u128, "uatom"))
+            .with_funds("ann", coins(20_000_000u128, "uatom"))
+            .build();
 
-        let res: HighestBidderResponse = app
-            .wrap()
-            .query_wasm_smart(contract_addr.clone(), &QueryMsg::HighestBidder {})
+        let contract = native_auction(
+            &mut suite,
+            InstantiateMsg {
+                contract_owner: None,
+                commission: None,
+                ..InstantiateMsg::default()
+            },
+            2_000_000,
+        );
+
+        // Alex bids with 15 ATOM
+        suite
+            .execute(
+                "alex",
+                contract.bid(1, coins(15_000_000u128, "uatom")).unwrap(),
+            )
             .unwrap();
 
+        // Highest bidder should be Alex @ 14.985 ATOM
+        let res = contract.highest_bidder(&suite.app.wrap(), 1).unwrap();
         assert_eq!(res.addr, "alex");
         assert_eq!(res.total_bid, Uint128::from(14_985_000u128));
 
         // ANN bids with 17 ATOM
-        app.execute_contract(
-            ann.clone(),
-            contract_addr.clone(),
-            &bid_msg,
-            &coins(17_000_000u128, "uatom"),
-        )
-        .unwrap();
-
-        // Highest bidder should be Ann @ 16.983 ATOM
-        let res: HighestBidderResponse = app
-            .wrap()
-            .query_wasm_smart(contract_addr.clone(), &QueryMsg::HighestBidder {})
+        suite
+            .execute(
+                "ann",
+                contract.bid(1, coins(17_000_000u128, "uatom")).unwrap(),
+            )
             .unwrap();
 
+        // Highest bidder should be Ann @ 16.983 ATOM
+        let res = contract.highest_bidder(&suite.app.wrap(), 1).unwrap();
         assert_eq!(res.addr, "ann");
         assert_eq!(res.total_bid, Uint128::from(16_983_000u32));
 
         // Total bid for Alex is 14.985 ATOM
-        let res: TotalBidResponse = app
-            .wrap()
-            .query_wasm_smart(
-                contract_addr.clone(),
-                &QueryMsg::TotalBid {
-                    address: "alex".to_string(),
-                },
-            )
-            .unwrap();
-
-        assert_eq!(res.total_bid, Uint128::from(14_985_000u32));
+        let total_bid = contract.total_bid(&suite.app.wrap(), 1, "alex").unwrap();
+        assert_eq!(total_bid, Uint128::from(14_985_000u32));
 
         // ANN bids with 2 ATOM
-        app.execute_contract(
-            ann.clone(),
-            contract_addr.clone(),
-            &bid_msg,
-            &coins(2_000_000u128, "uatom"),
-        )
-        .unwrap();
-
-        // Highest bidder should be Ann @ 18_981_000 uATOM
-        let res: HighestBidderResponse = app
-            .wrap()
-            .query_wasm_smart(contract_addr.clone(), &QueryMsg::HighestBidder {})
+        suite
+            .execute(
+                "ann",
+                contract.bid(1, coins(2_000_000u128, "uatom")).unwrap(),
+            )
             .unwrap();
 
+        // Highest bidder should be Ann @ 18_981_000 uATOM
+        let res = contract.highest_bidder(&suite.app.wrap(), 1).unwrap();
         assert_eq!(res.addr, "ann");
         assert_eq!(res.total_bid, Uint128::from(18_981_000u32));
 
         // Total bid for Alex is 14.985 ATOM
-        let res: TotalBidResponse = app
-            .wrap()
-            .query_wasm_smart(
-                contract_addr.clone(),
-                &QueryMsg::TotalBid {
-                    address: "alex".to_string(),
-                },
-            )
-            .unwrap();
-
-        assert_eq!(res.total_bid, Uint128::from(14_985_000u32));
+        let total_bid = contract.total_bid(&suite.app.wrap(), 1, "alex").unwrap();
+        assert_eq!(total_bid, Uint128::from(14_985_000u32));
 
         // Alex bids with 1 ATOM -> SHOULD FAIL
-        let err_res = app.execute_contract(
-            alex.clone(),
-            contract_addr.clone(),
-            &bid_msg,
-            &coins(1_000_000u128, "uatom"),
+        let err_res = suite.execute(
+            "alex",
+            contract.bid(1, coins(1_000_000u128, "uatom")).unwrap(),
         );
-
         assert!(err_res.is_err());
 
         // Alex bids with 5 ATOM
-        app.execute_contract(
-            alex.clone(),
-            contract_addr.clone(),
-            &bid_msg,
-            &coins(5_000_000u128, "uatom"),
-        )
-        .unwrap();
-
-        assert!(err_res.is_err());
-
-        // query highest bidder should return Alex @ 19_980_000 uATOM
-        let res: HighestBidderResponse = app
-            .wrap()
-            .query_wasm_smart(contract_addr.clone(), &QueryMsg::HighestBidder {})
+        suite
+            .execute(
+                "alex",
+                contract.bid(1, coins(5_000_000u128, "uatom")).unwrap(),
+            )
             .unwrap();
 
+        // query highest bidder should return Alex @ 19_980_000 uATOM
+        let res = contract.highest_bidder(&suite.app.wrap(), 1).unwrap();
         assert_eq!(res.addr, "alex");
         assert_eq!(res.total_bid, Uint128::from(19_980_000u128));
 
         // Total bid for Ann is 18_981_000 uATOM
-        let res: TotalBidResponse = app
-            .wrap()
-            .query_wasm_smart(
-                contract_addr.clone(),
-                &QueryMsg::TotalBid {
-                    address: "ann".to_string(),
-                },
-            )
-            .unwrap();
-
-        assert_eq!(res.total_bid, Uint128::from(18_981_000u128));
+        let total_bid = contract.total_bid(&suite.app.wrap(), 1, "ann").unwrap();
+        assert_eq!(total_bid, Uint128::from(18_981_000u128));
 
         // Bid should be opened
-        let res: IsBidClosedResponse = app
-            .wrap()
-            .query_wasm_smart(contract_addr.clone(), &QueryMsg::IsBidClosed {})
-            .unwrap();
-
-        assert!(!res.is_closed);
+        assert!(!contract.is_closed(&suite.app.wrap(), 1).unwrap());
 
         // querying the bid winner should throw an error
-        let res: Result<BidWinnerResponse, cosmwasm_std::StdError> = app
-            .wrap()
-            .query_wasm_smart(contract_addr.clone(), &QueryMsg::BidWinner {});
-
-        assert!(res.is_err());
+        assert!(contract.winner(&suite.app.wrap(), 1).is_err());
 
         // Owner closes bid
-        let close_bid_msg = ExecuteMsg::Close {};
-
-        app.execute_contract(
-            owner.clone(),
-            contract_addr.clone(),
-            &close_bid_msg,
-            &vec![],
-        )
-        .unwrap();
+        suite.execute("owner", contract.close(1).unwrap()).unwrap();
 
         // Ann retracts bid to herself
-        let retract_bid_msg = ExecuteMsg::Retract { receiver: None };
-        app.execute_contract(
-            ann.clone(),
-            contract_addr.clone(),
-            &retract_bid_msg,
-            &vec![],
-        )
-        .unwrap();
+        suite
+            .execute("ann", contract.retract(1, None).unwrap())
+            .unwrap();
 
         // Owner retracts initial bid to an Anon address
-        let retract_bid_to_anon_msg = ExecuteMsg::Retract {
-            receiver: Some("anon".to_string()),
-        };
-
-        app.execute_contract(
-            owner.clone(),
-            contract_addr.clone(),
-            &retract_bid_to_anon_msg,
-            &vec![],
-        )
-        .unwrap();
-
-        // query highest bidder should return Alex @ 19_980_000 uATOM
-        let res: HighestBidderResponse = app
-            .wrap()
-            .query_wasm_smart(contract_addr.clone(), &QueryMsg::HighestBidder {})
+        suite
+            .execute(
+                "owner",
+                contract.retract(1, Some("anon".to_string())).unwrap(),
+            )
             .unwrap();
 
+        // query highest bidder should return Alex @ 19_980_000 uATOM
+        let res = contract.highest_bidder(&suite.app.wrap(), 1).unwrap();
         assert_eq!(res.addr, "alex");
         assert_eq!(res.total_bid, Uint128::from(19_980_000u128));
 
         // Total bid for Ann should be 0 ATOM
-        let res: TotalBidResponse = app
-            .wrap()
-            .query_wasm_smart(
-                contract_addr.clone(),
-                &QueryMsg::TotalBid {
-                    address: "ann".to_string(),
-                },
-            )
-            .unwrap();
-
-        assert!(res.total_bid.is_zero());
+        assert!(contract
+            .total_bid(&suite.app.wrap(), 1, "ann")
+            .unwrap()
+            .is_zero());
 
         // Total bid for Owner should be 0 ATOM
-        let res: TotalBidResponse = app
-            .wrap()
-            .query_wasm_smart(
-                contract_addr.clone(),
-                &QueryMsg::TotalBid {
-                    address: "owner".to_string(),
-                },
-            )
-            .unwrap();
-
-        assert!(res.total_bid.is_zero());
+        assert!(contract
+            .total_bid(&suite.app.wrap(), 1, "owner")
+            .unwrap()
+            .is_zero());
 
         // Owner should have 20_019_000 uATOM (Alex bid + Ann's commissions)
-        let final_balance_owner = app.wrap().query_all_balances("owner").unwrap();
+        let final_balance_owner = suite.balances("owner");
         assert_eq!(final_balance_owner, coins(20_019_000, "uatom"));
 
         // Alex should have 0 ATOM as she bought gold with all their tokens
-        let final_balance_alex = app.wrap().query_all_balances("alex").unwrap();
+        let final_balance_alex = suite.balances("alex");
         assert_eq!(final_balance_alex, []);
 
         // Ann
-        let final_balance_ann = app.wrap().query_all_balances("ann").unwrap();
+        let final_balance_ann = suite.balances("ann");
         assert_eq!(final_balance_ann, coins(19_981_000, "uatom"));
 
         // anon should have 1_000_000 uATOM (owner's donated their initial balance)
-        let final_balance_anon = app.wrap().query_all_balances("anon").unwrap();
+        let final_balance_anon = suite.balances("anon");
         assert_eq!(final_balance_anon, coins(2_000_000, "uatom"));
 
         // check that no tokens were lost -> 42_000_000 uATOM were printed!
@@ -295,19 +347,798 @@ mod tests {
         );
 
         // Bid should be closed
-        let res: IsBidClosedResponse = app
+        assert!(contract.is_closed(&suite.app.wrap(), 1).unwrap());
+
+        // Alex should be the bid winner :)
+        let winner = contract.winner(&suite.app.wrap(), 1).unwrap();
+        assert_eq!(winner, Some("alex".to_string()));
+    }
+
+    #[test]
+    fn bidding_process_events() {
+        let mut suite = SuiteBuilder::new("owner")
+            .with_funds("owner", coins(1_000_000u128, "uatom"))
+            .with_funds("alex", coins(10_000_000u128, "uatom"))
+            .with_funds("ann", coins(10_000_000u128, "uatom"))
+            .build();
+
+        let contract = native_auction(&mut suite, InstantiateMsg::default(), 1_000_000);
+
+        // alex outbids the owner's initial bid
+        let res = suite
+            .execute(
+                "alex",
+                contract.bid(1, coins(2_000_000u128, "uatom")).unwrap(),
+            )
+            .unwrap();
+        res.assert_event(&Event::new("wasm-auction_bid").add_attributes(vec![
+            ("auction_id", "1"),
+            ("bidder", "alex"),
+            ("gross", "2000000"),
+            ("commission", "2000"),
+            ("net", "1998000"),
+            ("total_bid", "1998000"),
+            ("denom", "uatom"),
+            ("previous_leader", "owner"),
+        ]));
+
+        // and ann outbids alex
+        let res = suite
+            .execute(
+                "ann",
+                contract.bid(1, coins(3_000_000u128, "uatom")).unwrap(),
+            )
+            .unwrap();
+        res.assert_event(&Event::new("wasm-auction_bid").add_attributes(vec![
+            ("bidder", "ann"),
+            ("net", "2997000"),
+            ("total_bid", "2997000"),
+            ("previous_leader", "alex"),
+        ]));
+
+        // commission was already paid on every bid, so the seller gets all of ann's bid
+        let res = suite.execute("owner", contract.close(1).unwrap()).unwrap();
+        res.assert_event(&Event::new("wasm-auction_closed").add_attributes(vec![
+            ("auction_id", "1"),
+            ("winner", "ann"),
+            ("gross", "2997000"),
+            ("commission", "0"),
+            ("net", "2997000"),
+            ("denom", "uatom"),
+        ]));
+
+        // alex retracts her bid to anon, and ann refunds the owner
+        let res = suite
+            .execute(
+                "alex",
+                contract.retract(1, Some("anon".to_string())).unwrap(),
+            )
+            .unwrap();
+        res.assert_event(&Event::new("wasm-auction_refund").add_attributes(vec![
+            ("auction_id", "1"),
+            ("bidder", "alex"),
+            ("recipient", "anon"),
+            ("amount", "1998000"),
+            ("denom", "uatom"),
+        ]));
+
+        let refund_all_msg = ExecuteMsg::RefundAll {
+            auction_id: 1,
+            limit: None,
+        };
+        let res = suite
+            .execute("ann", contract.call(refund_all_msg, vec![]).unwrap())
+            .unwrap();
+        res.assert_event(&Event::new("wasm-auction_refund").add_attributes(vec![
+            ("bidder", "owner"),
+            ("recipient", "owner"),
+            ("amount", "1000000"),
+        ]));
+    }
+
+    #[test]
+    fn bidding_process_timed() {
+        let mut suite = SuiteBuilder::new("owner")
+            .with_funds("owner", coins(1_000_000u128, "uatom"))
+            .with_funds("alex", coins(10_000_000u128, "uatom"))
+            .with_funds("ann", coins(10_000_000u128, "uatom"))
+            .build();
+
+        let contract = native_auction(
+            &mut suite,
+            InstantiateMsg {
+                duration: Some(Duration::Height(100)),
+                ..InstantiateMsg::default()
+            },
+            1_000_000,
+        );
+
+        suite
+            .execute(
+                "alex",
+                contract.bid(1, coins(2_000_000u128, "uatom")).unwrap(),
+            )
+            .unwrap();
+
+        // the owner cannot end the auction early while alex is winning
+        let err = suite
+            .execute("owner", contract.close(1).unwrap())
+            .unwrap_err();
+        assert_eq!(
+            err.downcast::<ContractError>().unwrap(),
+            ContractError::BidStillOpen {}
+        );
+
+        // so ann still gets to outbid alex before it expires
+        suite.app.update_block(|block| block.height += 99);
+        suite
+            .execute(
+                "ann",
+                contract.bid(1, coins(3_000_000u128, "uatom")).unwrap(),
+            )
+            .unwrap();
+        assert!(suite.execute("owner", contract.close(1).unwrap()).is_err());
+
+        suite.app.update_block(|block| block.height += 1);
+        suite.execute("owner", contract.close(1).unwrap()).unwrap();
+
+        let winner = contract.winner(&suite.app.wrap(), 1).unwrap();
+        assert_eq!(winner, Some("ann".to_string()));
+    }
+
+    #[test]
+    fn bidding_process_cw20() {
+        let owner = Addr::unchecked("owner");
+        let alex = Addr::unchecked("alex");
+        let ann = Addr::unchecked("ann");
+
+        let mut suite = SuiteBuilder::new("owner").build();
+
+        // alex and ann get 20 tokens each
+        let cw20_id = suite.app.store_code(cw20_contract());
+        let cw20_addr = suite
+            .app
+            .instantiate_contract(
+                cw20_id,
+                owner.clone(),
+                &cw20_base::msg::InstantiateMsg {
+                    name: "Gold Token".to_string(),
+                    symbol: "GOLD".to_string(),
+                    decimals: 6,
+                    initial_balances: vec![
+                        Cw20Coin {
+                            address: alex.to_string(),
+                            amount: Uint128::from(20_000_000u128),
+                        },
+                        Cw20Coin {
+                            address: ann.to_string(),
+                            amount: Uint128::from(20_000_000u128),
+                        },
+                    ],
+                    mint: None,
+                    marketing: None,
+                },
+                &[],
+                "Gold Token",
+                None,
+            )
+            .unwrap();
+
+        // cw20 bids are instantiated without an initial deposit from the owner
+        let contract = suite.instantiate(
+            &InstantiateMsg {
+                commodity: "gold".to_string(),
+                cw20_addr: Some(cw20_addr.to_string()),
+                ..InstantiateMsg::default()
+            },
+            &[],
+        );
+
+        let bid = |amount: u128| Cw20ExecuteMsg::Send {
+            contract: contract.addr().to_string(),
+            amount: Uint128::from(amount),
+            msg: to_json_binary(&ReceiveMsg::Bid { auction_id: 1 }).unwrap(),
+        };
+
+        // Alex bids with 15 tokens
+        suite
+            .app
+            .execute_contract(alex.clone(), cw20_addr.clone(), &bid(15_000_000), &[])
+            .unwrap();
+
+        // Ann bids with 17 tokens
+        suite
+            .app
+            .execute_contract(ann.clone(), cw20_addr.clone(), &bid(17_000_000), &[])
+            .unwrap();
+
+        // Highest bidder should be Ann @ 16.983 tokens
+        let res = contract.highest_bidder(&suite.app.wrap(), 1).unwrap();
+        assert_eq!(res.addr, "ann");
+        assert_eq!(res.total_bid, Uint128::from(16_983_000u128));
+
+        // bidding with native tokens should fail
+        let err_res = suite.execute(
+            "alex",
+            contract.bid(1, coins(5_000_000u128, "uatom")).unwrap(),
+        );
+        assert!(err_res.is_err());
+
+        // Owner closes bid
+        suite.execute("owner", contract.close(1).unwrap()).unwrap();
+
+        // Alex retracts bid to herself
+        suite
+            .execute("alex", contract.retract(1, None).unwrap())
+            .unwrap();
+
+        let balance = |app: &App, addr: &Addr| -> Uint128 {
+            let res: BalanceResponse = app
+                .wrap()
+                .query_wasm_smart(
+                    cw20_addr.clone(),
+                    &Cw20QueryMsg::Balance {
+                        address: addr.to_string(),
+                    },
+                )
+                .unwrap();
+            res.balance
+        };
+
+        // Owner should have Ann's bid plus the commissions of both bids
+        assert_eq!(balance(&suite.app, &owner), Uint128::from(17_015_000u128));
+
+        // Alex got back her bid minus commissions
+        assert_eq!(balance(&suite.app, &alex), Uint128::from(19_985_000u128));
+
+        // Ann paid for the gold
+        assert_eq!(balance(&suite.app, &ann), Uint128::from(3_000_000u128));
+
+        // Nothing is left in the contract
+        assert!(balance(&suite.app, &contract.addr()).is_zero());
+
+        // Ann should be the bid winner
+        let winner = contract.winner(&suite.app.wrap(), 1).unwrap();
+        assert_eq!(winner, Some("ann".to_string()));
+    }
+
+    #[test]
+    fn bidding_process_cw1155() {
+        let owner = Addr::unchecked("owner");
+        let alex = Addr::unchecked("alex");
+        let ann = Addr::unchecked("ann");
+
+        let mut suite = SuiteBuilder::new("owner")
+            .with_funds("owner", coins(1_000_000u128, "uatom"))
+            .with_funds("alex", coins(20_000_000u128, "uatom"))
+            .build();
+
+        // owner mints 10 gold bars
+        let cw1155_id = suite.app.store_code(cw1155_contract());
+        let cw1155_addr = suite
+            .app
+            .instantiate_contract(
+                cw1155_id,
+                owner.clone(),
+                &cw1155_base::msg::InstantiateMsg {
+                    minter: owner.to_string(),
+                },
+                &[],
+                "Gold Bars",
+                None,
+            )
+            .unwrap();
+
+        suite
+            .app
+            .execute_contract(
+                owner.clone(),
+                cw1155_addr.clone(),
+                &Cw1155ExecuteMsg::Mint {
+                    to: ann.to_string(),
+                    token_id: "gold".to_string(),
+                    value: Uint128::from(10u128),
+                    msg: None,
+                },
+                &[],
+            )
+            .unwrap();
+
+        let contract = suite.instantiate(
+            &InstantiateMsg {
+                commodity: "silver".to_string(),
+                ..InstantiateMsg::default()
+            },
+            &coins(1_000_000u128, "uatom"),
+        );
+
+        // Ann puts 4 gold bars up for auction, and then 2 more
+        let deposit = |value: u128| Cw1155ExecuteMsg::SendFrom {
+            from: ann.to_string(),
+            to: contract.addr().to_string(),
+            token_id: "gold".to_string(),
+            value: Uint128::from(value),
+            msg: Some(
+                to_json_binary(&DepositMsg::CreateAuction(AuctionMsg {
+                    commodity: "gold".to_string(),
+                    native_denom: Some("uatom".to_string()),
+                    ..AuctionMsg::default()
+                }))
+                .unwrap(),
+            ),
+        };
+        suite
+            .app
+            .execute_contract(ann.clone(), cw1155_addr.clone(), &deposit(4), &[])
+            .unwrap();
+        suite
+            .app
+            .execute_contract(ann.clone(), cw1155_addr.clone(), &deposit(2), &[])
+            .unwrap();
+
+        // Alex bids 10 ATOM on the 4 gold bars
+        suite
+            .execute(
+                "alex",
+                contract.bid(2, coins(10_000_000u128, "uatom")).unwrap(),
+            )
+            .unwrap();
+
+        // Ann closes both auctions
+        for auction_id in [2, 3] {
+            suite
+                .execute("ann", contract.close(auction_id).unwrap())
+                .unwrap();
+        }
+
+        let balance = |app: &App, addr: &Addr| -> Uint128 {
+            let res: cw1155::BalanceResponse = app
+                .wrap()
+                .query_wasm_smart(
+                    cw1155_addr.clone(),
+                    &Cw1155QueryMsg::Balance {
+                        owner: addr.to_string(),
+                        token_id: "gold".to_string(),
+                    },
+                )
+                .unwrap();
+            res.balance
+        };
+
+        // Alex got the 4 gold bars she won, Ann kept 4 and got back the 2 nobody bid on
+        assert_eq!(balance(&suite.app, &alex), Uint128::from(4u128));
+        assert_eq!(balance(&suite.app, &ann), Uint128::from(6u128));
+        assert!(balance(&suite.app, &contract.addr()).is_zero());
+
+        // Ann got paid Alex's bid minus commission, which went to the owner
+        assert_eq!(suite.balances("ann"), coins(9_990_000, "uatom"));
+        assert_eq!(suite.balances("owner"), coins(10_000, "uatom"));
+    }
+
+    #[test]
+    fn bidding_process_refund_all() {
+        let bidders = ["alex", "ann", "bob", "carl"];
+
+        let mut suite = bidders
+            .iter()
+            .fold(
+                SuiteBuilder::new("owner").with_funds("owner", coins(1_000_000u128, "uatom")),
+                |builder, bidder| builder.with_funds(bidder, coins(10_000_000u128, "uatom")),
+            )
+            .build();
+
+        let contract = native_auction(&mut suite, InstantiateMsg::default(), 1_000_000);
+
+        // everyone outbids the previous bidder, carl wins with 5 ATOM
+        for (i, bidder) in bidders.iter().enumerate() {
+            let funds = coins(2_000_000 + i as u128 * 1_000_000, "uatom");
+            suite
+                .execute(bidder, contract.bid(1, funds).unwrap())
+                .unwrap();
+        }
+
+        // refunds cannot be sent while the auction is open
+        let refund_all_msg = ExecuteMsg::RefundAll {
+            auction_id: 1,
+            limit: Some(2),
+        };
+        let refund_all = || contract.call(refund_all_msg.clone(), vec![]).unwrap();
+        assert!(suite.execute(bidders[0], refund_all()).is_err());
+
+        suite.execute("owner", contract.close(1).unwrap()).unwrap();
+
+        let outstanding = |app: &App| -> Vec<String> {
+            let res: OutstandingRefundsResponse = app
+                .wrap()
+                .query_wasm_smart(
+                    contract.addr(),
+                    &QueryMsg::OutstandingRefunds {
+                        auction_id: 1,
+                        start_after: None,
+                        limit: None,
+                    },
+                )
+                .unwrap();
+            res.refunds
+                .into_iter()
+                .map(|refund| refund.bidder)
+                .collect()
+        };
+
+        // carl's winning bid was paid to the owner, so it is not outstanding
+        assert_eq!(outstanding(&suite.app), vec!["alex", "ann", "bob", "owner"]);
+
+        // anyone can refund the first two bidders
+        suite.execute(bidders[3], refund_all()).unwrap();
+        assert_eq!(outstanding(&suite.app), vec!["bob", "owner"]);
+
+        // the next batch refunds everyone left
+        suite.execute(bidders[3], refund_all()).unwrap();
+        assert!(outstanding(&suite.app).is_empty());
+
+        // alex bid 2 ATOM, ann 3 ATOM and bob 4 ATOM, all of them minus commission
+        assert_eq!(suite.balances("alex"), coins(9_998_000, "uatom"));
+        assert_eq!(suite.balances("ann"), coins(9_997_000, "uatom"));
+        assert_eq!(suite.balances("bob"), coins(9_996_000, "uatom"));
+        assert_eq!(suite.balances("carl"), coins(5_000_000, "uatom"));
+
+        // the owner got their initial bid back, carl's winning bid and every commission
+        assert_eq!(suite.balances("owner"), coins(6_009_000, "uatom"));
+
+        // nothing is left in the contract
+        assert!(suite.balances(contract.addr().as_str()).is_empty());
+    }
+
+    #[test]
+    fn bidding_process_multi_denom() {
+        let owner = Addr::unchecked("owner");
+
+        let mut suite = SuiteBuilder::new("owner")
+            .with_funds("owner", coins(1_000_000u128, "uatom"))
+            .with_funds("alex", coins(10_000_000u128, "uosmo"))
+            .with_funds("ann", coins(10_000_000u128, "uatom"))
+            .build();
+
+        let oracle_id = suite.app.store_code(oracle_contract());
+        let oracle_addr = suite
+            .app
+            .instantiate_contract(oracle_id, owner, &Empty {}, &[], "Oracle", None)
+            .unwrap();
+
+        // bids can be made in OSMO as well, valued at the oracle price
+        let contract = native_auction(
+            &mut suite,
+            InstantiateMsg {
+                accepted_denoms: Some(vec!["uosmo".to_string()]),
+                price_oracle: Some(oracle_addr.to_string()),
+                ..InstantiateMsg::default()
+            },
+            1_000_000,
+        );
+
+        let res: RateResponse = suite
+            .app
+            .wrap()
+            .query_wasm_smart(
+                contract.addr(),
+                &QueryMsg::Rate {
+                    auction_id: 1,
+                    denom: "uosmo".to_string(),
+                },
+            )
+            .unwrap();
+        assert_eq!(res.rate, Decimal::percent(50));
+
+        // alex bids 6 OSMO, ann outbids her with 4 ATOM and alex outbids ann with 4 more OSMO
+        let bids = [
+            ("alex", coins(6_000_000u128, "uosmo")),
+            ("ann", coins(4_000_000u128, "uatom")),
+        ];
+        for (bidder, funds) in bids {
+            suite
+                .execute(bidder, contract.bid(1, funds).unwrap())
+                .unwrap();
+        }
+        let res = suite
+            .execute(
+                "alex",
+                contract.bid(1, coins(4_000_000u128, "uosmo")).unwrap(),
+            )
+            .unwrap();
+
+        // the bid event reports the OSMO bid at its value in ATOM
+        res.assert_event(&Event::new("wasm-auction_bid").add_attributes(vec![
+            ("bidder", "alex"),
+            ("gross", "2000000"),
+            ("commission", "2000"),
+            ("net", "1998000"),
+            ("total_bid", "4995000"),
+            ("denom", "uatom"),
+            ("previous_leader", "ann"),
+        ]));
+
+        let res = contract.highest_bidder(&suite.app.wrap(), 1).unwrap();
+        assert_eq!(res.addr, "alex");
+        assert_eq!(res.total_bid, Uint128::from(4_995_000u128));
+
+        // the contract holds alex's bid in OSMO, and not its value in ATOM
+        let outstanding = |app: &App| -> Vec<(String, u128, String)> {
+            let res: OutstandingRefundsResponse = app
+                .wrap()
+                .query_wasm_smart(
+                    contract.addr(),
+                    &QueryMsg::OutstandingRefunds {
+                        auction_id: 1,
+                        start_after: None,
+                        limit: None,
+                    },
+                )
+                .unwrap();
+            res.refunds
+                .into_iter()
+                .map(|refund| (refund.bidder, refund.amount.u128(), refund.denom))
+                .collect()
+        };
+        assert_eq!(
+            outstanding(&suite.app),
+            vec![
+                ("alex".to_string(), 9_990_000, "uosmo".to_string()),
+                ("ann".to_string(), 3_996_000, "uatom".to_string()),
+                ("owner".to_string(), 1_000_000, "uatom".to_string()),
+            ]
+        );
+
+        suite.execute("owner", contract.close(1).unwrap()).unwrap();
+        assert_eq!(
+            outstanding(&suite.app),
+            vec![
+                ("ann".to_string(), 3_996_000, "uatom".to_string()),
+                ("owner".to_string(), 1_000_000, "uatom".to_string()),
+            ]
+        );
+        for bidder in ["ann", "owner"] {
+            suite
+                .execute(bidder, contract.retract(1, None).unwrap())
+                .unwrap();
+        }
+
+        // everyone is paid back in the denom they bid in
+        assert_eq!(suite.balances("alex"), []);
+        assert_eq!(suite.balances("ann"), coins(9_996_000, "uatom"));
+
+        // the owner got alex's OSMO, their initial bid back and every commission
+        assert_eq!(
+            suite.balances("owner"),
+            vec![coin(1_004_000, "uatom"), coin(10_000_000, "uosmo")]
+        );
+        assert!(suite.balances(contract.addr().as_str()).is_empty());
+    }
+
+    #[test]
+    fn bidding_process_migrate() {
+        let owner = Addr::unchecked("owner");
+        let alex = Addr::unchecked("alex");
+        let ann = Addr::unchecked("ann");
+
+        let mut suite = SuiteBuilder::new("owner")
+            .with_funds("owner", coins(2_000_000u128, "uatom"))
+            .with_funds("alex", coins(20_000_000u128, "uatom"))
+            .with_funds("ann", coins(20_000_000u128, "uatom"))
+            .build();
+
+        // deploy the v0.1 contract, with the owner as admin so they can migrate it
+        let old_code_id = suite.app.store_code(v0_1::contract());
+        let contract_addr = suite
+            .app
+            .instantiate_contract(
+                old_code_id,
+                owner.clone(),
+                &v0_1::InstantiateMsg {
+                    commodity: "gold".to_string(),
+                    contract_owner: None,
+                    commision: None,
+                },
+                &coins(1_000_000u128, "uatom"),
+                "Bidding Platform",
+                Some(owner.to_string()),
+            )
+            .unwrap();
+
+        // alex bids 10 ATOM, ann 12 ATOM and alex 3 more ATOM, all of them minus 0.1% commission
+        let bids = [
+            (&alex, 10_000_000u128),
+            (&ann, 12_000_000),
+            (&alex, 3_000_000),
+        ];
+        for (bidder, amount) in bids {
+            suite
+                .app
+                .execute_contract(
+                    bidder.clone(),
+                    contract_addr.clone(),
+                    &v0_1::ExecuteMsg::Bid {},
+                    &coins(amount, "uatom"),
+                )
+                .unwrap();
+        }
+
+        suite
+            .app
+            .migrate_contract(
+                owner.clone(),
+                contract_addr.clone(),
+                &MigrateMsg {},
+                suite.code_id,
+            )
+            .unwrap();
+        let contract = BiddingContract(contract_addr);
+
+        let info = cw2::query_contract_info(&suite.app.wrap(), contract.addr()).unwrap();
+        assert_eq!(info.version, env!("CARGO_PKG_VERSION"));
+
+        // the old auction is now the first one, with its owner, commission and bids preserved
+        let res: ConfigResponse = suite
+            .app
             .wrap()
-            .query_wasm_smart(contract_addr.clone(), &QueryMsg::IsBidClosed {})
+            .query_wasm_smart(contract.addr(), &QueryMsg::Config {})
             .unwrap();
+        assert_eq!(res.contract_owner, "owner");
+        assert_eq!(res.commission.rate, Decimal::permille(1));
 
-        assert!(res.is_closed);
+        let res = contract.highest_bidder(&suite.app.wrap(), 1).unwrap();
+        assert_eq!(res.addr, "alex");
+        assert_eq!(res.total_bid, Uint128::from(12_987_000u128));
 
-        // Alex should be the bid winner :)
-        let res: BidWinnerResponse = app
+        let res: ListBidsResponse = suite
+            .app
             .wrap()
-            .query_wasm_smart(contract_addr.clone(), &QueryMsg::BidWinner {})
+            .query_wasm_smart(
+                contract.addr(),
+                &QueryMsg::ListBids {
+                    auction_id: 1,
+                    start_after: None,
+                    limit: None,
+                    order: None,
+                },
+            )
+            .unwrap();
+        let bids: Vec<_> = res
+            .bids
+            .into_iter()
+            .map(|bid| (bid.bidder, bid.total_bid.u128()))
+            .collect();
+        assert_eq!(
+            bids,
+            vec![
+                ("alex".to_string(), 12_987_000),
+                ("ann".to_string(), 11_988_000),
+                ("owner".to_string(), 1_000_000),
+            ]
+        );
+
+        // the auction goes on: ann bids 2 more ATOM and the owner closes it
+        suite
+            .execute(
+                "ann",
+                contract.bid(1, coins(2_000_000u128, "uatom")).unwrap(),
+            )
             .unwrap();
+        suite.execute("owner", contract.close(1).unwrap()).unwrap();
+
+        // alex and the owner get their bids back
+        for bidder in ["alex", "owner"] {
+            suite
+                .execute(bidder, contract.retract(1, None).unwrap())
+                .unwrap();
+        }
+
+        assert_eq!(suite.balances("alex"), coins(19_987_000, "uatom"));
+        assert_eq!(suite.balances("ann"), coins(6_000_000, "uatom"));
 
-        assert_eq!(res.winner, "alex".to_string());
+        // the owner got ann's winning bid, their initial bid back and every commission
+        assert_eq!(suite.balances("owner"), coins(16_013_000, "uatom"));
+        assert!(suite.balances(contract.addr().as_str()).is_empty());
+    }
+
+    #[test]
+    fn bidding_process_group() {
+        let owner = Addr::unchecked("owner");
+        let alex = Addr::unchecked("alex");
+        let ann = Addr::unchecked("ann");
+
+        let mut suite = SuiteBuilder::new("owner")
+            .with_funds("owner", coins(1_000_000u128, "uatom"))
+            .with_funds("alex", coins(10_000_000u128, "uatom"))
+            .with_funds("ann", coins(10_000_000u128, "uatom"))
+            .build();
+
+        // only alex is a member of the group
+        let group_id = suite.app.store_code(cw4_group_contract());
+        let group_addr = suite
+            .app
+            .instantiate_contract(
+                group_id,
+                owner.clone(),
+                &cw4_group::msg::InstantiateMsg {
+                    admin: Some(owner.to_string()),
+                    members: vec![Member {
+                        addr: alex.to_string(),
+                        weight: 1,
+                    }],
+                },
+                &[],
+                "Group",
+                None,
+            )
+            .unwrap();
+
+        let contract = native_auction(
+            &mut suite,
+            InstantiateMsg {
+                bidder_group: Some(group_addr.to_string()),
+                ..InstantiateMsg::default()
+            },
+            1_000_000,
+        );
+
+        let eligible = |app: &App, address: &Addr| {
+            let res: EligibilityResponse = app
+                .wrap()
+                .query_wasm_smart(
+                    contract.addr(),
+                    &QueryMsg::Eligibility {
+                        auction_id: 1,
+                        address: address.to_string(),
+                    },
+                )
+                .unwrap();
+            res.eligible
+        };
+        assert!(eligible(&suite.app, &alex));
+        assert!(!eligible(&suite.app, &ann));
+
+        suite
+            .execute(
+                "alex",
+                contract.bid(1, coins(2_000_000u128, "uatom")).unwrap(),
+            )
+            .unwrap();
+        let err = suite
+            .execute(
+                "ann",
+                contract.bid(1, coins(3_000_000u128, "uatom")).unwrap(),
+            )
+            .unwrap_err();
+        assert_eq!(
+            err.downcast::<ContractError>().unwrap(),
+            ContractError::NotAllowedToBid {}
+        );
+
+        // ann can bid once she joins the group
+        suite
+            .app
+            .execute_contract(
+                owner.clone(),
+                group_addr,
+                &cw4_group::msg::ExecuteMsg::UpdateMembers {
+                    remove: vec![],
+                    add: vec![Member {
+                        addr: ann.to_string(),
+                        weight: 1,
+                    }],
+                },
+                &[],
+            )
+            .unwrap();
+        assert!(eligible(&suite.app, &ann));
+        suite
+            .execute(
+                "ann",
+                contract.bid(1, coins(3_000_000u128, "uatom")).unwrap(),
+            )
+            .unwrap();
+
+        let res = contract.highest_bidder(&suite.app.wrap(), 1).unwrap();
+        assert_eq!(res.addr, "ann");
     }
 }
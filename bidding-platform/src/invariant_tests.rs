@@ -0,0 +1,411 @@
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use cosmwasm_std::{coins, Uint128};
+
+    use crate::{
+        helpers::{
+            multitest::{Suite, SuiteBuilder},
+            BiddingContract,
+        },
+        msg::{ExecuteMsg, InstantiateMsg, OutstandingRefundsResponse, QueryMsg},
+    };
+
+    const DENOM: &str = "uatom";
+    const OWNER: &str = "owner";
+    const BIDDERS: [&str; 5] = ["alex", "ann", "bob", "carl", "dave"];
+    const ACCOUNTS: [&str; 6] = [OWNER, "alex", "ann", "bob", "carl", "dave"];
+
+    // every case replays the same actions from its seed, so failures can be reproduced
+    const CASES: u64 = 64;
+    const STEPS: usize = 40;
+
+    // splitmix64, enough to generate actions without pulling in a randomness crate
+    struct Rng(u64);
+
+    impl Rng {
+        fn next(&mut self) -> u64 {
+            self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+            let mut z = self.0;
+            z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+            z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+            z ^ (z >> 31)
+        }
+
+        fn below(&mut self, n: u64) -> u64 {
+            self.next() % n
+        }
+
+        fn pick(&mut self, accounts: &[&'static str]) -> &'static str {
+            accounts[self.below(accounts.len() as u64) as usize]
+        }
+    }
+
+    #[derive(Clone, Debug)]
+    enum Action {
+        Bid {
+            bidder: &'static str,
+            amount: u128,
+        },
+        Close {
+            sender: &'static str,
+        },
+        Retract {
+            bidder: &'static str,
+            receiver: Option<&'static str>,
+        },
+        Withdraw {
+            bidder: &'static str,
+        },
+        SetMaxBid {
+            bidder: &'static str,
+            max: u128,
+            deposit: u128,
+        },
+    }
+
+    impl Action {
+        fn random(rng: &mut Rng, model: &Model) -> Self {
+            match rng.below(12) {
+                0..=5 => {
+                    let bidder = rng.pick(&ACCOUNTS);
+                    // bidders mostly bid what they can afford, sometimes more
+                    let balance = model.balances[bidder] as u64;
+                    let amount = 1 + rng.below(balance + balance / 10 + 1) as u128;
+                    Action::Bid { bidder, amount }
+                }
+                6 => Action::Close {
+                    sender: rng.pick(&ACCOUNTS),
+                },
+                7 => Action::Withdraw {
+                    bidder: rng.pick(&ACCOUNTS),
+                },
+                8 | 9 => {
+                    let bidder = rng.pick(&ACCOUNTS);
+                    // escrow nothing more at times, to move the max bid within the escrow
+                    let balance = model.balances[bidder] as u64;
+                    let deposit = match rng.below(3) {
+                        0 => 0,
+                        _ => 1 + rng.below(balance + balance / 10 + 1) as u128,
+                    };
+                    // max bids mostly within the escrow, sometimes beyond it
+                    let escrow = model.bid(bidder) + model.reserve(bidder) + deposit;
+                    let room = escrow.saturating_sub(model.leader.1) + escrow / 10;
+                    let max = model.leader.1 + rng.below(room as u64 + 2) as u128;
+                    Action::SetMaxBid {
+                        bidder,
+                        max,
+                        deposit,
+                    }
+                }
+                _ => Action::Retract {
+                    bidder: rng.pick(&ACCOUNTS),
+                    receiver: match rng.below(3) {
+                        0 => Some(rng.pick(&ACCOUNTS)),
+                        _ => None,
+                    },
+                },
+            }
+        }
+
+        fn execute(&self, suite: &mut Suite, contract: &BiddingContract) -> anyhow::Result<()> {
+            let (sender, msg) = match *self {
+                Action::Bid { bidder, amount } => (bidder, contract.bid(1, coins(amount, DENOM))),
+                Action::Close { sender } => (sender, contract.close(1)),
+                Action::Retract { bidder, receiver } => {
+                    (bidder, contract.retract(1, receiver.map(String::from)))
+                }
+                Action::Withdraw { bidder } => (bidder, contract.withdraw(1, None, None)),
+                Action::SetMaxBid {
+                    bidder,
+                    max,
+                    deposit,
+                } => {
+                    let msg = ExecuteMsg::SetMaxBid {
+                        auction_id: 1,
+                        max: Uint128::from(max),
+                    };
+                    let funds = match deposit {
+                        0 => vec![],
+                        _ => coins(deposit, DENOM),
+                    };
+                    (bidder, contract.call(msg, funds))
+                }
+            };
+            suite.execute(sender, msg?)?;
+            Ok(())
+        }
+    }
+
+    // what every account and the contract are expected to hold, the seller being the owner
+    struct Model {
+        balances: BTreeMap<&'static str, u128>,
+        bids: BTreeMap<&'static str, u128>,
+        // max bid and unused escrow of every proxy
+        proxies: BTreeMap<&'static str, (u128, u128)>,
+        leader: (&'static str, u128),
+        closed: bool,
+        commissions: u128,
+        // what the owner started with, and got back from bids, besides the sale and commissions
+        owner_funds: u128,
+    }
+
+    impl Model {
+        fn new(initial_bid: u128) -> Self {
+            Model {
+                balances: ACCOUNTS.iter().map(|account| (*account, 0)).collect(),
+                bids: BTreeMap::from([(OWNER, initial_bid)]),
+                proxies: BTreeMap::new(),
+                leader: (OWNER, initial_bid),
+                closed: false,
+                commissions: 0,
+                owner_funds: 0,
+            }
+        }
+
+        fn bid(&self, bidder: &str) -> u128 {
+            self.bids.get(bidder).copied().unwrap_or_default()
+        }
+
+        fn reserve(&self, bidder: &str) -> u128 {
+            self.proxies.get(bidder).map_or(0, |(_, reserve)| *reserve)
+        }
+
+        // applies the action if the contract should accept it, returning whether it should
+        fn apply(&mut self, action: &Action) -> bool {
+            match *action {
+                Action::Bid { bidder, amount } => {
+                    // 0.1% commission
+                    let commission = amount / 1000;
+                    let total_bid = self.bid(bidder) + amount - commission;
+                    if self.closed
+                        || bidder == OWNER
+                        || amount > self.balances[bidder]
+                        || total_bid <= self.leader.1
+                    {
+                        return false;
+                    }
+                    *self.balances.get_mut(bidder).unwrap() -= amount;
+                    *self.balances.get_mut(OWNER).unwrap() += commission;
+                    self.commissions += commission;
+                    self.bids.insert(bidder, total_bid);
+                    self.leader = (bidder, total_bid);
+                    self.resolve_proxies();
+                }
+                Action::Close { sender } => {
+                    if self.closed || sender != OWNER {
+                        return false;
+                    }
+                    self.closed = true;
+                    *self.balances.get_mut(OWNER).unwrap() += self.leader.1;
+                }
+                Action::Retract { bidder, receiver } => {
+                    let receiver = receiver.unwrap_or(bidder);
+                    if !self.closed {
+                        return false;
+                    }
+                    // the winner only gets back what their proxy did not use
+                    if bidder == self.leader.0 {
+                        let Some((_, reserve)) = self.proxies.remove(bidder) else {
+                            return false;
+                        };
+                        if reserve == 0 {
+                            return false;
+                        }
+                        self.pay(receiver, reserve);
+                    } else if !self.refund(bidder, receiver) {
+                        return false;
+                    }
+                }
+                Action::Withdraw { bidder } => {
+                    if self.closed || bidder == self.leader.0 || !self.refund(bidder, bidder) {
+                        return false;
+                    }
+                }
+                Action::SetMaxBid {
+                    bidder,
+                    max,
+                    deposit,
+                } => {
+                    let commission = deposit / 1000;
+                    let bid = self.bid(bidder);
+                    let reserve = self.reserve(bidder) + deposit - commission;
+                    if self.closed
+                        || bidder == OWNER
+                        || deposit > self.balances[bidder]
+                        || max > bid + reserve
+                        || max < bid
+                        || (bidder != self.leader.0 && max <= self.leader.1)
+                    {
+                        return false;
+                    }
+                    *self.balances.get_mut(bidder).unwrap() -= deposit;
+                    *self.balances.get_mut(OWNER).unwrap() += commission;
+                    self.commissions += commission;
+                    self.proxies.insert(bidder, (max, reserve));
+                    self.resolve_proxies();
+                }
+            }
+            true
+        }
+
+        // proxies outbid the leader by one, the smallest increment, until none can anymore; the
+        // highest max bid challenges first, the lowest address on equal max bids
+        fn resolve_proxies(&mut self) {
+            loop {
+                let (leader, amount) = self.leader;
+                let leader_max = self.proxies.get(leader).map_or(amount, |p| p.0.max(amount));
+                let mut challenger: Option<(&'static str, u128)> = None;
+                for (&bidder, &(max, _)) in &self.proxies {
+                    if bidder != leader && max > challenger.map_or(amount, |(_, best)| best) {
+                        challenger = Some((bidder, max));
+                    }
+                }
+                let Some((challenger, max)) = challenger else {
+                    return;
+                };
+
+                if max > leader_max {
+                    self.raise(leader, leader_max);
+                    self.raise(challenger, max.min(leader_max + 1));
+                } else {
+                    self.raise(challenger, max);
+                    self.raise(leader, leader_max.min(max + 1));
+                }
+            }
+        }
+
+        // moves escrow into the bid, making the bidder the leader
+        fn raise(&mut self, bidder: &'static str, amount: u128) {
+            let bid = self.bid(bidder);
+            if amount <= bid {
+                return;
+            }
+            let (max, reserve) = self.proxies.get_mut(bidder).unwrap();
+            *reserve -= amount - bid;
+            if amount >= *max && *reserve == 0 {
+                self.proxies.remove(bidder);
+            }
+            self.bids.insert(bidder, amount);
+            self.leader = (bidder, amount);
+        }
+
+        // refunds the bid and the proxy escrow, returning whether there was anything to refund
+        fn refund(&mut self, bidder: &'static str, receiver: &'static str) -> bool {
+            let bid = self.bids.remove(bidder).unwrap_or_default();
+            let (_, reserve) = self.proxies.remove(bidder).unwrap_or_default();
+            if bid + reserve == 0 {
+                return false;
+            }
+            self.pay(receiver, bid + reserve);
+            true
+        }
+
+        fn pay(&mut self, receiver: &'static str, amount: u128) {
+            *self.balances.get_mut(receiver).unwrap() += amount;
+            if receiver == OWNER {
+                self.owner_funds += amount;
+            }
+        }
+
+        // bids and escrow still held by the contract, the winning bid being paid out at close
+        fn outstanding(&self) -> u128 {
+            let bids: u128 = self.bids.values().sum();
+            let reserves: u128 = self.proxies.values().map(|(_, reserve)| reserve).sum();
+            if self.closed {
+                bids + reserves - self.leader.1
+            } else {
+                bids + reserves
+            }
+        }
+    }
+
+    fn balance(suite: &Suite, addr: &str) -> u128 {
+        suite
+            .app
+            .wrap()
+            .query_balance(addr, DENOM)
+            .unwrap()
+            .amount
+            .u128()
+    }
+
+    fn assert_invariants(suite: &Suite, contract: &BiddingContract, model: &Model, case: &str) {
+        for account in ACCOUNTS {
+            assert_eq!(
+                balance(suite, account),
+                model.balances[account],
+                "{case}: balance of {account}"
+            );
+        }
+
+        // the contract holds exactly what is outstanding
+        let res: OutstandingRefundsResponse = suite
+            .app
+            .wrap()
+            .query_wasm_smart(
+                contract.addr(),
+                &QueryMsg::OutstandingRefunds {
+                    auction_id: 1,
+                    start_after: None,
+                    limit: Some(30),
+                },
+            )
+            .unwrap();
+        let refunds: Uint128 = res.refunds.iter().map(|refund| refund.amount).sum();
+        let held = balance(suite, contract.addr().as_str());
+        assert_eq!(held, refunds.u128(), "{case}: outstanding refunds");
+        assert_eq!(held, model.outstanding(), "{case}: contract balance");
+
+        // the owner receives exactly the winning bid and every commission
+        let paid = if model.closed { model.leader.1 } else { 0 };
+        assert_eq!(
+            balance(suite, OWNER) - model.owner_funds,
+            paid + model.commissions,
+            "{case}: owner balance"
+        );
+    }
+
+    #[test]
+    fn bidding_invariants() {
+        for seed in 0..CASES {
+            let mut rng = Rng(seed);
+
+            // the owner opens the auction with everything they have
+            let initial_bid = 1 + rng.below(2_000_000) as u128;
+            let mut model = Model::new(initial_bid);
+            let mut builder = SuiteBuilder::new(OWNER).with_funds(OWNER, coins(initial_bid, DENOM));
+            for bidder in BIDDERS {
+                let funds = 1 + rng.below(20_000_000) as u128;
+                builder = builder.with_funds(bidder, coins(funds, DENOM));
+                model.balances.insert(bidder, funds);
+            }
+            let mut suite = builder.build();
+            let contract = suite.instantiate(
+                &InstantiateMsg {
+                    commodity: "gold".to_string(),
+                    ..InstantiateMsg::default()
+                },
+                &coins(initial_bid, DENOM),
+            );
+            assert_invariants(&suite, &contract, &model, &format!("seed {seed}"));
+
+            for step in 0..STEPS {
+                let action = Action::random(&mut rng, &model);
+                let case = format!("seed {seed}, step {step}, {action:?}");
+
+                let accepted = model.apply(&action);
+                let res = action.execute(&mut suite, &contract);
+                assert_eq!(res.is_ok(), accepted, "{case}: {res:?}");
+                assert_invariants(&suite, &contract, &model, &case);
+
+                // nobody can retract twice
+                if let (Action::Retract { .. }, true) = (&action, accepted) {
+                    assert!(action.execute(&mut suite, &contract).is_err(), "{case}");
+                    assert_invariants(&suite, &contract, &model, &case);
+                }
+            }
+        }
+    }
+}
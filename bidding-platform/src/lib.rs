@@ -1,8 +1,11 @@
 pub mod contract;
 mod error;
+pub mod event;
 pub mod execute;
 pub mod helpers;
 pub mod integration_tests;
+pub mod invariant_tests;
+pub mod migrate;
 pub mod msg;
 pub mod query;
 pub mod state;
@@ -0,0 +1,100 @@
+use cosmwasm_std::{Decimal, DepsMut, Order, StdError, StdResult, Uint128};
+use cw20::Denom;
+use cw_utils::Expiration;
+
+use crate::state::{
+    auctions, bids, Auction, BidStatus, Commission, CommissionMode, Config, Winner, AUCTION_COUNT,
+    CONFIG, OWNER, WINNER,
+};
+
+// the single auction of v0.1 contracts becomes the first auction of the auction house
+const AUCTION_ID: u64 = 1;
+
+/// Storage layout of v0.1 contracts, which only ever ran one auction.
+pub mod v0_1 {
+    use cosmwasm_schema::cw_serde;
+    use cosmwasm_std::{Addr, Uint128};
+    use cw_storage_plus::{Item, Map};
+
+    // commission rates were expressed in millionths
+    pub const COMMISSION_DECIMALS: u32 = 6;
+
+    #[cw_serde]
+    pub struct Config {
+        pub commodity: String,
+        pub contract_owner: Addr,
+        pub commission: u128,
+        pub denom: String,
+        pub status: BidStatus,
+    }
+
+    #[cw_serde]
+    pub enum BidStatus {
+        Opened,
+        Closed,
+    }
+
+    pub const CONFIG: Item<Config> = Item::new("config");
+    pub const BIDS: Map<Addr, Uint128> = Map::new("bids");
+    pub const WINNER: Item<(Addr, Uint128)> = Item::new("winner");
+}
+
+// moves the v0.1 auction, its bids and its winner under auction id 1, and the owner to OWNER
+pub fn from_v0_1(mut deps: DepsMut) -> StdResult<()> {
+    let old_cfg = v0_1::CONFIG.load(deps.storage)?;
+
+    let rate = Decimal::from_atomics(old_cfg.commission, v0_1::COMMISSION_DECIMALS)
+        .map_err(|_| StdError::generic_err("Commission rate out of range"))?;
+    let cfg = Config {
+        commission: Commission {
+            rate,
+            tiers: vec![],
+            mode: CommissionMode::EveryBid,
+        },
+        fee_collector: None,
+        sellers: None,
+    };
+    CONFIG.save(deps.storage, &cfg)?;
+    OWNER.set(deps.branch(), Some(old_cfg.contract_owner.clone()))?;
+
+    let auction = Auction {
+        commodity: old_cfg.commodity,
+        seller: old_cfg.contract_owner,
+        denom: Denom::Native(old_cfg.denom),
+        status: match old_cfg.status {
+            v0_1::BidStatus::Opened => BidStatus::Opened,
+            v0_1::BidStatus::Closed => BidStatus::Closed,
+        },
+        expiration: Expiration::Never {},
+        soft_close: None,
+        reserve_price: None,
+        min_increment: None,
+        item: None,
+        sealed: None,
+        dutch: None,
+        buy_now: None,
+        bidders: None,
+        max_bid: None,
+        accepted_denoms: None,
+    };
+    AUCTION_COUNT.save(deps.storage, &AUCTION_ID)?;
+    auctions().save(deps.storage, AUCTION_ID, &auction)?;
+
+    let (bidder, amount) = v0_1::WINNER.load(deps.storage)?;
+    v0_1::WINNER.remove(deps.storage);
+    WINNER.save(deps.storage, AUCTION_ID, &Winner { bidder, amount })?;
+
+    // both layouts share the "bids" namespace, so every old entry is read before any new one is
+    // written, and retracted bids, which were zeroed instead of removed, are dropped
+    let old_bids = v0_1::BIDS
+        .range(deps.storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<_>>>()?;
+    for (bidder, bid) in old_bids {
+        v0_1::BIDS.remove(deps.storage, bidder.clone());
+        if bid != Uint128::zero() {
+            bids().save(deps.storage, (AUCTION_ID, bidder), &bid)?;
+        }
+    }
+
+    Ok(())
+}
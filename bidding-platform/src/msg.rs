@@ -1,15 +1,161 @@
 use cosmwasm_schema::{cw_serde, QueryResponses};
-use cosmwasm_std::{StdError, StdResult, Uint128};
+use cosmwasm_std::{Binary, Coin, Decimal, Order, StdError, StdResult, Timestamp, Uint128};
+use cw1155::Cw1155ReceiveMsg;
+use cw20::{Cw20ReceiveMsg, Denom};
+use cw_utils::{Duration, Expiration};
+
+use crate::state::{
+    AcceptedDenoms, AuctionItem, BidStatus, Bidders, BuyNow, Commission, Commitment, Decay,
+    DutchAuction, Forfeit, MinIncrement, Pricing, SealedBid, SoftClose,
+};
+#[cfg(test)]
+use crate::state::{CommissionMode, CommissionTier};
 
 #[cw_serde]
 #[cfg_attr(test, derive(Default))]
 pub struct InstantiateMsg {
     pub commodity: String,
     pub contract_owner: Option<String>,
-    pub commision: Option<u128>,
+    // 0.1% of every bid when unset
+    pub commission: Option<Commission>,
+    // commissions are paid to the contract owner when unset
+    pub fee_collector: Option<String>,
+    // how long the bid stays open, it can only be closed by the owner when unset
+    pub duration: Option<Duration>,
+    // extends the bid when someone bids right before it expires, requires a duration
+    pub soft_close: Option<SoftClose>,
+    // the commodity is not sold and every bidder is refunded if the winning bid is below it
+    pub reserve_price: Option<Uint128>,
+    // how much a new bid has to raise the highest bid by
+    pub min_increment: Option<MinIncrement>,
+    // bids are made in this cw20 token instead of the native token sent at instantiation
+    pub cw20_addr: Option<String>,
+    // bids are committed and revealed instead of being public, requires a duration
+    pub sealed: Option<SealedBidMsg>,
+    // sells to the first buyer paying the current, decreasing, price instead, requires a duration
+    pub dutch: Option<DutchAuctionMsg>,
+    // anyone paying this price closes the bid right away and wins
+    pub buy_now_price: Option<Uint128>,
+    // buying now is disabled once the highest bid exceeds this fraction of the buy now price,
+    // 100% when unset
+    pub buy_now_cutoff: Option<Decimal>,
+    // only these addresses can bid, more can be added later by the seller or the contract owner
+    pub allowlist: Option<Vec<String>>,
+    // only members of this cw4 group can bid
+    pub bidder_group: Option<String>,
+    // highest total bid a single bidder can reach
+    pub max_bid: Option<Uint128>,
+    // bids can also be made in these native denoms, valued in the initial bidder denom
+    pub accepted_denoms: Option<Vec<String>>,
+    // values accepted denoms at the prices of this oracle instead of the contract owner's rates
+    pub price_oracle: Option<String>,
+    // addresses allowed to create more auctions besides the contract owner, anyone when unset
+    pub sellers: Option<Vec<String>>,
 }
 
 impl InstantiateMsg {
+    pub fn validate(&self) -> StdResult<()> {
+        // validate commission
+        if !self.has_valid_commission() {
+            return Err(StdError::generic_err(
+                "Commission rates cannot be above 100% and tiers must be sorted by bid size",
+            ));
+        }
+        // validate the first auction
+        self.auction().validate()
+    }
+    // the first auction is opened by the contract owner at instantiation
+    pub fn auction(&self) -> AuctionMsg {
+        AuctionMsg {
+            commodity: self.commodity.clone(),
+            duration: self.duration,
+            soft_close: self.soft_close.clone(),
+            reserve_price: self.reserve_price,
+            min_increment: self.min_increment.clone(),
+            cw20_addr: self.cw20_addr.clone(),
+            native_denom: None,
+            sealed: self.sealed.clone(),
+            dutch: self.dutch.clone(),
+            buy_now_price: self.buy_now_price,
+            buy_now_cutoff: self.buy_now_cutoff,
+            allowlist: self.allowlist.clone(),
+            bidder_group: self.bidder_group.clone(),
+            max_bid: self.max_bid,
+            accepted_denoms: self.accepted_denoms.clone(),
+            price_oracle: self.price_oracle.clone(),
+        }
+    }
+    // TODO: validate contract owner
+    fn has_valid_commission(&self) -> bool {
+        let Some(commission) = &self.commission else {
+            return true;
+        };
+        let rates_valid = std::iter::once(commission.rate)
+            .chain(commission.tiers.iter().map(|tier| tier.rate))
+            .all(|rate| rate <= Decimal::one());
+        let tiers_sorted = commission
+            .tiers
+            .windows(2)
+            .all(|tiers| tiers[0].min_bid < tiers[1].min_bid);
+        rates_valid && tiers_sorted
+    }
+}
+
+#[cw_serde]
+#[cfg_attr(test, derive(Default))]
+pub struct AuctionMsg {
+    pub commodity: String,
+    // how long the auction stays open, it can only be closed by the seller when unset
+    pub duration: Option<Duration>,
+    // extends the auction when someone bids right before it expires, requires a duration
+    pub soft_close: Option<SoftClose>,
+    // the commodity is not sold and every bidder is refunded if the winning bid is below it
+    pub reserve_price: Option<Uint128>,
+    // how much a new bid has to raise the highest bid by
+    pub min_increment: Option<MinIncrement>,
+    // bids are made in this cw20 token instead of the native token sent by the seller
+    pub cw20_addr: Option<String>,
+    // bids are made in this native token when the seller cannot send a deposit along,
+    // i.e. when the auction is opened by sending its item to the contract
+    pub native_denom: Option<String>,
+    // bids are committed and revealed instead of being public, requires a duration
+    pub sealed: Option<SealedBidMsg>,
+    // sells to the first buyer paying the current, decreasing, price instead, requires a duration
+    pub dutch: Option<DutchAuctionMsg>,
+    // anyone paying this price closes the auction right away and wins
+    pub buy_now_price: Option<Uint128>,
+    // buying now is disabled once the highest bid exceeds this fraction of the buy now price,
+    // 100% when unset
+    pub buy_now_cutoff: Option<Decimal>,
+    // only these addresses can bid, more can be added later by the seller or the contract owner
+    pub allowlist: Option<Vec<String>>,
+    // only members of this cw4 group can bid
+    pub bidder_group: Option<String>,
+    // highest total bid a single bidder can reach
+    pub max_bid: Option<Uint128>,
+    // bids can also be made in these native denoms, valued in the auction denom
+    pub accepted_denoms: Option<Vec<String>>,
+    // values accepted denoms at the prices of this oracle instead of the contract owner's rates
+    pub price_oracle: Option<String>,
+}
+
+#[cw_serde]
+pub struct SealedBidMsg {
+    // how long bids can be revealed once the auction expires, in the same unit as its duration
+    pub reveal_period: Duration,
+    pub pricing: Pricing,
+    pub forfeit: Forfeit,
+}
+
+#[cw_serde]
+pub struct DutchAuctionMsg {
+    pub start_price: Uint128,
+    // the price stays there once reached, until the auction expires
+    pub floor_price: Uint128,
+    pub decay: Decay,
+}
+
+impl AuctionMsg {
     pub fn validate(&self) -> StdResult<()> {
         // validate commodity name
         if !self.has_valid_name() {
@@ -17,6 +163,63 @@ impl InstantiateMsg {
                 "Commodity name is not in the expected format (3-50 UTF-8 bytes)",
             ));
         }
+        // validate bid duration
+        if !self.has_valid_duration() {
+            return Err(StdError::generic_err("Bid duration cannot be zero"));
+        }
+        // validate soft close
+        if !self.has_valid_soft_close() {
+            return Err(StdError::generic_err(
+                "Soft close requires a duration and non-zero periods in the same unit",
+            ));
+        }
+        // validate minimum increment
+        if !self.has_valid_min_increment() {
+            return Err(StdError::generic_err("Minimum increment cannot be zero"));
+        }
+        // validate sealed bids
+        if !self.has_valid_sealed() {
+            return Err(StdError::generic_err(
+                "Sealed bids require a duration and a non-zero reveal period in the same unit, \
+                 without soft close nor minimum increment",
+            ));
+        }
+        // validate dutch auction
+        if !self.has_valid_dutch() {
+            return Err(StdError::generic_err(
+                "Dutch auctions require a duration, a start price above the floor price and a \
+                 non-zero decay, without any bidding rule",
+            ));
+        }
+        // validate buy now price
+        if !self.has_valid_buy_now() {
+            return Err(StdError::generic_err(
+                "Buy now requires a non-zero price not below the reserve price and a cutoff \
+                 between 0 and 100%, without sealed bids nor dutch auction",
+            ));
+        }
+        // validate bidders
+        if self.allowlist.is_some() && self.bidder_group.is_some() {
+            return Err(StdError::generic_err(
+                "Bidders cannot be restricted by both an allowlist and a group",
+            ));
+        }
+        if self.max_bid.is_some_and(|max_bid| max_bid.is_zero()) {
+            return Err(StdError::generic_err("Maximum bid cannot be zero"));
+        }
+        // validate bid denom
+        if self.cw20_addr.is_some() && self.native_denom.is_some() {
+            return Err(StdError::generic_err(
+                "Bids cannot be made in both a cw20 and a native token",
+            ));
+        }
+        // validate accepted denoms
+        if !self.has_valid_accepted_denoms() {
+            return Err(StdError::generic_err(
+                "Bids in several denoms require a native auction without sealed bids, dutch \
+                 auction nor buy now price, and a price oracle requires accepted denoms",
+            ));
+        }
         Ok(())
     }
     fn has_valid_name(&self) -> bool {
@@ -26,35 +229,340 @@ impl InstantiateMsg {
         }
         true
     }
-    // TODO: validate contract owner
-    // TODO: validate commission
+    fn has_valid_duration(&self) -> bool {
+        !matches!(
+            self.duration,
+            Some(Duration::Height(0)) | Some(Duration::Time(0))
+        )
+    }
+    fn has_valid_soft_close(&self) -> bool {
+        let (duration, soft_close) = match (&self.duration, &self.soft_close) {
+            (_, None) => return true,
+            (None, Some(_)) => return false,
+            (Some(duration), Some(soft_close)) => (duration, soft_close),
+        };
+        let same_unit = |period: &Duration| {
+            matches!(
+                (duration, period),
+                (Duration::Height(_), Duration::Height(1..))
+                    | (Duration::Time(_), Duration::Time(1..))
+            )
+        };
+        let deadline_in_unit = matches!(
+            (duration, &soft_close.hard_deadline),
+            (_, None)
+                | (Duration::Height(_), Some(Expiration::AtHeight(_)))
+                | (Duration::Time(_), Some(Expiration::AtTime(_)))
+        );
+        same_unit(&soft_close.window) && same_unit(&soft_close.extension) && deadline_in_unit
+    }
+    fn has_valid_min_increment(&self) -> bool {
+        match self.min_increment {
+            Some(MinIncrement::Absolute(amount)) => !amount.is_zero(),
+            Some(MinIncrement::Percentage(rate)) => !rate.is_zero(),
+            None => true,
+        }
+    }
+    fn has_valid_sealed(&self) -> bool {
+        let Some(sealed) = &self.sealed else {
+            return true;
+        };
+        // bids are hidden, so they cannot extend the auction nor be raised by some increment
+        if self.soft_close.is_some() || self.min_increment.is_some() {
+            return false;
+        }
+        matches!(
+            (&self.duration, &sealed.reveal_period),
+            (Some(Duration::Height(_)), Duration::Height(1..))
+                | (Some(Duration::Time(_)), Duration::Time(1..))
+        )
+    }
+    fn has_valid_dutch(&self) -> bool {
+        let Some(dutch) = &self.dutch else {
+            return true;
+        };
+        // there are no bids to apply these rules to
+        if self.soft_close.is_some()
+            || self.reserve_price.is_some()
+            || self.min_increment.is_some()
+            || self.sealed.is_some()
+        {
+            return false;
+        }
+        let valid_decay = match dutch.decay {
+            Decay::Linear => true,
+            Decay::Step { interval, amount } => interval > 0 && !amount.is_zero(),
+        };
+        self.duration.is_some() && dutch.start_price > dutch.floor_price && valid_decay
+    }
+    fn has_valid_buy_now(&self) -> bool {
+        let Some(price) = self.buy_now_price else {
+            return self.buy_now_cutoff.is_none();
+        };
+        // sealed bids cannot be compared to the price and dutch auctions are bought anyway
+        if self.sealed.is_some() || self.dutch.is_some() {
+            return false;
+        }
+        let valid_cutoff = self
+            .buy_now_cutoff
+            .map_or(true, |cutoff| !cutoff.is_zero() && cutoff <= Decimal::one());
+        !price.is_zero()
+            && self.reserve_price.map_or(true, |reserve| reserve <= price)
+            && valid_cutoff
+    }
+    fn has_valid_accepted_denoms(&self) -> bool {
+        let Some(denoms) = &self.accepted_denoms else {
+            return self.price_oracle.is_none();
+        };
+        // deposits in other denoms are only valued as bids are placed, so they can neither be
+        // committed, nor pay a price, nor be sent through a cw20 token
+        !denoms.is_empty()
+            && self.cw20_addr.is_none()
+            && self.sealed.is_none()
+            && self.dutch.is_none()
+            && self.buy_now_price.is_none()
+    }
 }
 
+// contracts are migrated from whatever older version they run, nothing to configure
+#[cw_serde]
+pub struct MigrateMsg {}
+
+// messages are only deserialized once per call, boxing the auction settings is not worth it
+#[allow(clippy::large_enum_variant)]
 #[cw_serde]
 pub enum ExecuteMsg {
-    // any user other than the contract owner can raise their bid by sending tokens to the contract with the Bid {} message
-    Bid {},
-    // only owner can close bid
-    Close {},
+    // opens a new auction sold by the sender, who sends the minimum required bid along like at instantiation
+    CreateAuction(AuctionMsg),
+    // any user other than the seller can raise their bid by sending tokens to the contract with the Bid {} message
+    Bid {
+        auction_id: u64,
+    },
+    // only the seller can close their auction
+    Close {
+        auction_id: u64,
+    },
     // sends all senders bids (minus commissions) to the receiver account (if provided) or to the original bidder
-    Retract { receiver: Option<String> },
+    Retract {
+        auction_id: u64,
+        receiver: Option<String>,
+    },
+    // outbid bidders can withdraw `amount` of their bid, or all of it, while the auction is open;
+    // this also cancels their max bid and sends back what is left of its escrow
+    Withdraw {
+        auction_id: u64,
+        amount: Option<Uint128>,
+        receiver: Option<String>,
+    },
+    // anyone can finalize an auction once it has expired, paying the winning bid to the seller
+    Finalize {
+        auction_id: u64,
+    },
+    // commits to a sealed bid with a hash of it (see helpers::bid_commitment) and a deposit covering it
+    Commit {
+        auction_id: u64,
+        commitment: Binary,
+    },
+    // reveals a committed bid once the auction expired, only revealed bids can win
+    Reveal {
+        auction_id: u64,
+        amount: Uint128,
+        salt: String,
+    },
+    // buys a dutch auction at its current price, sending back any change
+    Buy {
+        auction_id: u64,
+    },
+    // buys the item at its buy now price, sending back any change and the buyer's previous bid
+    BuyNow {
+        auction_id: u64,
+    },
+    // escrows the tokens sent along for the contract to outbid anyone else by the minimum
+    // increment, up to a total bid of `max`, which the sender's escrow has to cover
+    SetMaxBid {
+        auction_id: u64,
+        max: Uint128,
+    },
+    // the seller or the contract owner adds or removes bidders from the auction allowlist
+    UpdateBidders {
+        auction_id: u64,
+        add: Vec<String>,
+        remove: Vec<String>,
+    },
+    // anyone can refund up to `limit` bidders at once once the auction is closed, like Retract would
+    RefundAll {
+        auction_id: u64,
+        limit: Option<u32>,
+    },
+    // the contract owner proposes a new owner, who has to accept before taking over
+    ProposeOwner {
+        new_owner: String,
+    },
+    AcceptOwnership {},
+    // the contract owner ends an auction without a sale, every bid becomes refundable
+    Cancel {
+        auction_id: u64,
+    },
+    // the contract owner updates the settings open auctions do not depend on, unset ones are kept
+    UpdateConfig {
+        fee_collector: Option<String>,
+        sellers: Option<Vec<String>>,
+    },
+    // the contract owner collects commissions again, and lets anyone sell again, for each setting
+    // cleared
+    ClearConfig {
+        fee_collector: bool,
+        sellers: bool,
+    },
+    // the contract owner sets the price of one `denom` in `reference`, which bids in `denom` are
+    // valued at on auctions held in `reference` without a price oracle, or removes it when unset
+    SetRate {
+        denom: String,
+        reference: String,
+        rate: Option<Decimal>,
+    },
+    // cw20 bids and cw1155 items are sent through their token contract with a ReceiveMsg or a DepositMsg
+    Receive(ReceiveHook),
+    // cw721 items are sent through their nft contract with a DepositMsg
+    ReceiveNft(Cw721ReceiveMsg),
+}
+
+// cw20 and cw1155 contracts both call the `receive` hook, they are told apart by their fields
+#[cw_serde]
+#[serde(untagged)]
+pub enum ReceiveHook {
+    Cw20(Cw20ReceiveMsg),
+    Cw1155(Cw1155ReceiveMsg),
+}
+
+#[cw_serde]
+pub enum ReceiveMsg {
+    // same as ExecuteMsg::Bid {}, with the cw20 tokens sent along
+    Bid { auction_id: u64 },
+    // same as ExecuteMsg::Commit {}, with the cw20 deposit sent along
+    Commit { auction_id: u64, commitment: Binary },
+    // same as ExecuteMsg::Buy {}, with the cw20 tokens sent along
+    Buy { auction_id: u64 },
+    // same as ExecuteMsg::BuyNow {}, with the cw20 tokens sent along
+    BuyNow { auction_id: u64 },
+    // same as ExecuteMsg::SetMaxBid {}, with the cw20 tokens sent along
+    SetMaxBid { auction_id: u64, max: Uint128 },
+}
+
+#[cw_serde]
+pub enum DepositMsg {
+    // same as ExecuteMsg::CreateAuction {}, escrowing the cw721 or cw1155 item sent along
+    CreateAuction(AuctionMsg),
+}
+
+// cw721 receiver hook, as sent by `SendNft` in the cw721 spec
+#[cw_serde]
+pub struct Cw721ReceiveMsg {
+    pub sender: String,
+    pub token_id: String,
+    pub msg: Binary,
+}
+
+// the only cw721 message needed to release escrowed nfts
+#[cw_serde]
+pub enum Cw721ExecuteMsg {
+    TransferNft { recipient: String, token_id: String },
 }
 
 #[cw_serde]
 #[derive(QueryResponses)]
 pub enum QueryMsg {
+    /// Returns whether the given address can bid on the auction and how much more it can bid.
+    #[returns(EligibilityResponse)]
+    Eligibility { auction_id: u64, address: String },
+    /// Returns the allowlist of the auction, empty unless bidders are restricted by an allowlist.
+    #[returns(ListBiddersResponse)]
+    ListBidders {
+        auction_id: u64,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    /// Returns the house wide settings and who owns the contract.
+    #[returns(ConfigResponse)]
+    Config {},
+    /// Returns the price bids in the given denom are currently valued at in the auction denom.
+    #[returns(RateResponse)]
+    Rate { auction_id: u64, denom: String },
+    /// Returns the coins the given address deposited on an auction taking several denoms.
+    #[returns(DepositsResponse)]
+    Deposits { auction_id: u64, address: String },
     /// Returns the total bid of the given address, 0 if unset.
     #[returns(TotalBidResponse)]
-    TotalBid { address: String },
+    TotalBid { auction_id: u64, address: String },
     /// Returns the highest bidder and their total bid amount.
     #[returns(HighestBidderResponse)]
-    HighestBidder {},
-    /// Returns true if bid is closed.
+    HighestBidder { auction_id: u64 },
+    /// Returns true if bid is closed or has expired.
     #[returns(IsBidClosedResponse)]
-    IsBidClosed {},
-    /// Returns the highest bidder and their total bid amount.
+    IsBidClosed { auction_id: u64 },
+    /// Returns the bid winner once the bid is closed, none if the reserve price was not met.
     #[returns(BidWinnerResponse)]
-    BidWinner {},
+    BidWinner { auction_id: u64 },
+    /// Returns when the bid expires, taking soft close extensions into account.
+    #[returns(ExpirationResponse)]
+    Expiration { auction_id: u64 },
+    /// Returns the auction details along with its highest bid.
+    #[returns(AuctionResponse)]
+    Auction { auction_id: u64 },
+    /// Lists auctions by id, optionally filtered by status and seller.
+    #[returns(ListAuctionsResponse)]
+    ListAuctions {
+        status: Option<BidStatus>,
+        seller: Option<String>,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+    /// Returns the sealed bid commitment of the given address until it is revealed.
+    #[returns(CommitmentResponse)]
+    Commitment { auction_id: u64, address: String },
+    /// Returns the price a dutch auction can be bought at in the current block.
+    #[returns(CurrentPriceResponse)]
+    CurrentPrice { auction_id: u64 },
+    /// Lists what the auction still holds for every bidder, by denom: bids, unused proxy escrow
+    /// and unrevealed deposits that are not forfeited. Refundable once it is closed, except for
+    /// the winning bid, which is left out once paid to the seller.
+    #[returns(OutstandingRefundsResponse)]
+    OutstandingRefunds {
+        auction_id: u64,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    /// Lists the total bids of an auction by amount, highest first unless the order is ascending.
+    #[returns(ListBidsResponse)]
+    ListBids {
+        auction_id: u64,
+        start_after: Option<String>,
+        limit: Option<u32>,
+        order: Option<OrderBy>,
+    },
+    /// Lists every bid placed on an auction, oldest first.
+    #[returns(BidHistoryResponse)]
+    BidHistory {
+        auction_id: u64,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+}
+
+// cosmwasm_std::Order cannot be sent in messages
+#[cw_serde]
+pub enum OrderBy {
+    Ascending,
+    Descending,
+}
+
+impl From<OrderBy> for Order {
+    fn from(order: OrderBy) -> Self {
+        match order {
+            OrderBy::Ascending => Order::Ascending,
+            OrderBy::Descending => Order::Descending,
+        }
+    }
 }
 
 // We define a custom struct for each query response
@@ -76,7 +584,135 @@ pub struct IsBidClosedResponse {
 
 #[cw_serde]
 pub struct BidWinnerResponse {
-    pub winner: String,
+    pub winner: Option<String>,
+}
+
+#[cw_serde]
+pub struct ExpirationResponse {
+    pub expiration: Expiration,
+    pub soft_close: Option<SoftClose>,
+    pub is_expired: bool,
+}
+
+#[cw_serde]
+pub struct AuctionResponse {
+    pub auction_id: u64,
+    pub commodity: String,
+    pub seller: String,
+    pub denom: Denom,
+    // closed once the auction expired, even if nobody finalized it yet
+    pub status: BidStatus,
+    pub expiration: Expiration,
+    pub highest_bidder: String,
+    pub highest_bid: Uint128,
+    pub item: Option<AuctionItem>,
+    pub sealed: Option<SealedBid>,
+    pub dutch: Option<DutchAuction>,
+    pub buy_now: Option<BuyNow>,
+    pub bidders: Option<Bidders>,
+    pub max_bid: Option<Uint128>,
+    pub accepted_denoms: Option<AcceptedDenoms>,
+}
+
+#[cw_serde]
+pub struct ListAuctionsResponse {
+    pub auctions: Vec<AuctionResponse>,
+}
+
+#[cw_serde]
+pub struct CommitmentResponse {
+    pub commitment: Option<Commitment>,
+}
+
+#[cw_serde]
+pub struct CurrentPriceResponse {
+    pub price: Uint128,
+}
+
+#[cw_serde]
+pub struct Refund {
+    pub bidder: String,
+    pub amount: Uint128,
+    pub denom: String,
+}
+
+#[cw_serde]
+pub struct OutstandingRefundsResponse {
+    pub refunds: Vec<Refund>,
+}
+
+#[cw_serde]
+pub struct BidInfo {
+    pub bidder: String,
+    pub total_bid: Uint128,
+}
+
+#[cw_serde]
+pub struct ListBidsResponse {
+    pub bids: Vec<BidInfo>,
+}
+
+#[cw_serde]
+pub struct BidHistoryEntry {
+    pub id: u64,
+    pub bidder: String,
+    pub gross: Uint128,
+    pub commission: Uint128,
+    pub net: Uint128,
+    pub height: u64,
+    pub time: Timestamp,
+    // raised by the contract on behalf of the bidder, up to their max bid
+    pub proxy: bool,
+}
+
+#[cw_serde]
+pub struct BidHistoryResponse {
+    pub bids: Vec<BidHistoryEntry>,
+}
+
+#[cw_serde]
+pub struct EligibilityResponse {
+    pub eligible: bool,
+    pub max_bid: Option<Uint128>,
+    // how much more the address can add to its total bid before reaching the maximum bid
+    pub remaining: Option<Uint128>,
+}
+
+#[cw_serde]
+pub struct ListBiddersResponse {
+    pub bidders: Vec<String>,
+}
+
+#[cw_serde]
+pub struct RateResponse {
+    pub rate: Decimal,
+}
+
+#[cw_serde]
+pub struct DepositsResponse {
+    pub deposits: Vec<Coin>,
+}
+
+// query price oracles have to answer to value bids in other denoms than the auction's
+#[cw_serde]
+pub enum OracleQueryMsg {
+    // price of one `base` in `quote`
+    Price { base: String, quote: String },
+}
+
+#[cw_serde]
+pub struct PriceResponse {
+    pub rate: Decimal,
+}
+
+#[cw_serde]
+pub struct ConfigResponse {
+    pub contract_owner: String,
+    /// Proposed owner who has not accepted yet.
+    pub pending_owner: Option<String>,
+    pub commission: Commission,
+    pub fee_collector: String,
+    pub sellers: Option<Vec<String>>,
 }
 
 #[cfg(test)]
@@ -84,11 +720,11 @@ mod tests {
     use super::*;
 
     #[test]
-    fn validate_instantiate_msg_name() {
+    fn validate_auction_msg_name() {
         // Too short
-        let mut msg = InstantiateMsg {
+        let mut msg = AuctionMsg {
             commodity: str::repeat("a", 2),
-            ..InstantiateMsg::default()
+            ..AuctionMsg::default()
         };
         assert!(!msg.has_valid_name());
 
@@ -100,4 +736,269 @@ mod tests {
         msg.commodity = str::repeat("a", 51);
         assert!(!msg.has_valid_name());
     }
+
+    #[test]
+    fn validate_auction_msg_duration() {
+        // No duration
+        let mut msg = AuctionMsg::default();
+        assert!(msg.has_valid_duration());
+
+        // Zero duration
+        msg.duration = Some(Duration::Height(0));
+        assert!(!msg.has_valid_duration());
+        msg.duration = Some(Duration::Time(0));
+        assert!(!msg.has_valid_duration());
+
+        // Non-zero duration
+        msg.duration = Some(Duration::Time(3600));
+        assert!(msg.has_valid_duration());
+    }
+
+    #[test]
+    fn validate_auction_msg_soft_close() {
+        let soft_close = SoftClose {
+            window: Duration::Time(60),
+            extension: Duration::Time(120),
+            hard_deadline: Some(Expiration::AtTime(Timestamp::from_seconds(7200))),
+        };
+
+        // Soft close without duration
+        let mut msg = AuctionMsg {
+            soft_close: Some(soft_close.clone()),
+            ..AuctionMsg::default()
+        };
+        assert!(!msg.has_valid_soft_close());
+
+        // Soft close in the same unit as the duration
+        msg.duration = Some(Duration::Time(3600));
+        assert!(msg.has_valid_soft_close());
+
+        // Soft close in a different unit than the duration
+        msg.duration = Some(Duration::Height(100));
+        assert!(!msg.has_valid_soft_close());
+
+        // Hard deadline in a different unit than the duration
+        msg.duration = Some(Duration::Time(3600));
+        msg.soft_close = Some(SoftClose {
+            hard_deadline: Some(Expiration::AtHeight(1000)),
+            ..soft_close.clone()
+        });
+        assert!(!msg.has_valid_soft_close());
+
+        // Zero extension
+        msg.soft_close = Some(SoftClose {
+            extension: Duration::Time(0),
+            ..soft_close
+        });
+        assert!(!msg.has_valid_soft_close());
+    }
+
+    #[test]
+    fn validate_auction_msg_min_increment() {
+        // No minimum increment
+        let mut msg = AuctionMsg::default();
+        assert!(msg.has_valid_min_increment());
+
+        // Zero minimum increment
+        msg.min_increment = Some(MinIncrement::Absolute(Uint128::zero()));
+        assert!(!msg.has_valid_min_increment());
+        msg.min_increment = Some(MinIncrement::Percentage(Decimal::zero()));
+        assert!(!msg.has_valid_min_increment());
+
+        // Non-zero minimum increment
+        msg.min_increment = Some(MinIncrement::Percentage(Decimal::percent(5)));
+        assert!(msg.has_valid_min_increment());
+    }
+
+    #[test]
+    fn validate_auction_msg_sealed() {
+        // Sealed bids without duration
+        let mut msg = AuctionMsg {
+            sealed: Some(SealedBidMsg {
+                reveal_period: Duration::Time(600),
+                pricing: Pricing::SecondPrice,
+                forfeit: Forfeit::Seller,
+            }),
+            ..AuctionMsg::default()
+        };
+        assert!(!msg.has_valid_sealed());
+
+        // Reveal period in the same unit as the duration
+        msg.duration = Some(Duration::Time(3600));
+        assert!(msg.has_valid_sealed());
+
+        // Reveal period in a different unit than the duration
+        msg.duration = Some(Duration::Height(100));
+        assert!(!msg.has_valid_sealed());
+
+        // Sealed bids cannot have a minimum increment
+        msg.duration = Some(Duration::Time(3600));
+        msg.min_increment = Some(MinIncrement::Absolute(Uint128::one()));
+        assert!(!msg.has_valid_sealed());
+    }
+
+    #[test]
+    fn validate_instantiate_msg_commission() {
+        // Default commission
+        let mut msg = InstantiateMsg::default();
+        assert!(msg.has_valid_commission());
+
+        // Tiered commission
+        let tier = |min_bid: u128, rate: Decimal| CommissionTier {
+            min_bid: Uint128::from(min_bid),
+            rate,
+        };
+        msg.commission = Some(Commission {
+            rate: Decimal::percent(100),
+            tiers: vec![
+                tier(1_000, Decimal::percent(5)),
+                tier(10_000, Decimal::percent(1)),
+            ],
+            mode: CommissionMode::EveryBid,
+        });
+        assert!(msg.has_valid_commission());
+
+        // Tiers out of order
+        msg.commission = Some(Commission {
+            rate: Decimal::percent(10),
+            tiers: vec![
+                tier(10_000, Decimal::percent(1)),
+                tier(1_000, Decimal::percent(5)),
+            ],
+            mode: CommissionMode::EveryBid,
+        });
+        assert!(!msg.has_valid_commission());
+
+        // Rates above 100%
+        msg.commission = Some(Commission {
+            rate: Decimal::percent(101),
+            tiers: vec![],
+            mode: CommissionMode::WinningBid,
+        });
+        assert!(!msg.has_valid_commission());
+        msg.commission = Some(Commission {
+            rate: Decimal::percent(10),
+            tiers: vec![tier(1_000, Decimal::percent(150))],
+            mode: CommissionMode::WinningBid,
+        });
+        assert!(!msg.has_valid_commission());
+    }
+
+    #[test]
+    fn validate_auction_msg_dutch() {
+        // Dutch auction without duration
+        let mut msg = AuctionMsg {
+            dutch: Some(DutchAuctionMsg {
+                start_price: Uint128::from(100u128),
+                floor_price: Uint128::from(10u128),
+                decay: Decay::Linear,
+            }),
+            ..AuctionMsg::default()
+        };
+        assert!(!msg.has_valid_dutch());
+
+        // Dutch auction with duration
+        msg.duration = Some(Duration::Height(100));
+        assert!(msg.has_valid_dutch());
+
+        // Zero step
+        msg.dutch = Some(DutchAuctionMsg {
+            start_price: Uint128::from(100u128),
+            floor_price: Uint128::from(10u128),
+            decay: Decay::Step {
+                interval: 0,
+                amount: Uint128::from(5u128),
+            },
+        });
+        assert!(!msg.has_valid_dutch());
+
+        // Floor price above the start price
+        msg.dutch = Some(DutchAuctionMsg {
+            start_price: Uint128::from(10u128),
+            floor_price: Uint128::from(100u128),
+            decay: Decay::Linear,
+        });
+        assert!(!msg.has_valid_dutch());
+    }
+
+    #[test]
+    fn validate_auction_msg_buy_now() {
+        // Cutoff without a buy now price
+        let mut msg = AuctionMsg {
+            buy_now_cutoff: Some(Decimal::percent(50)),
+            ..AuctionMsg::default()
+        };
+        assert!(!msg.has_valid_buy_now());
+
+        // Buy now price with a cutoff
+        msg.buy_now_price = Some(Uint128::from(100u128));
+        assert!(msg.has_valid_buy_now());
+
+        // Cutoff above 100%
+        msg.buy_now_cutoff = Some(Decimal::percent(150));
+        assert!(!msg.has_valid_buy_now());
+
+        // Buy now price below the reserve price
+        msg.buy_now_cutoff = None;
+        msg.reserve_price = Some(Uint128::from(200u128));
+        assert!(!msg.has_valid_buy_now());
+
+        // Buy now price on a sealed auction
+        msg.reserve_price = None;
+        msg.duration = Some(Duration::Height(100));
+        msg.sealed = Some(SealedBidMsg {
+            reveal_period: Duration::Height(10),
+            pricing: Pricing::FirstPrice,
+            forfeit: Forfeit::Bidder,
+        });
+        assert!(!msg.has_valid_buy_now());
+    }
+
+    #[test]
+    fn validate_auction_msg_bidders() {
+        // Allowlist and group at once
+        let mut msg = AuctionMsg {
+            commodity: "gold".to_string(),
+            allowlist: Some(vec!["alex".to_string()]),
+            bidder_group: Some("group".to_string()),
+            ..AuctionMsg::default()
+        };
+        assert!(msg.validate().is_err());
+
+        // Allowlist only
+        msg.bidder_group = None;
+        assert!(msg.validate().is_ok());
+
+        // Zero maximum bid
+        msg.max_bid = Some(Uint128::zero());
+        assert!(msg.validate().is_err());
+    }
+
+    #[test]
+    fn validate_auction_msg_accepted_denoms() {
+        // Price oracle without accepted denoms
+        let mut msg = AuctionMsg {
+            commodity: "gold".to_string(),
+            price_oracle: Some("oracle".to_string()),
+            ..AuctionMsg::default()
+        };
+        assert!(msg.validate().is_err());
+
+        // No accepted denoms
+        msg.accepted_denoms = Some(vec![]);
+        assert!(msg.validate().is_err());
+
+        // Accepted denoms priced by an oracle
+        msg.accepted_denoms = Some(vec!["uosmo".to_string()]);
+        assert!(msg.validate().is_ok());
+
+        // Along a buy now price
+        msg.buy_now_price = Some(Uint128::from(10u128));
+        assert!(msg.validate().is_err());
+
+        // Along cw20 bids
+        msg.buy_now_price = None;
+        msg.cw20_addr = Some("token".to_string());
+        assert!(msg.validate().is_err());
+    }
 }
@@ -1,46 +1,469 @@
-use cosmwasm_std::{Deps, StdError, StdResult};
+use cosmwasm_std::{
+    to_json_binary, Addr, Coin, Decimal, Deps, Env, Order, QueryRequest, StdError, StdResult,
+    Uint128, WasmQuery,
+};
+use cw20::Denom;
+use cw_storage_plus::Bound;
+use cw_utils::maybe_addr;
 
 use crate::{
-    msg::{BidWinnerResponse, HighestBidderResponse, IsBidClosedResponse, TotalBidResponse},
-    state::{BidStatus, BIDS, CONFIG, WINNER},
+    execute,
+    msg::{
+        AuctionResponse, BidHistoryEntry, BidHistoryResponse, BidInfo, BidWinnerResponse,
+        CommitmentResponse, ConfigResponse, CurrentPriceResponse, DepositsResponse,
+        EligibilityResponse, ExpirationResponse, HighestBidderResponse, IsBidClosedResponse,
+        ListAuctionsResponse, ListBiddersResponse, ListBidsResponse, OracleQueryMsg, OrderBy,
+        OutstandingRefundsResponse, PriceResponse, RateResponse, Refund, TotalBidResponse,
+    },
+    state::{
+        auctions, bids, proxy_bids, Auction, BidStatus, Bidders, Pricing, ALLOWED_BIDDERS,
+        BID_HISTORY, COMMITMENTS, CONFIG, DEPOSITS, OWNER, PENDING_OWNER, RATES, RUNNER_UP, WINNER,
+    },
 };
 
-pub fn highest_bid(deps: Deps) -> StdResult<HighestBidderResponse> {
+// settings for pagination
+const MAX_LIMIT: u32 = 30;
+const DEFAULT_LIMIT: u32 = 10;
+
+pub fn eligibility(deps: Deps, auction_id: u64, address: String) -> StdResult<EligibilityResponse> {
+    let auction = auctions().load(deps.storage, auction_id)?;
+    let address = deps.api.addr_validate(&address)?;
+
+    let total_bid = bids()
+        .may_load(deps.storage, (auction_id, address.clone()))?
+        .unwrap_or_default();
+
+    Ok(EligibilityResponse {
+        eligible: address != auction.seller && is_eligible(deps, auction_id, &auction, &address)?,
+        max_bid: auction.max_bid,
+        remaining: auction
+            .max_bid
+            .map(|max_bid| max_bid.saturating_sub(total_bid)),
+    })
+}
+
+// whether the address is allowed to bid, or buy, on the auction
+pub fn is_eligible(
+    deps: Deps,
+    auction_id: u64,
+    auction: &Auction,
+    address: &Addr,
+) -> StdResult<bool> {
+    match &auction.bidders {
+        None => Ok(true),
+        Some(Bidders::Allowlist) => {
+            Ok(ALLOWED_BIDDERS.has(deps.storage, (auction_id, address.clone())))
+        }
+        Some(Bidders::Group(group)) => Ok(group.is_member(&deps.querier, address, None)?.is_some()),
+    }
+}
+
+pub fn list_bidders(
+    deps: Deps,
+    auction_id: u64,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<ListBiddersResponse> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = maybe_addr(deps.api, start_after)?.map(Bound::exclusive);
+
+    let bidders = ALLOWED_BIDDERS
+        .prefix(auction_id)
+        .keys(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|bidder| bidder.map(String::from))
+        .collect::<StdResult<_>>()?;
+
+    Ok(ListBiddersResponse { bidders })
+}
+
+pub fn config(deps: Deps) -> StdResult<ConfigResponse> {
+    let cfg = CONFIG.load(deps.storage)?;
+    let contract_owner = contract_owner(deps)?;
+
+    Ok(ConfigResponse {
+        fee_collector: cfg.fee_collector(&contract_owner).to_string(),
+        contract_owner: contract_owner.to_string(),
+        pending_owner: PENDING_OWNER.may_load(deps.storage)?.map(String::from),
+        commission: cfg.commission,
+        sellers: cfg
+            .sellers
+            .map(|sellers| sellers.into_iter().map(String::from).collect()),
+    })
+}
+
+pub fn rate(deps: Deps, auction_id: u64, denom: String) -> StdResult<RateResponse> {
+    let auction = auctions().load(deps.storage, auction_id)?;
+
+    Ok(RateResponse {
+        rate: price(deps, &auction, &Denom::Native(denom))?,
+    })
+}
+
+// price of one unit of `denom` in the auction denom, from the auction oracle if it has one
+pub fn price(deps: Deps, auction: &Auction, denom: &Denom) -> StdResult<Decimal> {
+    if *denom == auction.denom {
+        return Ok(Decimal::one());
+    }
+
+    let (Some(accepted), Denom::Native(base), Denom::Native(quote)) =
+        (&auction.accepted_denoms, denom, &auction.denom)
+    else {
+        return Err(StdError::generic_err("Denom not accepted by the auction"));
+    };
+    if !accepted.denoms.contains(base) {
+        return Err(StdError::generic_err("Denom not accepted by the auction"));
+    }
+
+    match &accepted.oracle {
+        Some(oracle) => {
+            let res: PriceResponse = deps.querier.query(&QueryRequest::Wasm(WasmQuery::Smart {
+                contract_addr: oracle.to_string(),
+                msg: to_json_binary(&OracleQueryMsg::Price {
+                    base: base.clone(),
+                    quote: quote.clone(),
+                })?,
+            }))?;
+            Ok(res.rate)
+        }
+        None => RATES
+            .may_load(deps.storage, (base, quote))?
+            .ok_or_else(|| StdError::not_found(format!("rate of {base} in {quote}"))),
+    }
+}
+
+pub fn deposits(deps: Deps, auction_id: u64, address: String) -> StdResult<DepositsResponse> {
+    let address = deps.api.addr_validate(&address)?;
+
+    let deposits = DEPOSITS
+        .prefix((auction_id, address))
+        .range(deps.storage, None, None, Order::Ascending)
+        .map(|deposit| deposit.map(|(denom, amount)| Coin { denom, amount }))
+        .collect::<StdResult<_>>()?;
+
+    Ok(DepositsResponse { deposits })
+}
+
+// the owner is set at instantiation and can only be handed over, never unset
+pub fn contract_owner(deps: Deps) -> StdResult<Addr> {
+    OWNER
+        .get(deps)?
+        .ok_or_else(|| StdError::not_found("contract owner"))
+}
+
+pub fn highest_bid(deps: Deps, auction_id: u64) -> StdResult<HighestBidderResponse> {
     // if maps is small, might be cleaner to iterate through the bids and collect the highest.
-    let winner = WINNER.load(deps.storage)?;
+    let winner = WINNER.load(deps.storage, auction_id)?;
 
     Ok(HighestBidderResponse {
-        addr: winner.0.to_string(),
-        total_bid: winner.1.to_owned(),
+        addr: winner.bidder.to_string(),
+        total_bid: winner.amount,
     })
 }
 
-pub fn total_bid(deps: Deps, addr: String) -> StdResult<TotalBidResponse> {
+pub fn total_bid(deps: Deps, auction_id: u64, addr: String) -> StdResult<TotalBidResponse> {
     // if maps is small, might be cleaner to iterate through the bids and collect the highest.
-    let bid = BIDS.load(deps.storage, deps.api.addr_validate(&addr)?)?;
+    let bid = bids()
+        .may_load(deps.storage, (auction_id, deps.api.addr_validate(&addr)?))?
+        .unwrap_or_default();
 
     Ok(TotalBidResponse { total_bid: bid })
 }
 
-pub fn is_closed(deps: Deps) -> StdResult<IsBidClosedResponse> {
-    let cfg = CONFIG.load(deps.storage)?;
+pub fn is_closed(deps: Deps, env: Env, auction_id: u64) -> StdResult<IsBidClosedResponse> {
+    let auction = auctions().load(deps.storage, auction_id)?;
 
-    match cfg.status {
-        BidStatus::Closed => Ok(IsBidClosedResponse { is_closed: true }),
-        BidStatus::Opened => Ok(IsBidClosedResponse { is_closed: false }),
-    }
+    Ok(IsBidClosedResponse {
+        is_closed: auction.is_closed(&env.block),
+    })
 }
 
-pub fn bid_winner(deps: Deps) -> StdResult<BidWinnerResponse> {
-    let cfg = CONFIG.load(deps.storage)?;
+pub fn bid_winner(deps: Deps, env: Env, auction_id: u64) -> StdResult<BidWinnerResponse> {
+    let auction = auctions().load(deps.storage, auction_id)?;
 
-    // return error if bid is not closed
-    if cfg.status != BidStatus::Closed {
+    // return error if bid is not closed nor expired
+    if !auction.is_closed(&env.block) {
         return Err(StdError::generic_err("Bid is not closed"));
     }
 
-    let winner = WINNER.load(deps.storage)?;
+    // no sale if the auction was cancelled or the reserve price was not met
+    if auction.status == BidStatus::Cancelled {
+        return Ok(BidWinnerResponse { winner: None });
+    }
+    let winner = WINNER.load(deps.storage, auction_id)?;
+    let price = winning_price(deps, auction_id, &auction)?;
     Ok(BidWinnerResponse {
-        winner: winner.0.to_string(),
+        winner: auction
+            .is_sold(&winner.bidder, price)
+            .then(|| winner.bidder.to_string()),
+    })
+}
+
+// what the winner pays, which is the second highest bid on second-price sealed auctions, but no
+// less than the reserve price and the minimum bid over the seller's, when the seller's is that bid
+pub fn winning_price(deps: Deps, auction_id: u64, auction: &Auction) -> StdResult<Uint128> {
+    let winner = WINNER.load(deps.storage, auction_id)?;
+    match auction.sealed.as_ref().map(|sealed| &sealed.pricing) {
+        Some(Pricing::SecondPrice) => {
+            let runner_up = RUNNER_UP
+                .may_load(deps.storage, auction_id)?
+                .unwrap_or(winner.amount);
+            let opening_bid = bids()
+                .may_load(deps.storage, (auction_id, auction.seller.clone()))?
+                .unwrap_or_default();
+            let floor = auction
+                .min_bid(opening_bid)
+                .max(auction.reserve_price.unwrap_or_default());
+            Ok(runner_up.max(floor).min(winner.amount))
+        }
+        _ => Ok(winner.amount),
+    }
+}
+
+pub fn current_price(deps: Deps, env: Env, auction_id: u64) -> StdResult<CurrentPriceResponse> {
+    let auction = auctions().load(deps.storage, auction_id)?;
+
+    let dutch = auction
+        .dutch
+        .ok_or_else(|| StdError::generic_err("Not a dutch auction"))?;
+
+    Ok(CurrentPriceResponse {
+        price: dutch.price(&auction.expiration, &env.block),
+    })
+}
+
+pub fn outstanding_refunds(
+    deps: Deps,
+    auction_id: u64,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<OutstandingRefundsResponse> {
+    let auction = auctions().load(deps.storage, auction_id)?;
+    let winner = WINNER.load(deps.storage, auction_id)?;
+
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after
+        .map(|addr| deps.api.addr_validate(&addr))
+        .transpose()?
+        .map(Bound::exclusive);
+
+    // bidders holding a bid, or an unrevealed deposit they get back, by address
+    let mut bidders = bids()
+        .prefix(auction_id)
+        .keys(deps.storage, start.clone(), None, Order::Ascending)
+        .take(limit)
+        .collect::<StdResult<Vec<_>>>()?;
+    if auction.refunds_unrevealed() {
+        let unrevealed = COMMITMENTS
+            .prefix(auction_id)
+            .keys(deps.storage, start, None, Order::Ascending)
+            .take(limit)
+            .collect::<StdResult<Vec<_>>>()?;
+        bidders.extend(unrevealed);
+        bidders.sort();
+        bidders.dedup();
+        bidders.truncate(limit);
+    }
+
+    let denom = execute::denom_to_string(&auction.denom);
+    let mut refunds = vec![];
+    for bidder in bidders {
+        // once settled, the winning bid was paid to the seller, but unused proxy escrow is not
+        let mut amount = if auction.is_settled() && auction.is_winning_bid(&winner, &bidder) {
+            Uint128::zero()
+        } else {
+            bids()
+                .may_load(deps.storage, (auction_id, bidder.clone()))?
+                .unwrap_or_default()
+        };
+
+        // bids in several denoms are held in the coins they were made with
+        if auction.accepted_denoms.is_some() && !amount.is_zero() {
+            let deposits = DEPOSITS
+                .prefix((auction_id, bidder.clone()))
+                .range(deps.storage, None, None, Order::Ascending)
+                .collect::<StdResult<Vec<_>>>()?;
+            for (denom, amount) in deposits {
+                refunds.push(Refund {
+                    bidder: bidder.to_string(),
+                    amount,
+                    denom,
+                });
+            }
+            amount = Uint128::zero();
+        }
+
+        amount += proxy_bids()
+            .may_load(deps.storage, (auction_id, bidder.clone()))?
+            .map_or(Uint128::zero(), |proxy| proxy.reserve);
+        if auction.refunds_unrevealed() {
+            amount += COMMITMENTS
+                .may_load(deps.storage, (auction_id, bidder.clone()))?
+                .map_or(Uint128::zero(), |commitment| commitment.deposit);
+        }
+        if !amount.is_zero() {
+            refunds.push(Refund {
+                bidder: bidder.to_string(),
+                amount,
+                denom: denom.clone(),
+            });
+        }
+    }
+
+    Ok(OutstandingRefundsResponse { refunds })
+}
+
+pub fn list_bids(
+    deps: Deps,
+    auction_id: u64,
+    start_after: Option<String>,
+    limit: Option<u32>,
+    order: Option<OrderBy>,
+) -> StdResult<ListBidsResponse> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let order = order.map_or(Order::Descending, Order::from);
+
+    // bids are ordered by amount, so the page starts after the amount of the given bidder
+    let start = maybe_addr(deps.api, start_after)?
+        .map(|bidder| -> StdResult<_> {
+            let amount = bids().load(deps.storage, (auction_id, bidder.clone()))?;
+            Ok(Bound::exclusive((amount.u128(), (auction_id, bidder))))
+        })
+        .transpose()?;
+    let (min, max) = match order {
+        Order::Ascending => (start, None),
+        Order::Descending => (None, start),
+    };
+
+    let bids = bids()
+        .idx
+        .amount
+        .sub_prefix(auction_id)
+        .range(deps.storage, min, max, order)
+        .take(limit)
+        .map(|item| {
+            let ((_, bidder), total_bid) = item?;
+            Ok(BidInfo {
+                bidder: bidder.to_string(),
+                total_bid,
+            })
+        })
+        .collect::<StdResult<_>>()?;
+
+    Ok(ListBidsResponse { bids })
+}
+
+pub fn bid_history(
+    deps: Deps,
+    auction_id: u64,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<BidHistoryResponse> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.map(Bound::exclusive);
+
+    let bids = BID_HISTORY
+        .prefix(auction_id)
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|item| {
+            let (id, bid) = item?;
+            Ok(BidHistoryEntry {
+                id,
+                bidder: bid.bidder.to_string(),
+                gross: bid.gross,
+                commission: bid.commission,
+                net: bid.net,
+                height: bid.height,
+                time: bid.time,
+                proxy: bid.proxy,
+            })
+        })
+        .collect::<StdResult<_>>()?;
+
+    Ok(BidHistoryResponse { bids })
+}
+
+pub fn commitment(deps: Deps, auction_id: u64, addr: String) -> StdResult<CommitmentResponse> {
+    let commitment =
+        COMMITMENTS.may_load(deps.storage, (auction_id, deps.api.addr_validate(&addr)?))?;
+
+    Ok(CommitmentResponse { commitment })
+}
+
+pub fn expiration(deps: Deps, env: Env, auction_id: u64) -> StdResult<ExpirationResponse> {
+    let auction = auctions().load(deps.storage, auction_id)?;
+
+    Ok(ExpirationResponse {
+        is_expired: auction.expiration.is_expired(&env.block),
+        expiration: auction.expiration,
+        soft_close: auction.soft_close,
+    })
+}
+
+pub fn auction(deps: Deps, env: Env, auction_id: u64) -> StdResult<AuctionResponse> {
+    let auction = auctions().load(deps.storage, auction_id)?;
+    to_auction_response(deps, &env, auction_id, auction)
+}
+
+pub fn list_auctions(
+    deps: Deps,
+    env: Env,
+    status: Option<BidStatus>,
+    seller: Option<String>,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<ListAuctionsResponse> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.map(Bound::exclusive);
+
+    let auctions_iter = match seller {
+        Some(seller) => auctions()
+            .idx
+            .seller
+            .prefix(deps.api.addr_validate(&seller)?)
+            .range(deps.storage, start, None, Order::Ascending),
+        None => auctions().range(deps.storage, start, None, Order::Ascending),
+    };
+
+    let auctions = auctions_iter
+        .filter(|item| match (item, &status) {
+            (Ok((_, auction)), Some(status)) => auction.phase(&env.block) == *status,
+            _ => true,
+        })
+        .take(limit)
+        .map(|item| {
+            let (auction_id, auction) = item?;
+            to_auction_response(deps, &env, auction_id, auction)
+        })
+        .collect::<StdResult<_>>()?;
+
+    Ok(ListAuctionsResponse { auctions })
+}
+
+fn to_auction_response(
+    deps: Deps,
+    env: &Env,
+    auction_id: u64,
+    auction: Auction,
+) -> StdResult<AuctionResponse> {
+    let winner = WINNER.load(deps.storage, auction_id)?;
+
+    Ok(AuctionResponse {
+        auction_id,
+        status: auction.phase(&env.block),
+        commodity: auction.commodity,
+        seller: auction.seller.to_string(),
+        denom: auction.denom,
+        expiration: auction.expiration,
+        highest_bidder: winner.bidder.to_string(),
+        highest_bid: winner.amount,
+        item: auction.item,
+        sealed: auction.sealed,
+        dutch: auction.dutch,
+        buy_now: auction.buy_now,
+        bidders: auction.bidders,
+        max_bid: auction.max_bid,
+        accepted_denoms: auction.accepted_denoms,
     })
 }
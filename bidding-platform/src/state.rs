@@ -1,22 +1,441 @@
 use cosmwasm_schema::cw_serde;
-use cosmwasm_std::{Addr, Uint128};
-use cw_storage_plus::{Item, Map};
+use cosmwasm_std::{Addr, Binary, BlockInfo, Decimal, Empty, Timestamp, Uint128};
+use cw20::Denom;
+use cw4::Cw4Contract;
+use cw_controllers::Admin;
+use cw_storage_plus::{Index, IndexList, IndexedMap, Item, KeyDeserialize, Map, MultiIndex};
+use cw_utils::{Duration, Expiration};
 
+/// House wide settings shared by every auction.
 #[cw_serde]
 pub struct Config {
+    pub commission: Commission,
+    /// Commissions are paid here instead of to the contract owner when set.
+    pub fee_collector: Option<Addr>,
+    /// Addresses allowed to create auctions besides the contract owner, anyone when unset.
+    pub sellers: Option<Vec<Addr>>,
+}
+
+impl Config {
+    pub fn fee_collector<'a>(&'a self, contract_owner: &'a Addr) -> &'a Addr {
+        self.fee_collector.as_ref().unwrap_or(contract_owner)
+    }
+
+    pub fn can_sell(&self, contract_owner: &Addr, addr: &Addr) -> bool {
+        addr == contract_owner
+            || self
+                .sellers
+                .as_ref()
+                .map_or(true, |sellers| sellers.contains(addr))
+    }
+}
+
+#[cw_serde]
+pub struct Commission {
+    pub rate: Decimal,
+    /// Rates replacing the base rate from a given bid size on, sorted by increasing bid size.
+    #[serde(default)]
+    pub tiers: Vec<CommissionTier>,
+    #[serde(default)]
+    pub mode: CommissionMode,
+}
+
+impl Commission {
+    /// Rate of the biggest tier the amount reaches, the base rate below every tier.
+    pub fn rate(&self, amount: Uint128) -> Decimal {
+        self.tiers
+            .iter()
+            .rev()
+            .find(|tier| amount >= tier.min_bid)
+            .map_or(self.rate, |tier| tier.rate)
+    }
+
+    pub fn of(&self, amount: Uint128) -> Uint128 {
+        amount.mul_floor(self.rate(amount))
+    }
+}
+
+#[cw_serde]
+pub struct CommissionTier {
+    pub min_bid: Uint128,
+    pub rate: Decimal,
+}
+
+#[cw_serde]
+#[derive(Default)]
+pub enum CommissionMode {
+    /// Every bid pays its commission as it is placed, even if it is refunded later.
+    #[default]
+    EveryBid,
+    /// Only the winning bid pays commission, out of what the seller gets at close.
+    WinningBid,
+}
+
+#[cw_serde]
+pub struct Auction {
     pub commodity: String,
-    pub contract_owner: Addr,
-    pub commission: u128,
-    pub denom: String,
+    pub seller: Addr,
+    pub denom: Denom,
     pub status: BidStatus,
+    pub expiration: Expiration,
+    pub soft_close: Option<SoftClose>,
+    pub reserve_price: Option<Uint128>,
+    pub min_increment: Option<MinIncrement>,
+    /// Escrowed item released to the winner when the auction is settled.
+    pub item: Option<AuctionItem>,
+    /// Bids are committed until the expiration and revealed afterwards when set.
+    pub sealed: Option<SealedBid>,
+    /// The first buyer paying the current, decreasing, price wins when set.
+    pub dutch: Option<DutchAuction>,
+    /// Anyone can buy the item right away at a fixed price when set, until bids get close to it.
+    pub buy_now: Option<BuyNow>,
+    /// Only these bidders can bid or buy when set, anyone when unset.
+    pub bidders: Option<Bidders>,
+    /// Highest total bid a single bidder can reach when set.
+    pub max_bid: Option<Uint128>,
+    /// Bids can also be made in these native denoms when set, valued in the auction denom.
+    pub accepted_denoms: Option<AcceptedDenoms>,
+}
+
+impl Auction {
+    /// Whether bids can be made in the given native denom.
+    pub fn accepts(&self, denom: &str) -> bool {
+        self.denom == Denom::Native(denom.to_string())
+            || self
+                .accepted_denoms
+                .as_ref()
+                .is_some_and(|accepted| accepted.denoms.iter().any(|d| d == denom))
+    }
+
+    /// Phase the auction is effectively in, even if nobody finalized it yet: open auctions close
+    /// at their expiration, sealed ones reveal their bids after it and close at the reveal deadline.
+    pub fn phase(&self, block: &BlockInfo) -> BidStatus {
+        match &self.sealed {
+            _ if self.is_settled() => self.status.clone(),
+            None if self.expiration.is_expired(block) => BidStatus::Closed,
+            None => BidStatus::Opened,
+            Some(sealed) if sealed.reveal_deadline.is_expired(block) => BidStatus::Closed,
+            Some(_) if self.expiration.is_expired(block) => BidStatus::Reveal,
+            Some(_) => BidStatus::Commit,
+        }
+    }
+
+    /// An auction is effectively closed once the seller closed it or its last phase is over.
+    pub fn is_closed(&self, block: &BlockInfo) -> bool {
+        matches!(self.phase(block), BidStatus::Closed | BidStatus::Cancelled)
+    }
+
+    /// Settled auctions were closed or cancelled for good, nothing can change them anymore.
+    pub fn is_settled(&self) -> bool {
+        matches!(self.status, BidStatus::Closed | BidStatus::Cancelled)
+    }
+
+    /// The commodity is only sold if the winning bid reached the reserve price.
+    pub fn reserve_met(&self, winning_bid: Uint128) -> bool {
+        self.reserve_price
+            .map_or(true, |reserve_price| winning_bid >= reserve_price)
+    }
+
+    /// The commodity is sold if the winning bid reached the reserve price and, on sealed auctions,
+    /// a revealed bid beat the seller's, since sealed bids are charged out of their deposit.
+    pub fn is_sold(&self, bidder: &Addr, price: Uint128) -> bool {
+        self.reserve_met(price)
+            && (self.sealed.is_none() || (bidder != self.seller && !price.is_zero()))
+    }
+
+    /// On open auctions the winning bid is paid to the seller, unless the reserve price was not
+    /// met or the auction was cancelled.
+    pub fn is_winning_bid(&self, winner: &Winner, bidder: &Addr) -> bool {
+        self.sealed.is_none()
+            && self.status != BidStatus::Cancelled
+            && winner.bidder == *bidder
+            && self.reserve_met(winner.amount)
+    }
+
+    /// Unrevealed deposits are refunded as well unless they are forfeited, which they never are
+    /// once the auction was cancelled.
+    pub fn refunds_unrevealed(&self) -> bool {
+        match &self.sealed {
+            Some(_) if self.status == BidStatus::Cancelled => true,
+            Some(sealed) => sealed.forfeit == Forfeit::Bidder,
+            None => false,
+        }
+    }
+
+    /// Lowest total bid that outbids the current highest bid.
+    pub fn min_bid(&self, highest_bid: Uint128) -> Uint128 {
+        let increment = match self.min_increment {
+            Some(MinIncrement::Absolute(amount)) => amount,
+            Some(MinIncrement::Percentage(rate)) => highest_bid.mul_ceil(rate),
+            None => Uint128::one(),
+        };
+        // bids always have to be strictly higher than the highest bid, even when the increment
+        // rounds down to nothing, as percentages of an empty opening bid do
+        highest_bid + increment.max(Uint128::one())
+    }
+}
+
+#[cw_serde]
+pub enum AuctionItem {
+    Cw721 {
+        contract: Addr,
+        token_id: String,
+    },
+    Cw1155 {
+        contract: Addr,
+        token_id: String,
+        amount: Uint128,
+    },
+}
+
+#[cw_serde]
+pub enum MinIncrement {
+    Absolute(Uint128),
+    Percentage(Decimal),
+}
+
+/// Anti-sniping rule: bids landing within `window` of the expiration push it out by `extension`,
+/// but never past the `hard_deadline` if one is set.
+#[cw_serde]
+pub struct SoftClose {
+    pub window: Duration,
+    pub extension: Duration,
+    pub hard_deadline: Option<Expiration>,
+}
+
+#[cw_serde]
+pub enum Bidders {
+    /// Addresses added by the seller or the contract owner, kept in `ALLOWED_BIDDERS`.
+    Allowlist,
+    /// Members of a cw4 group, whatever their weight.
+    Group(Cw4Contract),
+}
+
+#[cw_serde]
+pub struct BuyNow {
+    pub price: Uint128,
+    /// Buying now is disabled once the highest bid exceeds this fraction of the price.
+    pub cutoff: Decimal,
+}
+
+impl BuyNow {
+    /// Whether the item can still be bought now given the current highest bid.
+    pub fn available(&self, highest_bid: Uint128) -> bool {
+        highest_bid <= self.price.mul_floor(self.cutoff)
+    }
+}
+
+#[cw_serde]
+pub struct AcceptedDenoms {
+    pub denoms: Vec<String>,
+    /// Prices are queried from this oracle when set, and set by the contract owner otherwise.
+    pub oracle: Option<Addr>,
+}
+
+/// Descending price, dropping from `start_price` towards `floor_price` in the unit of the
+/// auction duration, counted from `start`.
+#[cw_serde]
+pub struct DutchAuction {
+    pub start_price: Uint128,
+    pub floor_price: Uint128,
+    pub decay: Decay,
+    pub start: Expiration,
+}
+
+impl DutchAuction {
+    /// Price a buyer has to pay at the given block, until the auction ends at `end`.
+    pub fn price(&self, end: &Expiration, block: &BlockInfo) -> Uint128 {
+        let (elapsed, total) = match (self.start, end) {
+            (Expiration::AtHeight(start), Expiration::AtHeight(end)) => {
+                (block.height.saturating_sub(start), end - start)
+            }
+            (Expiration::AtTime(start), Expiration::AtTime(end)) => (
+                block.time.seconds().saturating_sub(start.seconds()),
+                end.seconds() - start.seconds(),
+            ),
+            // dutch auctions always expire in the unit they started in
+            _ => return self.floor_price,
+        };
+        let drop = match self.decay {
+            Decay::Linear => {
+                (self.start_price - self.floor_price).multiply_ratio(elapsed.min(total), total)
+            }
+            Decay::Step { interval, amount } => {
+                amount.saturating_mul(Uint128::from(elapsed / interval))
+            }
+        };
+        self.start_price.saturating_sub(drop).max(self.floor_price)
+    }
+}
+
+#[cw_serde]
+pub enum Decay {
+    /// Drops continuously to reach the floor price when the auction expires.
+    Linear,
+    /// Drops by `amount` every `interval` blocks or seconds.
+    Step { interval: u64, amount: Uint128 },
+}
+
+/// Commit-reveal rules: bidders commit a hash of their bid along with a deposit covering it and
+/// reveal the bid once the auction expires.
+#[cw_serde]
+pub struct SealedBid {
+    pub reveal_deadline: Expiration,
+    pub pricing: Pricing,
+    pub forfeit: Forfeit,
+}
+
+#[cw_serde]
+pub enum Pricing {
+    /// The winner pays their own bid.
+    FirstPrice,
+    /// The winner pays the second highest revealed bid (Vickrey auction).
+    SecondPrice,
+}
+
+/// Who gets the deposits of commitments that were never revealed, or revealed invalid bids.
+#[cw_serde]
+pub enum Forfeit {
+    Bidder,
+    Seller,
+    ContractOwner,
+}
+
+/// Highest bidder of an auction and their total bid, the seller until someone outbids them.
+#[cw_serde]
+pub struct Winner {
+    pub bidder: Addr,
+    pub amount: Uint128,
+}
+
+/// Hash of a sealed bid and the deposit sent along, until the bid is revealed.
+#[cw_serde]
+pub struct Commitment {
+    pub hash: Binary,
+    pub deposit: Uint128,
 }
 
 #[cw_serde]
 pub enum BidStatus {
     Opened,
+    Commit,
+    Reveal,
     Closed,
+    /// Cancelled by the contract owner, every bid is refundable and nothing is sold.
+    Cancelled,
+}
+
+/// Every `Bid {}` placed, and every raise made on behalf of a bidder by their proxy, in the
+/// order they were placed.
+#[cw_serde]
+pub struct BidRecord {
+    pub bidder: Addr,
+    pub gross: Uint128,
+    pub commission: Uint128,
+    pub net: Uint128,
+    pub height: u64,
+    pub time: Timestamp,
+    #[serde(default)]
+    pub proxy: bool,
+}
+
+/// Escrow a bidder set aside for the contract to raise their bid with, up to `max`.
+#[cw_serde]
+pub struct ProxyBid {
+    pub max: Uint128,
+    /// Escrowed on top of the bid and not used yet, commission already paid.
+    pub reserve: Uint128,
+}
+
+pub struct AuctionIndexes<'a> {
+    pub seller: MultiIndex<'a, Addr, Auction, u64>,
+}
+
+impl<'a> IndexList<Auction> for AuctionIndexes<'a> {
+    fn get_indexes(&'_ self) -> Box<dyn Iterator<Item = &'_ dyn Index<Auction>> + '_> {
+        let v: Vec<&dyn Index<Auction>> = vec![&self.seller];
+        Box::new(v.into_iter())
+    }
+}
+
+pub fn auctions<'a>() -> IndexedMap<'a, u64, Auction, AuctionIndexes<'a>> {
+    let indexes = AuctionIndexes {
+        seller: MultiIndex::new(
+            |_pk, auction| auction.seller.clone(),
+            "auctions",
+            "auctions__seller",
+        ),
+    };
+    IndexedMap::new("auctions", indexes)
+}
+
+pub struct BidIndexes<'a> {
+    pub amount: MultiIndex<'a, (u64, u128), Uint128, (u64, Addr)>,
+}
+
+impl<'a> IndexList<Uint128> for BidIndexes<'a> {
+    fn get_indexes(&'_ self) -> Box<dyn Iterator<Item = &'_ dyn Index<Uint128>> + '_> {
+        let v: Vec<&dyn Index<Uint128>> = vec![&self.amount];
+        Box::new(v.into_iter())
+    }
+}
+
+/// Total bid of every bidder by auction, indexed by amount for leaderboards.
+pub fn bids<'a>() -> IndexedMap<'a, (u64, Addr), Uint128, BidIndexes<'a>> {
+    let indexes = BidIndexes {
+        amount: MultiIndex::new(
+            |pk, amount| {
+                let (auction_id, _) =
+                    <(u64, Addr)>::from_slice(pk).expect("bids are keyed by auction and bidder");
+                (auction_id, amount.u128())
+            },
+            "bids",
+            "bids__amount",
+        ),
+    };
+    IndexedMap::new("bids", indexes)
+}
+
+pub struct ProxyBidIndexes<'a> {
+    pub max: MultiIndex<'a, (u64, u128), ProxyBid, (u64, Addr)>,
+}
+
+impl<'a> IndexList<ProxyBid> for ProxyBidIndexes<'a> {
+    fn get_indexes(&'_ self) -> Box<dyn Iterator<Item = &'_ dyn Index<ProxyBid>> + '_> {
+        let v: Vec<&dyn Index<ProxyBid>> = vec![&self.max];
+        Box::new(v.into_iter())
+    }
+}
+
+/// Proxy bids by auction, indexed by max bid so only the highest ones challenge the highest bid.
+pub fn proxy_bids<'a>() -> IndexedMap<'a, (u64, Addr), ProxyBid, ProxyBidIndexes<'a>> {
+    let indexes = ProxyBidIndexes {
+        max: MultiIndex::new(
+            |pk, proxy| {
+                let (auction_id, _) = <(u64, Addr)>::from_slice(pk)
+                    .expect("proxy bids are keyed by auction and bidder");
+                (auction_id, proxy.max.u128())
+            },
+            "proxy_bids",
+            "proxy_bids__max",
+        ),
+    };
+    IndexedMap::new("proxy_bids", indexes)
 }
 
+pub const OWNER: Admin = Admin::new("owner");
+/// Proposed owner, who becomes the owner once they accept.
+pub const PENDING_OWNER: Item<Addr> = Item::new("pending_owner");
 pub const CONFIG: Item<Config> = Item::new("config");
-pub const BIDS: Map<Addr, Uint128> = Map::new("bids");
-pub const WINNER: Item<(Addr, Uint128)> = Item::new("winner");
+pub const AUCTION_COUNT: Item<u64> = Item::new("auction_count");
+pub const BID_COUNT: Map<u64, u64> = Map::new("bid_count");
+pub const BID_HISTORY: Map<(u64, u64), BidRecord> = Map::new("bid_history");
+pub const WINNER: Map<u64, Winner> = Map::new("winner");
+pub const RUNNER_UP: Map<u64, Uint128> = Map::new("runner_up");
+pub const ALLOWED_BIDDERS: Map<(u64, Addr), Empty> = Map::new("allowed_bidders");
+pub const COMMITMENTS: Map<(u64, Addr), Commitment> = Map::new("commitments");
+pub const UNREVEALED: Map<u64, Uint128> = Map::new("unrevealed");
+/// Price of one unit of a denom in a reference denom, set by the contract owner.
+pub const RATES: Map<(&str, &str), Decimal> = Map::new("rates");
+/// Coins deposited by every bidder, net of commission, on auctions taking several denoms.
+pub const DEPOSITS: Map<(u64, Addr, String), Uint128> = Map::new("deposits");
@@ -2,17 +2,28 @@
 mod tests {
     use crate::{
         contract::{execute, instantiate, query},
+        helpers::bid_commitment,
         msg::{
-            ExecuteMsg, HighestBidderResponse, InstantiateMsg, IsBidClosedResponse, QueryMsg,
-            TotalBidResponse,
+            AuctionMsg, AuctionResponse, BidHistoryResponse, BidWinnerResponse, ConfigResponse,
+            CurrentPriceResponse, Cw721ExecuteMsg, Cw721ReceiveMsg, DepositMsg, DepositsResponse,
+            DutchAuctionMsg, EligibilityResponse, ExecuteMsg, ExpirationResponse,
+            HighestBidderResponse, InstantiateMsg, IsBidClosedResponse, ListAuctionsResponse,
+            ListBiddersResponse, ListBidsResponse, OrderBy, OutstandingRefundsResponse, QueryMsg,
+            RateResponse, SealedBidMsg, TotalBidResponse,
+        },
+        state::{
+            proxy_bids, AuctionItem, BidStatus, Commission, CommissionMode, CommissionTier, Decay,
+            Forfeit, MinIncrement, Pricing, SoftClose,
         },
         ContractError,
     };
     use cosmwasm_std::{
-        attr, coin, from_binary,
+        attr, coin, from_json,
         testing::{mock_dependencies, mock_env, mock_info},
-        BankMsg, CosmosMsg, Uint128,
+        to_json_binary, Addr, BankMsg, CosmosMsg, Decimal, Event, Uint128, WasmMsg,
     };
+    use cw_controllers::AdminError;
+    use cw_utils::{Duration, Expiration};
 
     // three fake addresses we will use to mock_info
     pub const ADDR1: &str = "addr1";
@@ -23,8 +34,8 @@ mod tests {
     pub const COMMODITY: &str = "gold";
     pub const DENOM: &str = "uatom";
 
-    // commision is 0,1% or 0.001 with 6 decimals
-    const COMMISSION_RATE: u128 = 1000u128;
+    // commision is 0,1% or 0.001
+    const COMMISSION_RATE: Decimal = Decimal::permille(1);
 
     #[test]
     fn test_instantiate() {
@@ -34,18 +45,21 @@ mod tests {
         let msg = InstantiateMsg {
             commodity: COMMODITY.to_string(),
             contract_owner: None,
-            commision: None,
+            commission: None,
+            ..InstantiateMsg::default()
         };
         let res = instantiate(deps.as_mut(), env, info, msg).unwrap();
-        assert_eq!(res.attributes.len(), 5);
+        assert_eq!(res.attributes.len(), 7);
         assert_eq!(
             res.attributes,
             vec![
                 attr("action", "instantiate"),
+                attr("auction_id", "1"),
                 attr("commodity", COMMODITY),
                 attr("contract_owner", ADDR1),
                 attr("commission", COMMISSION_RATE.to_string()),
-                attr("denom", DENOM)
+                attr("denom", DENOM),
+                attr("expiration", "expiration: never")
             ]
         );
     }
@@ -58,11 +72,12 @@ mod tests {
         let msg = InstantiateMsg {
             commodity: "gold".to_string(),
             contract_owner: None,
-            commision: None,
+            commission: None,
+            ..InstantiateMsg::default()
         };
         instantiate(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
 
-        let execute_msg = ExecuteMsg::Bid {};
+        let execute_msg = ExecuteMsg::Bid { auction_id: 1 };
 
         // bids with less amount than highest bid should fail
         let execute_info = mock_info(ADDR2, &[coin(1_000, DENOM)]);
@@ -96,15 +111,16 @@ mod tests {
             res.unwrap().attributes,
             vec![
                 attr("action", "bid"),
+                attr("auction_id", "1"),
                 attr("highest_bidder", ADDR2),
                 attr("highest_bid", "9990000")
             ]
         );
 
         // query highest bidder should return new bidder addr2 & 9990000 (10_000_000 - 10_000)
-        let query_msg = QueryMsg::HighestBidder {};
+        let query_msg = QueryMsg::HighestBidder { auction_id: 1 };
         let res: HighestBidderResponse =
-            from_binary(&query(deps.as_ref(), env.clone(), query_msg).unwrap()).unwrap();
+            from_json(query(deps.as_ref(), env.clone(), query_msg).unwrap()).unwrap();
         assert_eq!(res.total_bid, Uint128::from(9990000u128));
         assert_eq!(res.addr, ADDR2);
 
@@ -130,15 +146,16 @@ mod tests {
             res.unwrap().attributes,
             vec![
                 attr("action", "bid"),
+                attr("auction_id", "1"),
                 attr("highest_bidder", ADDR3),
                 attr("highest_bid", "99900000")
             ]
         );
 
         // query highest bidder should return new bidder addr3 & 500
-        let query_msg = QueryMsg::HighestBidder {};
+        let query_msg = QueryMsg::HighestBidder { auction_id: 1 };
         let res: HighestBidderResponse =
-            from_binary(&query(deps.as_ref(), env.clone(), query_msg).unwrap()).unwrap();
+            from_json(query(deps.as_ref(), env.clone(), query_msg).unwrap()).unwrap();
         assert_eq!(res.total_bid, Uint128::from(99900000u32));
         assert_eq!(res.addr, ADDR3);
     }
@@ -151,11 +168,12 @@ mod tests {
         let msg = InstantiateMsg {
             commodity: "gold".to_string(),
             contract_owner: None,
-            commision: None,
+            commission: None,
+            ..InstantiateMsg::default()
         };
         instantiate(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
 
-        let execute_msg = ExecuteMsg::Bid {};
+        let execute_msg = ExecuteMsg::Bid { auction_id: 1 };
 
         let execute_info = mock_info(ADDR2, &[coin(10_000_000, DENOM)]);
 
@@ -169,14 +187,14 @@ mod tests {
         .unwrap();
 
         // query highest bidder should return new bidder addr2 & 9990000 (10_000_000 - 10_000)
-        let query_msg = QueryMsg::HighestBidder {};
+        let query_msg = QueryMsg::HighestBidder { auction_id: 1 };
         let res: HighestBidderResponse =
-            from_binary(&query(deps.as_ref(), env.clone(), query_msg).unwrap()).unwrap();
+            from_json(query(deps.as_ref(), env.clone(), query_msg).unwrap()).unwrap();
         assert_eq!(res.total_bid, Uint128::from(9990000u128));
         assert_eq!(res.addr, ADDR2);
 
         // closing a bid as a non-contract owner should fail
-        let close_bid_msg = ExecuteMsg::Close {};
+        let close_bid_msg = ExecuteMsg::Close { auction_id: 1 };
         let res = execute(
             deps.as_mut(),
             env.clone(),
@@ -189,9 +207,9 @@ mod tests {
         let execute_info = mock_info(ADDR1, &[]);
 
         // query if bid is closed should return false
-        let query_msg = QueryMsg::IsBidClosed {};
+        let query_msg = QueryMsg::IsBidClosed { auction_id: 1 };
         let res: IsBidClosedResponse =
-            from_binary(&query(deps.as_ref(), env.clone(), query_msg).unwrap()).unwrap();
+            from_json(query(deps.as_ref(), env.clone(), query_msg).unwrap()).unwrap();
         assert!(!res.is_closed);
 
         // unwrap guarantees success
@@ -209,31 +227,33 @@ mod tests {
 
         // query total bid for ADDR1 after closing should be initial deposit: 1_000_000
         let query_msg = QueryMsg::TotalBid {
+            auction_id: 1,
             address: ADDR1.to_string(),
         };
         let res: TotalBidResponse =
-            from_binary(&query(deps.as_ref(), env.clone(), query_msg.clone()).unwrap()).unwrap();
+            from_json(query(deps.as_ref(), env.clone(), query_msg.clone()).unwrap()).unwrap();
         assert_eq!(res.total_bid, Uint128::from(1_000_000u128));
 
         // query total bid for ADDR2 (bid winner) should return 9990000
         let query_msg = QueryMsg::TotalBid {
+            auction_id: 1,
             address: ADDR2.to_string(),
         };
         let res: TotalBidResponse =
-            from_binary(&query(deps.as_ref(), env.clone(), query_msg).unwrap()).unwrap();
+            from_json(query(deps.as_ref(), env.clone(), query_msg).unwrap()).unwrap();
         assert_eq!(res.total_bid, Uint128::from(9990000u128));
 
         // query highest bidder should still return ADDR2 & 9990000 (10_000_000 - 10_000)
-        let query_msg = QueryMsg::HighestBidder {};
+        let query_msg = QueryMsg::HighestBidder { auction_id: 1 };
         let res: HighestBidderResponse =
-            from_binary(&query(deps.as_ref(), env.clone(), query_msg).unwrap()).unwrap();
+            from_json(query(deps.as_ref(), env.clone(), query_msg).unwrap()).unwrap();
         assert_eq!(res.total_bid, Uint128::from(9990000u128));
         assert_eq!(res.addr, ADDR2);
 
         // query if bid is closed should return true
-        let query_msg = QueryMsg::IsBidClosed {};
+        let query_msg = QueryMsg::IsBidClosed { auction_id: 1 };
         let res: IsBidClosedResponse =
-            from_binary(&query(deps.as_ref(), env.clone(), query_msg).unwrap()).unwrap();
+            from_json(query(deps.as_ref(), env.clone(), query_msg).unwrap()).unwrap();
         assert!(res.is_closed);
     }
 
@@ -245,12 +265,13 @@ mod tests {
         let msg = InstantiateMsg {
             commodity: "gold".to_string(),
             contract_owner: None,
-            commision: None,
+            commission: None,
+            ..InstantiateMsg::default()
         };
         instantiate(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
 
         // win bid as ADDR2
-        let execute_msg = ExecuteMsg::Bid {};
+        let execute_msg = ExecuteMsg::Bid { auction_id: 1 };
         let execute_info = mock_info(ADDR2, &[coin(10_000_000, DENOM)]);
 
         // bid with highest amount and unwrap() guarantees success
@@ -287,7 +308,7 @@ mod tests {
         .unwrap();
 
         // closing a bid as the contract owner
-        let close_bid_msg = ExecuteMsg::Close {};
+        let close_bid_msg = ExecuteMsg::Close { auction_id: 1 };
         let execute_info = mock_info(ADDR1, &[]);
 
         let res = execute(
@@ -301,12 +322,15 @@ mod tests {
         // create a bank message for owner
         let bank_msg = CosmosMsg::Bank(BankMsg::Send {
             to_address: ADDR1.to_string(),
-            amount: vec![coin(999_000_000u128.into(), DENOM)],
+            amount: vec![coin(999_000_000u128, DENOM)],
         });
         assert_eq!(res.messages[0].msg, bank_msg);
 
         // retracting bid for ADDR2 without passing receiver should work
-        let retract_bid_msg = ExecuteMsg::Retract { receiver: None };
+        let retract_bid_msg = ExecuteMsg::Retract {
+            auction_id: 1,
+            receiver: None,
+        };
         let execute_info = mock_info(ADDR2, &[]);
 
         let res = execute(
@@ -320,7 +344,7 @@ mod tests {
         // create a bank message for ADDR2
         let bank_msg = CosmosMsg::Bank(BankMsg::Send {
             to_address: ADDR2.to_string(),
-            amount: vec![coin(9_990_000u128.into(), DENOM)],
+            amount: vec![coin(9_990_000u128, DENOM)],
         });
         assert_eq!(res.messages[0].msg, bank_msg);
 
@@ -350,12 +374,13 @@ mod tests {
         // create a bank message for owner
         let bank_msg = CosmosMsg::Bank(BankMsg::Send {
             to_address: ADDR1.to_string(),
-            amount: vec![coin(1_000_000u128.into(), DENOM)],
+            amount: vec![coin(1_000_000u128, DENOM)],
         });
         assert_eq!(res.messages[0].msg, bank_msg);
 
         // retracting bid as ADDR3 (bid winner) to a different receiver should work
         let retract_bid_msg = ExecuteMsg::Retract {
+            auction_id: 1,
             receiver: Some("addr5".to_string()),
         };
         let execute_info = mock_info(ADDR3, &[]);
@@ -371,7 +396,7 @@ mod tests {
         // create a bank message for ADDR3
         let bank_msg = CosmosMsg::Bank(BankMsg::Send {
             to_address: "addr5".to_string(),
-            amount: vec![coin(99_900_000u128.into(), DENOM)],
+            amount: vec![coin(99_900_000u128, DENOM)],
         });
         assert_eq!(res.messages[0].msg, bank_msg);
 
@@ -396,12 +421,13 @@ mod tests {
         let msg = InstantiateMsg {
             commodity: "gold".to_string(),
             contract_owner: None,
-            commision: None,
+            commission: None,
+            ..InstantiateMsg::default()
         };
         instantiate(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
 
         // win bid as ADDR2
-        let execute_msg = ExecuteMsg::Bid {};
+        let execute_msg = ExecuteMsg::Bid { auction_id: 1 };
         let execute_info = mock_info(ADDR2, &[coin(10_000_000, DENOM)]);
 
         execute(
@@ -435,13 +461,13 @@ mod tests {
         .unwrap();
 
         // query highest bidder should return new bidder addr2 & 999_000_000
-        let query_msg = QueryMsg::HighestBidder {};
+        let query_msg = QueryMsg::HighestBidder { auction_id: 1 };
         let res: HighestBidderResponse =
-            from_binary(&query(deps.as_ref(), env.clone(), query_msg).unwrap()).unwrap();
+            from_json(query(deps.as_ref(), env.clone(), query_msg).unwrap()).unwrap();
         assert_eq!(res.total_bid, Uint128::from(999_000_000u32));
 
         // closing a bid as the contract owner
-        let close_bid_msg = ExecuteMsg::Close {};
+        let close_bid_msg = ExecuteMsg::Close { auction_id: 1 };
         let execute_info = mock_info(ADDR1, &[]);
 
         execute(
@@ -453,7 +479,10 @@ mod tests {
         .unwrap();
 
         // retracting bid for ADDR2 (bid winner) without receiver should fail
-        let retract_bid_msg = ExecuteMsg::Retract { receiver: None };
+        let retract_bid_msg = ExecuteMsg::Retract {
+            auction_id: 1,
+            receiver: None,
+        };
         let execute_info = mock_info(ADDR2, &[]);
 
         let res = execute(
@@ -466,6 +495,7 @@ mod tests {
 
         // retracting bid as ADDR3 to a different receiver should work
         let retract_bid_msg = ExecuteMsg::Retract {
+            auction_id: 1,
             receiver: Some("addr5".to_string()),
         };
         let execute_info = mock_info(ADDR3, &[]);
@@ -481,7 +511,7 @@ mod tests {
         // create a bank message for ADDR3
         let bank_msg = CosmosMsg::Bank(BankMsg::Send {
             to_address: "addr5".to_string(),
-            amount: vec![coin(99_900_000u128.into(), DENOM)],
+            amount: vec![coin(99_900_000u128, DENOM)],
         });
         assert_eq!(res.messages[0].msg, bank_msg);
 
@@ -506,12 +536,13 @@ mod tests {
         let msg = InstantiateMsg {
             commodity: "gold".to_string(),
             contract_owner: None,
-            commision: None,
+            commission: None,
+            ..InstantiateMsg::default()
         };
         instantiate(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
 
         // Alex wins bid with 15 ATOM
-        let execute_msg = ExecuteMsg::Bid {};
+        let execute_msg = ExecuteMsg::Bid { auction_id: 1 };
         let execute_info = mock_info("alex", &[coin(15_000_000, DENOM)]);
 
         execute(
@@ -523,9 +554,9 @@ mod tests {
         .unwrap();
 
         // query highest bidder should return Alex & 14.985 ATOM
-        let query_msg = QueryMsg::HighestBidder {};
+        let query_msg = QueryMsg::HighestBidder { auction_id: 1 };
         let res: HighestBidderResponse =
-            from_binary(&query(deps.as_ref(), env.clone(), query_msg).unwrap()).unwrap();
+            from_json(query(deps.as_ref(), env.clone(), query_msg).unwrap()).unwrap();
         assert_eq!(res.total_bid, Uint128::from(14_985_000u32));
 
         // Ann wins bid with 17 ATOM
@@ -540,17 +571,18 @@ mod tests {
         .unwrap();
 
         // query highest bidder should return Ann & 16.983 ATOM
-        let query_msg = QueryMsg::HighestBidder {};
+        let query_msg = QueryMsg::HighestBidder { auction_id: 1 };
         let res: HighestBidderResponse =
-            from_binary(&query(deps.as_ref(), env.clone(), query_msg).unwrap()).unwrap();
+            from_json(query(deps.as_ref(), env.clone(), query_msg).unwrap()).unwrap();
         assert_eq!(res.total_bid, Uint128::from(16_983_000u32));
 
         // query total bid by Alex should still say 14.985 ATOM
         let query_msg = QueryMsg::TotalBid {
+            auction_id: 1,
             address: "alex".to_string(),
         };
         let res: TotalBidResponse =
-            from_binary(&query(deps.as_ref(), env.clone(), query_msg).unwrap()).unwrap();
+            from_json(query(deps.as_ref(), env.clone(), query_msg).unwrap()).unwrap();
         assert_eq!(res.total_bid, Uint128::from(14_985_000u32));
 
         // Ann sends winning bid (+2 ATOM) and should now have 17.1 ATOM
@@ -565,22 +597,23 @@ mod tests {
         .unwrap();
 
         // query highest bidder should return Ann & 18_981_000
-        let query_msg = QueryMsg::HighestBidder {};
+        let query_msg = QueryMsg::HighestBidder { auction_id: 1 };
         let res: HighestBidderResponse =
-            from_binary(&query(deps.as_ref(), env.clone(), query_msg).unwrap()).unwrap();
+            from_json(query(deps.as_ref(), env.clone(), query_msg).unwrap()).unwrap();
         assert_eq!(res.total_bid, Uint128::from(18_981_000u32));
         assert_eq!(res.addr, "ann".to_string());
 
         // query total bid by alex should be 14.985
         let query_msg = QueryMsg::TotalBid {
+            auction_id: 1,
             address: "alex".to_string(),
         };
         let res: TotalBidResponse =
-            from_binary(&query(deps.as_ref(), env.clone(), query_msg).unwrap()).unwrap();
+            from_json(query(deps.as_ref(), env.clone(), query_msg).unwrap()).unwrap();
         assert_eq!(res.total_bid, Uint128::from(14_985_000u32));
 
         // Alex sends 1 ATOM (lower than highest bid) and should fail
-        let execute_msg = ExecuteMsg::Bid {};
+        let execute_msg = ExecuteMsg::Bid { auction_id: 1 };
         let execute_info = mock_info("alex", &[coin(1_000_000, DENOM)]);
 
         let res = execute(
@@ -592,7 +625,7 @@ mod tests {
         assert_eq!(res.unwrap_err(), ContractError::BidTooLow {});
 
         // Alex sends 5 ATOM
-        let execute_msg = ExecuteMsg::Bid {};
+        let execute_msg = ExecuteMsg::Bid { auction_id: 1 };
         let execute_info = mock_info("alex", &[coin(5_000_000, DENOM)]);
 
         execute(
@@ -604,22 +637,23 @@ mod tests {
         .unwrap();
 
         // query highest bidder should return Alex & 19_980_000
-        let query_msg = QueryMsg::HighestBidder {};
+        let query_msg = QueryMsg::HighestBidder { auction_id: 1 };
         let res: HighestBidderResponse =
-            from_binary(&query(deps.as_ref(), env.clone(), query_msg).unwrap()).unwrap();
+            from_json(query(deps.as_ref(), env.clone(), query_msg).unwrap()).unwrap();
         assert_eq!(res.total_bid, Uint128::from(19_980_000u32));
         assert_eq!(res.addr, "alex".to_string());
 
         // query total bid by Ann should be 18_981_000
         let query_msg = QueryMsg::TotalBid {
+            auction_id: 1,
             address: "ann".to_string(),
         };
         let res: TotalBidResponse =
-            from_binary(&query(deps.as_ref(), env.clone(), query_msg).unwrap()).unwrap();
+            from_json(query(deps.as_ref(), env.clone(), query_msg).unwrap()).unwrap();
         assert_eq!(res.total_bid, Uint128::from(18_981_000u32));
 
         // closing a bid as the contract owner
-        let close_bid_msg = ExecuteMsg::Close {};
+        let close_bid_msg = ExecuteMsg::Close { auction_id: 1 };
         let execute_info = mock_info("owner", &[]);
 
         let res = execute(
@@ -633,12 +667,15 @@ mod tests {
         // create a bank message for owner
         let bank_msg = CosmosMsg::Bank(BankMsg::Send {
             to_address: "owner".to_string(),
-            amount: vec![coin(19_980_000u128.into(), DENOM)],
+            amount: vec![coin(19_980_000u128, DENOM)],
         });
         assert_eq!(res.messages[0].msg, bank_msg);
 
         // retracting bid for Ann (without receiver) should work
-        let retract_bid_msg = ExecuteMsg::Retract { receiver: None };
+        let retract_bid_msg = ExecuteMsg::Retract {
+            auction_id: 1,
+            receiver: None,
+        };
         let execute_info = mock_info("ann", &[]);
 
         let res = execute(
@@ -652,12 +689,15 @@ mod tests {
         // create a bank message for ann
         let bank_msg = CosmosMsg::Bank(BankMsg::Send {
             to_address: "ann".to_string(),
-            amount: vec![coin(18_981_000u128.into(), DENOM)],
+            amount: vec![coin(18_981_000u128, DENOM)],
         });
         assert_eq!(res.messages[0].msg, bank_msg);
 
         // retracting initial bid for owner (without receiver) should work
-        let retract_bid_msg = ExecuteMsg::Retract { receiver: None };
+        let retract_bid_msg = ExecuteMsg::Retract {
+            auction_id: 1,
+            receiver: None,
+        };
         let execute_info = mock_info("owner", &[]);
 
         let res = execute(
@@ -671,8 +711,2563 @@ mod tests {
         // create a bank message for ann
         let bank_msg = CosmosMsg::Bank(BankMsg::Send {
             to_address: "owner".to_string(),
-            amount: vec![coin(1_000_000u128.into(), DENOM)],
+            amount: vec![coin(1_000_000u128, DENOM)],
+        });
+        assert_eq!(res.messages[0].msg, bank_msg);
+    }
+
+    #[test]
+    fn test_expired_bid() {
+        let mut deps = mock_dependencies();
+        let mut env = mock_env();
+        let info = mock_info(ADDR1, &[coin(1_000_000, DENOM)]);
+        let msg = InstantiateMsg {
+            commodity: "gold".to_string(),
+            contract_owner: None,
+            commission: None,
+            duration: Some(Duration::Height(100)),
+            ..InstantiateMsg::default()
+        };
+        instantiate(deps.as_mut(), env.clone(), info, msg).unwrap();
+
+        // bid as ADDR2 before the bid expires should work
+        let execute_msg = ExecuteMsg::Bid { auction_id: 1 };
+        let execute_info = mock_info(ADDR2, &[coin(10_000_000, DENOM)]);
+        execute(
+            deps.as_mut(),
+            env.clone(),
+            execute_info,
+            execute_msg.clone(),
+        )
+        .unwrap();
+
+        // finalizing a bid that has not expired yet should fail
+        let finalize_msg = ExecuteMsg::Finalize { auction_id: 1 };
+        let execute_info = mock_info(ADDR3, &[]);
+        let res = execute(
+            deps.as_mut(),
+            env.clone(),
+            execute_info.clone(),
+            finalize_msg.clone(),
+        );
+        assert_eq!(res.unwrap_err(), ContractError::BidNotExpired {});
+
+        // the bid expires 100 blocks after instantiation
+        env.block.height += 100;
+
+        // query if bid is closed should return true even if nobody finalized it
+        let query_msg = QueryMsg::IsBidClosed { auction_id: 1 };
+        let res: IsBidClosedResponse =
+            from_json(query(deps.as_ref(), env.clone(), query_msg).unwrap()).unwrap();
+        assert!(res.is_closed);
+
+        // query bid winner should return ADDR2
+        let query_msg = QueryMsg::BidWinner { auction_id: 1 };
+        let res: BidWinnerResponse =
+            from_json(query(deps.as_ref(), env.clone(), query_msg).unwrap()).unwrap();
+        assert_eq!(res.winner, Some(ADDR2.to_string()));
+
+        // bidding after expiration should fail
+        let execute_info = mock_info(ADDR3, &[coin(100_000_000, DENOM)]);
+        let res = execute(deps.as_mut(), env.clone(), execute_info, execute_msg);
+        assert_eq!(res.unwrap_err(), ContractError::BidExpired {});
+
+        // finalizing an expired bid as anyone should pay the owner
+        let execute_info = mock_info(ADDR3, &[]);
+        let res = execute(
+            deps.as_mut(),
+            env.clone(),
+            execute_info.clone(),
+            finalize_msg.clone(),
+        )
+        .unwrap();
+        let bank_msg = CosmosMsg::Bank(BankMsg::Send {
+            to_address: ADDR1.to_string(),
+            amount: vec![coin(9_990_000u128, DENOM)],
+        });
+        assert_eq!(res.messages[0].msg, bank_msg);
+
+        // finalizing twice should fail
+        let res = execute(deps.as_mut(), env.clone(), execute_info, finalize_msg);
+        assert_eq!(res.unwrap_err(), ContractError::BidClosed {});
+
+        // closing a finalized bid as the owner should fail too
+        let execute_info = mock_info(ADDR1, &[]);
+        let res = execute(
+            deps.as_mut(),
+            env,
+            execute_info,
+            ExecuteMsg::Close { auction_id: 1 },
+        );
+        assert_eq!(res.unwrap_err(), ContractError::BidClosed {});
+    }
+
+    #[test]
+    fn test_soft_close() {
+        let mut deps = mock_dependencies();
+        let mut env = mock_env();
+        let start = env.block.height;
+        let info = mock_info(ADDR1, &[coin(1_000_000, DENOM)]);
+        let msg = InstantiateMsg {
+            commodity: "gold".to_string(),
+            duration: Some(Duration::Height(100)),
+            soft_close: Some(SoftClose {
+                window: Duration::Height(10),
+                extension: Duration::Height(20),
+                hard_deadline: Some(Expiration::AtHeight(start + 130)),
+            }),
+            ..InstantiateMsg::default()
+        };
+        instantiate(deps.as_mut(), env.clone(), info, msg).unwrap();
+
+        // bidding outside of the soft close window should not extend the bid
+        env.block.height = start + 50;
+        let execute_msg = ExecuteMsg::Bid { auction_id: 1 };
+        let execute_info = mock_info(ADDR2, &[coin(10_000_000, DENOM)]);
+        let res = execute(
+            deps.as_mut(),
+            env.clone(),
+            execute_info,
+            execute_msg.clone(),
+        )
+        .unwrap();
+        assert_eq!(res.attributes.len(), 4);
+
+        // bidding inside the soft close window should extend the bid by 20 blocks
+        env.block.height = start + 95;
+        let execute_info = mock_info(ADDR3, &[coin(20_000_000, DENOM)]);
+        let res = execute(
+            deps.as_mut(),
+            env.clone(),
+            execute_info,
+            execute_msg.clone(),
+        )
+        .unwrap();
+        assert_eq!(
+            res.attributes[4],
+            attr(
+                "extended_expiration",
+                Expiration::AtHeight(start + 120).to_string()
+            )
+        );
+
+        // query expiration should return the extended expiration
+        let query_msg = QueryMsg::Expiration { auction_id: 1 };
+        let res: ExpirationResponse =
+            from_json(query(deps.as_ref(), env.clone(), query_msg.clone()).unwrap()).unwrap();
+        assert_eq!(res.expiration, Expiration::AtHeight(start + 120));
+        assert!(!res.is_expired);
+
+        // the extension should be capped by the hard deadline
+        env.block.height = start + 115;
+        let execute_info = mock_info(ADDR2, &[coin(20_000_000, DENOM)]);
+        let res = execute(
+            deps.as_mut(),
+            env.clone(),
+            execute_info,
+            execute_msg.clone(),
+        )
+        .unwrap();
+        assert_eq!(
+            res.attributes[4],
+            attr(
+                "extended_expiration",
+                Expiration::AtHeight(start + 130).to_string()
+            )
+        );
+
+        // bidding once the hard deadline was reached should not extend the bid anymore
+        env.block.height = start + 125;
+        let execute_info = mock_info(ADDR3, &[coin(20_000_000, DENOM)]);
+        let res = execute(
+            deps.as_mut(),
+            env.clone(),
+            execute_info,
+            execute_msg.clone(),
+        )
+        .unwrap();
+        assert_eq!(res.attributes.len(), 4);
+
+        // the bid expires at the hard deadline
+        env.block.height = start + 130;
+        let res: ExpirationResponse =
+            from_json(query(deps.as_ref(), env.clone(), query_msg).unwrap()).unwrap();
+        assert_eq!(res.expiration, Expiration::AtHeight(start + 130));
+        assert!(res.is_expired);
+
+        let execute_info = mock_info(ADDR2, &[coin(20_000_000, DENOM)]);
+        let res = execute(deps.as_mut(), env, execute_info, execute_msg);
+        assert_eq!(res.unwrap_err(), ContractError::BidExpired {});
+    }
+
+    #[test]
+    fn test_min_increment() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+        let info = mock_info(ADDR1, &[coin(1_000_000, DENOM)]);
+        let msg = InstantiateMsg {
+            commodity: "gold".to_string(),
+            min_increment: Some(MinIncrement::Percentage(Decimal::percent(10))),
+            ..InstantiateMsg::default()
+        };
+        instantiate(deps.as_mut(), env.clone(), info, msg).unwrap();
+
+        // outbidding the initial bid by less than 10% should fail
+        let execute_msg = ExecuteMsg::Bid { auction_id: 1 };
+        let execute_info = mock_info(ADDR2, &[coin(1_050_000, DENOM)]);
+        let res = execute(
+            deps.as_mut(),
+            env.clone(),
+            execute_info,
+            execute_msg.clone(),
+        );
+        assert_eq!(
+            res.unwrap_err(),
+            ContractError::BidBelowMinIncrement {
+                min_bid: Uint128::from(1_100_000u128)
+            }
+        );
+
+        // outbidding the initial bid by 10% (after commission) should work
+        let execute_info = mock_info(ADDR2, &[coin(1_102_000, DENOM)]);
+        execute(
+            deps.as_mut(),
+            env.clone(),
+            execute_info,
+            execute_msg.clone(),
+        )
+        .unwrap();
+
+        // outbidding ADDR2 (1_100_898) by less than 10% should fail
+        let execute_info = mock_info(ADDR3, &[coin(1_105_000, DENOM)]);
+        let res = execute(deps.as_mut(), env, execute_info, execute_msg);
+        assert_eq!(
+            res.unwrap_err(),
+            ContractError::BidBelowMinIncrement {
+                min_bid: Uint128::from(1_210_988u128)
+            }
+        );
+    }
+
+    #[test]
+    fn test_reserve_price_not_met() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+        let info = mock_info(ADDR1, &[coin(1_000_000, DENOM)]);
+        let msg = InstantiateMsg {
+            commodity: "gold".to_string(),
+            reserve_price: Some(Uint128::from(50_000_000u128)),
+            ..InstantiateMsg::default()
+        };
+        instantiate(deps.as_mut(), env.clone(), info, msg).unwrap();
+
+        // bids below the reserve price are accepted
+        let execute_msg = ExecuteMsg::Bid { auction_id: 1 };
+        let execute_info = mock_info(ADDR2, &[coin(10_000_000, DENOM)]);
+        execute(deps.as_mut(), env.clone(), execute_info, execute_msg).unwrap();
+
+        // closing the bid should not pay the owner
+        let execute_info = mock_info(ADDR1, &[]);
+        let res = execute(
+            deps.as_mut(),
+            env.clone(),
+            execute_info,
+            ExecuteMsg::Close { auction_id: 1 },
+        )
+        .unwrap();
+        assert!(res.messages.is_empty());
+
+        // query bid winner should report no sale
+        let query_msg = QueryMsg::BidWinner { auction_id: 1 };
+        let res: BidWinnerResponse =
+            from_json(query(deps.as_ref(), env.clone(), query_msg).unwrap()).unwrap();
+        assert_eq!(res.winner, None);
+
+        // the highest bidder can retract their bid
+        let retract_bid_msg = ExecuteMsg::Retract {
+            auction_id: 1,
+            receiver: None,
+        };
+        let execute_info = mock_info(ADDR2, &[]);
+        let res = execute(
+            deps.as_mut(),
+            env.clone(),
+            execute_info,
+            retract_bid_msg.clone(),
+        )
+        .unwrap();
+        let bank_msg = CosmosMsg::Bank(BankMsg::Send {
+            to_address: ADDR2.to_string(),
+            amount: vec![coin(9_990_000u128, DENOM)],
+        });
+        assert_eq!(res.messages[0].msg, bank_msg);
+
+        // and so can the owner
+        let execute_info = mock_info(ADDR1, &[]);
+        let res = execute(deps.as_mut(), env, execute_info, retract_bid_msg).unwrap();
+        let bank_msg = CosmosMsg::Bank(BankMsg::Send {
+            to_address: ADDR1.to_string(),
+            amount: vec![coin(1_000_000u128, DENOM)],
+        });
+        assert_eq!(res.messages[0].msg, bank_msg);
+    }
+
+    #[test]
+    fn test_auction_house() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+        let info = mock_info(ADDR1, &[coin(1_000_000, DENOM)]);
+        let msg = InstantiateMsg {
+            commodity: "gold".to_string(),
+            sellers: Some(vec![ADDR2.to_string()]),
+            ..InstantiateMsg::default()
+        };
+        instantiate(deps.as_mut(), env.clone(), info, msg).unwrap();
+
+        // creating an auction as a non-whitelisted seller should fail
+        let create_msg = ExecuteMsg::CreateAuction(AuctionMsg {
+            commodity: "silver".to_string(),
+            ..AuctionMsg::default()
+        });
+        let execute_info = mock_info(ADDR3, &[coin(1_000, DENOM)]);
+        let res = execute(deps.as_mut(), env.clone(), execute_info, create_msg.clone());
+        assert_eq!(res.unwrap_err(), ContractError::Unauthorized {});
+
+        // creating an auction as a whitelisted seller should work
+        let execute_info = mock_info(ADDR2, &[coin(1_000, DENOM)]);
+        let res = execute(deps.as_mut(), env.clone(), execute_info, create_msg.clone()).unwrap();
+        assert_eq!(res.attributes[1], attr("auction_id", "2"));
+
+        // the contract owner can always create auctions
+        let execute_info = mock_info(ADDR1, &[coin(1_000, DENOM)]);
+        let res = execute(deps.as_mut(), env.clone(), execute_info, create_msg).unwrap();
+        assert_eq!(res.attributes[1], attr("auction_id", "3"));
+
+        // sellers cannot bid on their own auction
+        let execute_info = mock_info(ADDR2, &[coin(10_000, DENOM)]);
+        let res = execute(
+            deps.as_mut(),
+            env.clone(),
+            execute_info,
+            ExecuteMsg::Bid { auction_id: 2 },
+        );
+        assert_eq!(res.unwrap_err(), ContractError::Unauthorized {});
+
+        // bidding on auction 2 should pay the commission to the contract owner
+        let execute_info = mock_info(ADDR3, &[coin(10_000, DENOM)]);
+        let res = execute(
+            deps.as_mut(),
+            env.clone(),
+            execute_info,
+            ExecuteMsg::Bid { auction_id: 2 },
+        )
+        .unwrap();
+        let bank_msg = CosmosMsg::Bank(BankMsg::Send {
+            to_address: ADDR1.to_string(),
+            amount: vec![coin(10u128, DENOM)],
+        });
+        assert_eq!(res.messages[0].msg, bank_msg);
+
+        // auction 1 is not affected by bids on auction 2
+        let query_msg = QueryMsg::HighestBidder { auction_id: 1 };
+        let res: HighestBidderResponse =
+            from_json(query(deps.as_ref(), env.clone(), query_msg).unwrap()).unwrap();
+        assert_eq!(res.addr, ADDR1);
+        assert_eq!(res.total_bid, Uint128::from(1_000_000u128));
+
+        // only the seller can close auction 2, not the contract owner
+        let execute_info = mock_info(ADDR1, &[]);
+        let res = execute(
+            deps.as_mut(),
+            env.clone(),
+            execute_info,
+            ExecuteMsg::Close { auction_id: 2 },
+        );
+        assert_eq!(res.unwrap_err(), ContractError::Unauthorized {});
+
+        // closing auction 2 as the seller should pay them the winning bid
+        let execute_info = mock_info(ADDR2, &[]);
+        let res = execute(
+            deps.as_mut(),
+            env.clone(),
+            execute_info,
+            ExecuteMsg::Close { auction_id: 2 },
+        )
+        .unwrap();
+        let bank_msg = CosmosMsg::Bank(BankMsg::Send {
+            to_address: ADDR2.to_string(),
+            amount: vec![coin(9_990u128, DENOM)],
         });
         assert_eq!(res.messages[0].msg, bank_msg);
+
+        // list every auction
+        let query_msg = QueryMsg::ListAuctions {
+            status: None,
+            seller: None,
+            start_after: None,
+            limit: None,
+        };
+        let res: ListAuctionsResponse =
+            from_json(query(deps.as_ref(), env.clone(), query_msg).unwrap()).unwrap();
+        let ids: Vec<u64> = res.auctions.iter().map(|a| a.auction_id).collect();
+        assert_eq!(ids, vec![1, 2, 3]);
+        assert_eq!(res.auctions[1].highest_bidder, ADDR3);
+        assert_eq!(res.auctions[1].status, BidStatus::Closed);
+
+        // list open auctions sold by the contract owner, one at a time
+        let query_msg = QueryMsg::ListAuctions {
+            status: Some(BidStatus::Opened),
+            seller: Some(ADDR1.to_string()),
+            start_after: None,
+            limit: Some(1),
+        };
+        let res: ListAuctionsResponse =
+            from_json(query(deps.as_ref(), env.clone(), query_msg).unwrap()).unwrap();
+        let ids: Vec<u64> = res.auctions.iter().map(|a| a.auction_id).collect();
+        assert_eq!(ids, vec![1]);
+
+        let query_msg = QueryMsg::ListAuctions {
+            status: Some(BidStatus::Opened),
+            seller: Some(ADDR1.to_string()),
+            start_after: Some(1),
+            limit: Some(1),
+        };
+        let res: ListAuctionsResponse =
+            from_json(query(deps.as_ref(), env.clone(), query_msg).unwrap()).unwrap();
+        let ids: Vec<u64> = res.auctions.iter().map(|a| a.auction_id).collect();
+        assert_eq!(ids, vec![3]);
+
+        // list closed auctions
+        let query_msg = QueryMsg::ListAuctions {
+            status: Some(BidStatus::Closed),
+            seller: None,
+            start_after: None,
+            limit: None,
+        };
+        let res: ListAuctionsResponse =
+            from_json(query(deps.as_ref(), env, query_msg).unwrap()).unwrap();
+        let ids: Vec<u64> = res.auctions.iter().map(|a| a.auction_id).collect();
+        assert_eq!(ids, vec![2]);
+    }
+
+    #[test]
+    fn test_nft_escrow() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+        let info = mock_info(ADDR1, &[coin(1_000_000, DENOM)]);
+        let msg = InstantiateMsg {
+            commodity: COMMODITY.to_string(),
+            ..InstantiateMsg::default()
+        };
+        instantiate(deps.as_mut(), env.clone(), info, msg).unwrap();
+
+        let deposit = |token_id: &str| {
+            ExecuteMsg::ReceiveNft(Cw721ReceiveMsg {
+                sender: ADDR2.to_string(),
+                token_id: token_id.to_string(),
+                msg: to_json_binary(&DepositMsg::CreateAuction(AuctionMsg {
+                    commodity: "punk".to_string(),
+                    native_denom: Some(DENOM.to_string()),
+                    ..AuctionMsg::default()
+                }))
+                .unwrap(),
+            })
+        };
+
+        // the nft contract opens two auctions on behalf of the seller
+        let execute_info = mock_info("nft", &[]);
+        let res = execute(
+            deps.as_mut(),
+            env.clone(),
+            execute_info.clone(),
+            deposit("1"),
+        )
+        .unwrap();
+        assert_eq!(res.attributes[1], attr("auction_id", "2"));
+        assert_eq!(res.attributes[3], attr("seller", ADDR2));
+        execute(deps.as_mut(), env.clone(), execute_info, deposit("2")).unwrap();
+
+        let query_msg = QueryMsg::Auction { auction_id: 2 };
+        let res: AuctionResponse =
+            from_json(query(deps.as_ref(), env.clone(), query_msg).unwrap()).unwrap();
+        assert_eq!(
+            res.item,
+            Some(AuctionItem::Cw721 {
+                contract: Addr::unchecked("nft"),
+                token_id: "1".to_string(),
+            })
+        );
+
+        // bid on the first nft only
+        let execute_info = mock_info(ADDR3, &[coin(1_000_000, DENOM)]);
+        execute(
+            deps.as_mut(),
+            env.clone(),
+            execute_info,
+            ExecuteMsg::Bid { auction_id: 2 },
+        )
+        .unwrap();
+
+        // closing should send the nft to the winner and the winning bid to the seller
+        let execute_info = mock_info(ADDR2, &[]);
+        let res = execute(
+            deps.as_mut(),
+            env.clone(),
+            execute_info.clone(),
+            ExecuteMsg::Close { auction_id: 2 },
+        )
+        .unwrap();
+        let transfer_msg: CosmosMsg = WasmMsg::Execute {
+            contract_addr: "nft".to_string(),
+            msg: to_json_binary(&Cw721ExecuteMsg::TransferNft {
+                recipient: ADDR3.to_string(),
+                token_id: "1".to_string(),
+            })
+            .unwrap(),
+            funds: vec![],
+        }
+        .into();
+        assert_eq!(res.messages[0].msg, transfer_msg);
+        let bank_msg = CosmosMsg::Bank(BankMsg::Send {
+            to_address: ADDR2.to_string(),
+            amount: vec![coin(999_000u128, DENOM)],
+        });
+        assert_eq!(res.messages[1].msg, bank_msg);
+
+        // closing without bids should only return the nft to the seller
+        let res = execute(
+            deps.as_mut(),
+            env,
+            execute_info,
+            ExecuteMsg::Close { auction_id: 3 },
+        )
+        .unwrap();
+        let transfer_msg: CosmosMsg = WasmMsg::Execute {
+            contract_addr: "nft".to_string(),
+            msg: to_json_binary(&Cw721ExecuteMsg::TransferNft {
+                recipient: ADDR2.to_string(),
+                token_id: "2".to_string(),
+            })
+            .unwrap(),
+            funds: vec![],
+        }
+        .into();
+        assert_eq!(res.messages.len(), 1);
+        assert_eq!(res.messages[0].msg, transfer_msg);
+    }
+
+    #[test]
+    fn test_sealed_bid() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+        let info = mock_info(ADDR1, &[coin(1_000_000, DENOM)]);
+        let msg = InstantiateMsg {
+            commodity: COMMODITY.to_string(),
+            ..InstantiateMsg::default()
+        };
+        instantiate(deps.as_mut(), env.clone(), info, msg).unwrap();
+
+        // ADDR2 sells silver in a second-price sealed auction, forfeiting unrevealed bids to them
+        let create_msg = ExecuteMsg::CreateAuction(AuctionMsg {
+            commodity: "silver".to_string(),
+            duration: Some(Duration::Time(100)),
+            sealed: Some(SealedBidMsg {
+                reveal_period: Duration::Time(100),
+                pricing: Pricing::SecondPrice,
+                forfeit: Forfeit::Seller,
+            }),
+            ..AuctionMsg::default()
+        });
+        let execute_info = mock_info(ADDR2, &[coin(1_000_000, DENOM)]);
+        execute(deps.as_mut(), env.clone(), execute_info, create_msg).unwrap();
+
+        // open bids are not accepted
+        let execute_info = mock_info(ADDR3, &[coin(5_000_000, DENOM)]);
+        let res = execute(
+            deps.as_mut(),
+            env.clone(),
+            execute_info,
+            ExecuteMsg::Bid { auction_id: 2 },
+        );
+        assert_eq!(res.unwrap_err(), ContractError::SealedBid {});
+
+        // three bidders commit, ADDR3 and ADDR4 deposit more than they bid
+        let commit = |bidder: &str, amount: u128, salt: &str| ExecuteMsg::Commit {
+            auction_id: 2,
+            commitment: bid_commitment(2, &Addr::unchecked(bidder), Uint128::from(amount), salt),
+        };
+        let bids = [
+            (ADDR3, 4_000_000u128, 5_000_000u128),
+            (ADDR4, 2_500_000, 3_000_000),
+        ];
+        for (bidder, amount, deposit) in bids {
+            let execute_info = mock_info(bidder, &[coin(deposit, DENOM)]);
+            execute(
+                deps.as_mut(),
+                env.clone(),
+                execute_info,
+                commit(bidder, amount, "salt"),
+            )
+            .unwrap();
+        }
+
+        // addr5 commits with a hash made for another auction
+        let execute_info = mock_info("addr5", &[coin(2_000_000, DENOM)]);
+        let commitment = bid_commitment(
+            1,
+            &Addr::unchecked("addr5"),
+            Uint128::from(2_000_000u128),
+            "salt",
+        );
+        execute(
+            deps.as_mut(),
+            env.clone(),
+            execute_info,
+            ExecuteMsg::Commit {
+                auction_id: 2,
+                commitment,
+            },
+        )
+        .unwrap();
+
+        let reveal = |amount: u128, salt: &str| ExecuteMsg::Reveal {
+            auction_id: 2,
+            amount: Uint128::from(amount),
+            salt: salt.to_string(),
+        };
+
+        // bids cannot be revealed during the commit phase
+        let execute_info = mock_info(ADDR3, &[]);
+        let res = execute(
+            deps.as_mut(),
+            env.clone(),
+            execute_info,
+            reveal(4_000_000, "salt"),
+        );
+        assert_eq!(res.unwrap_err(), ContractError::RevealTooEarly {});
+
+        // reveal phase
+        let mut reveal_env = env.clone();
+        reveal_env.block.time = env.block.time.plus_seconds(150);
+
+        let query_msg = QueryMsg::Auction { auction_id: 2 };
+        let res: AuctionResponse =
+            from_json(query(deps.as_ref(), reveal_env.clone(), query_msg).unwrap()).unwrap();
+        assert_eq!(res.status, BidStatus::Reveal);
+
+        // no more commitments
+        let execute_info = mock_info(ADDR3, &[coin(1_000_000, DENOM)]);
+        let res = execute(
+            deps.as_mut(),
+            reveal_env.clone(),
+            execute_info,
+            commit(ADDR3, 1_000_000, "salt"),
+        );
+        assert_eq!(res.unwrap_err(), ContractError::BidExpired {});
+
+        // revealing something else than what was committed should fail
+        let execute_info = mock_info(ADDR3, &[]);
+        let res = execute(
+            deps.as_mut(),
+            reveal_env.clone(),
+            execute_info.clone(),
+            reveal(4_000_000, "pepper"),
+        );
+        assert_eq!(res.unwrap_err(), ContractError::InvalidReveal {});
+
+        // nor can a commitment be revealed on another auction than the one it was made for
+        let res = execute(
+            deps.as_mut(),
+            reveal_env.clone(),
+            mock_info("addr5", &[]),
+            reveal(2_000_000, "salt"),
+        );
+        assert_eq!(res.unwrap_err(), ContractError::InvalidReveal {});
+
+        // ADDR3 and ADDR4 reveal, addr5 never does
+        execute(
+            deps.as_mut(),
+            reveal_env.clone(),
+            execute_info,
+            reveal(4_000_000, "salt"),
+        )
+        .unwrap();
+        let execute_info = mock_info(ADDR4, &[]);
+        execute(
+            deps.as_mut(),
+            reveal_env.clone(),
+            execute_info,
+            reveal(2_500_000, "salt"),
+        )
+        .unwrap();
+
+        // the seller cannot close the auction until every bid had the chance to be revealed
+        let execute_info = mock_info(ADDR2, &[]);
+        let res = execute(
+            deps.as_mut(),
+            reveal_env,
+            execute_info,
+            ExecuteMsg::Close { auction_id: 2 },
+        );
+        assert_eq!(res.unwrap_err(), ContractError::BidStillOpen {});
+
+        // anyone can finalize the auction once the reveal phase is over
+        let mut closed_env = env.clone();
+        closed_env.block.time = env.block.time.plus_seconds(250);
+        let execute_info = mock_info(ADDR4, &[]);
+        let res = execute(
+            deps.as_mut(),
+            closed_env.clone(),
+            execute_info,
+            ExecuteMsg::Finalize { auction_id: 2 },
+        )
+        .unwrap();
+
+        // unrevealed deposits go to the seller, who gets the second highest bid minus commission
+        let expected: Vec<CosmosMsg> = vec![
+            CosmosMsg::Bank(BankMsg::Send {
+                to_address: ADDR2.to_string(),
+                amount: vec![coin(2_000_000u128, DENOM)],
+            }),
+            CosmosMsg::Bank(BankMsg::Send {
+                to_address: ADDR1.to_string(),
+                amount: vec![coin(2_500u128, DENOM)],
+            }),
+            CosmosMsg::Bank(BankMsg::Send {
+                to_address: ADDR2.to_string(),
+                amount: vec![coin(2_497_500u128, DENOM)],
+            }),
+        ];
+        let msgs: Vec<CosmosMsg> = res.messages.into_iter().map(|m| m.msg).collect();
+        assert_eq!(msgs, expected);
+
+        let query_msg = QueryMsg::BidWinner { auction_id: 2 };
+        let res: BidWinnerResponse =
+            from_json(query(deps.as_ref(), closed_env.clone(), query_msg).unwrap()).unwrap();
+        assert_eq!(res.winner, Some(ADDR3.to_string()));
+
+        // the winner, the other revealed bidder and the seller get back what is left of their deposits
+        let refunds = [
+            (ADDR3, 2_500_000u128),
+            (ADDR4, 3_000_000),
+            (ADDR2, 1_000_000),
+        ];
+        let retract_msg = ExecuteMsg::Retract {
+            auction_id: 2,
+            receiver: None,
+        };
+        for (bidder, refund) in refunds {
+            let execute_info = mock_info(bidder, &[]);
+            let res = execute(
+                deps.as_mut(),
+                closed_env.clone(),
+                execute_info,
+                retract_msg.clone(),
+            )
+            .unwrap();
+            let bank_msg = CosmosMsg::Bank(BankMsg::Send {
+                to_address: bidder.to_string(),
+                amount: vec![coin(refund, DENOM)],
+            });
+            assert_eq!(res.messages[0].msg, bank_msg);
+        }
+
+        // the unrevealed deposit was forfeited
+        let execute_info = mock_info("addr5", &[]);
+        let res = execute(deps.as_mut(), closed_env, execute_info, retract_msg);
+        assert_eq!(res.unwrap_err(), ContractError::NothingToRetract {});
+    }
+
+    #[test]
+    fn test_sealed_bid_single_reveal() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+        let info = mock_info(ADDR1, &[coin(1_000_000, DENOM)]);
+        let msg = InstantiateMsg {
+            commodity: COMMODITY.to_string(),
+            ..InstantiateMsg::default()
+        };
+        instantiate(deps.as_mut(), env.clone(), info, msg).unwrap();
+
+        // ADDR2 escrows two nfts in second-price sealed auctions, the second with a reserve price
+        let deposit = |token_id: &str, reserve_price: Option<u128>| {
+            ExecuteMsg::ReceiveNft(Cw721ReceiveMsg {
+                sender: ADDR2.to_string(),
+                token_id: token_id.to_string(),
+                msg: to_json_binary(&DepositMsg::CreateAuction(AuctionMsg {
+                    commodity: "punk".to_string(),
+                    native_denom: Some(DENOM.to_string()),
+                    duration: Some(Duration::Time(100)),
+                    sealed: Some(SealedBidMsg {
+                        reveal_period: Duration::Time(100),
+                        pricing: Pricing::SecondPrice,
+                        forfeit: Forfeit::Seller,
+                    }),
+                    reserve_price: reserve_price.map(Uint128::from),
+                    ..AuctionMsg::default()
+                }))
+                .unwrap(),
+            })
+        };
+        let execute_info = mock_info("nft", &[]);
+        execute(
+            deps.as_mut(),
+            env.clone(),
+            execute_info.clone(),
+            deposit("1", None),
+        )
+        .unwrap();
+        execute(
+            deps.as_mut(),
+            env.clone(),
+            execute_info,
+            deposit("2", Some(300)),
+        )
+        .unwrap();
+
+        // ADDR3 is the only one to commit and reveal a bid of 500 on both
+        let mut reveal_env = env.clone();
+        reveal_env.block.time = env.block.time.plus_seconds(150);
+        for auction_id in [2, 3] {
+            let execute_info = mock_info(ADDR3, &[coin(500, DENOM)]);
+            let commitment = bid_commitment(
+                auction_id,
+                &Addr::unchecked(ADDR3),
+                Uint128::from(500u128),
+                "salt",
+            );
+            execute(
+                deps.as_mut(),
+                env.clone(),
+                execute_info,
+                ExecuteMsg::Commit {
+                    auction_id,
+                    commitment,
+                },
+            )
+            .unwrap();
+
+            let execute_info = mock_info(ADDR3, &[]);
+            execute(
+                deps.as_mut(),
+                reveal_env.clone(),
+                execute_info,
+                ExecuteMsg::Reveal {
+                    auction_id,
+                    amount: Uint128::from(500u128),
+                    salt: "salt".to_string(),
+                },
+            )
+            .unwrap();
+        }
+
+        // the runner-up is the seller's empty bid, so the winner pays the minimum bid over it,
+        // or the reserve price, and gets the nft
+        let mut closed_env = env.clone();
+        closed_env.block.time = env.block.time.plus_seconds(250);
+        for (auction_id, token_id, price, refund) in [(2, "1", 1u128, 499u128), (3, "2", 300, 200)]
+        {
+            let execute_info = mock_info(ADDR4, &[]);
+            let res = execute(
+                deps.as_mut(),
+                closed_env.clone(),
+                execute_info,
+                ExecuteMsg::Finalize { auction_id },
+            )
+            .unwrap();
+            let expected: Vec<CosmosMsg> = vec![
+                WasmMsg::Execute {
+                    contract_addr: "nft".to_string(),
+                    msg: to_json_binary(&Cw721ExecuteMsg::TransferNft {
+                        recipient: ADDR3.to_string(),
+                        token_id: token_id.to_string(),
+                    })
+                    .unwrap(),
+                    funds: vec![],
+                }
+                .into(),
+                CosmosMsg::Bank(BankMsg::Send {
+                    to_address: ADDR2.to_string(),
+                    amount: vec![coin(price, DENOM)],
+                }),
+            ];
+            let msgs: Vec<CosmosMsg> = res.messages.into_iter().map(|m| m.msg).collect();
+            assert_eq!(msgs, expected);
+
+            let query_msg = QueryMsg::BidWinner { auction_id };
+            let res: BidWinnerResponse =
+                from_json(query(deps.as_ref(), closed_env.clone(), query_msg).unwrap()).unwrap();
+            assert_eq!(res.winner, Some(ADDR3.to_string()));
+
+            // the winner only gets back what is left of their deposit
+            let execute_info = mock_info(ADDR3, &[]);
+            let res = execute(
+                deps.as_mut(),
+                closed_env.clone(),
+                execute_info,
+                ExecuteMsg::Retract {
+                    auction_id,
+                    receiver: None,
+                },
+            )
+            .unwrap();
+            let bank_msg = CosmosMsg::Bank(BankMsg::Send {
+                to_address: ADDR3.to_string(),
+                amount: vec![coin(refund, DENOM)],
+            });
+            assert_eq!(res.messages[0].msg, bank_msg);
+        }
+    }
+
+    #[test]
+    fn test_dutch_auction() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+        let info = mock_info(ADDR1, &[coin(1_000_000, DENOM)]);
+        let msg = InstantiateMsg {
+            commodity: COMMODITY.to_string(),
+            ..InstantiateMsg::default()
+        };
+        instantiate(deps.as_mut(), env.clone(), info, msg).unwrap();
+
+        // ADDR2 sells silver from 10 ATOM down to 2 ATOM over 100 blocks, ADDR3 copper in steps
+        let create_msg = |commodity: &str, decay: Decay| {
+            ExecuteMsg::CreateAuction(AuctionMsg {
+                commodity: commodity.to_string(),
+                duration: Some(Duration::Height(100)),
+                dutch: Some(DutchAuctionMsg {
+                    start_price: Uint128::from(10_000_000u128),
+                    floor_price: Uint128::from(2_000_000u128),
+                    decay,
+                }),
+                ..AuctionMsg::default()
+            })
+        };
+        let execute_info = mock_info(ADDR2, &[coin(1_000, DENOM)]);
+        execute(
+            deps.as_mut(),
+            env.clone(),
+            execute_info,
+            create_msg("silver", Decay::Linear),
+        )
+        .unwrap();
+        let execute_info = mock_info(ADDR3, &[coin(1_000, DENOM)]);
+        let decay = Decay::Step {
+            interval: 10,
+            amount: Uint128::from(1_000_000u128),
+        };
+        execute(
+            deps.as_mut(),
+            env.clone(),
+            execute_info,
+            create_msg("copper", decay),
+        )
+        .unwrap();
+
+        let current_price = |env: &cosmwasm_std::Env, auction_id: u64| -> Uint128 {
+            let query_msg = QueryMsg::CurrentPrice { auction_id };
+            let res: CurrentPriceResponse =
+                from_json(query(deps.as_ref(), env.clone(), query_msg).unwrap()).unwrap();
+            res.price
+        };
+
+        // halfway through, the price dropped by half of the way to the floor
+        let mut later_env = env.clone();
+        later_env.block.height += 50;
+        assert_eq!(current_price(&env, 2), Uint128::from(10_000_000u128));
+        assert_eq!(current_price(&later_env, 2), Uint128::from(6_000_000u128));
+
+        // stepwise, the price dropped 5 times and it never goes below the floor
+        assert_eq!(current_price(&later_env, 3), Uint128::from(5_000_000u128));
+        let mut last_env = env.clone();
+        last_env.block.height += 99;
+        assert_eq!(current_price(&last_env, 3), Uint128::from(2_000_000u128));
+
+        // dutch auctions cannot be bid on
+        let execute_info = mock_info(ADDR3, &[coin(7_000_000, DENOM)]);
+        let res = execute(
+            deps.as_mut(),
+            later_env.clone(),
+            execute_info,
+            ExecuteMsg::Bid { auction_id: 2 },
+        );
+        assert_eq!(res.unwrap_err(), ContractError::DutchAuction {});
+
+        // paying less than the current price should fail
+        let execute_info = mock_info(ADDR3, &[coin(5_000_000, DENOM)]);
+        let res = execute(
+            deps.as_mut(),
+            later_env.clone(),
+            execute_info,
+            ExecuteMsg::Buy { auction_id: 2 },
+        );
+        assert_eq!(res.unwrap_err(), ContractError::BidTooLow {});
+
+        // the first buyer paying enough wins right away and gets the change back
+        let execute_info = mock_info(ADDR3, &[coin(7_000_000, DENOM)]);
+        let res = execute(
+            deps.as_mut(),
+            later_env.clone(),
+            execute_info,
+            ExecuteMsg::Buy { auction_id: 2 },
+        )
+        .unwrap();
+        let expected: Vec<CosmosMsg> = vec![
+            CosmosMsg::Bank(BankMsg::Send {
+                to_address: ADDR1.to_string(),
+                amount: vec![coin(6_000u128, DENOM)],
+            }),
+            CosmosMsg::Bank(BankMsg::Send {
+                to_address: ADDR2.to_string(),
+                amount: vec![coin(5_994_000u128, DENOM)],
+            }),
+            CosmosMsg::Bank(BankMsg::Send {
+                to_address: ADDR3.to_string(),
+                amount: vec![coin(1_000_000u128, DENOM)],
+            }),
+        ];
+        // buying shows up as a bid of the whole price
+        assert!(res.events.contains(
+            &Event::new("auction_bid")
+                .add_attribute("auction_id", "2")
+                .add_attribute("bidder", ADDR3)
+                .add_attribute("gross", "6000000")
+                .add_attribute("commission", "6000")
+                .add_attribute("net", "5994000")
+                .add_attribute("total_bid", "6000000")
+                .add_attribute("denom", DENOM)
+                .add_attribute("previous_leader", ADDR2)
+        ));
+        let msgs: Vec<CosmosMsg> = res.messages.into_iter().map(|m| m.msg).collect();
+        assert_eq!(msgs, expected);
+
+        let query_msg = QueryMsg::BidWinner { auction_id: 2 };
+        let res: BidWinnerResponse =
+            from_json(query(deps.as_ref(), later_env.clone(), query_msg).unwrap()).unwrap();
+        assert_eq!(res.winner, Some(ADDR3.to_string()));
+
+        // nobody else can buy it
+        let execute_info = mock_info(ADDR4, &[coin(7_000_000, DENOM)]);
+        let res = execute(
+            deps.as_mut(),
+            later_env.clone(),
+            execute_info,
+            ExecuteMsg::Buy { auction_id: 2 },
+        );
+        assert_eq!(res.unwrap_err(), ContractError::BidClosed {});
+
+        // the seller gets their initial bid back
+        let execute_info = mock_info(ADDR2, &[]);
+        let res = execute(
+            deps.as_mut(),
+            later_env,
+            execute_info,
+            ExecuteMsg::Retract {
+                auction_id: 2,
+                receiver: None,
+            },
+        )
+        .unwrap();
+        let bank_msg = CosmosMsg::Bank(BankMsg::Send {
+            to_address: ADDR2.to_string(),
+            amount: vec![coin(1_000u128, DENOM)],
+        });
+        assert_eq!(res.messages[0].msg, bank_msg);
+    }
+
+    #[test]
+    fn test_bid_leaderboard() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+        let info = mock_info(ADDR1, &[coin(1_000_000, DENOM)]);
+        let msg = InstantiateMsg {
+            commodity: COMMODITY.to_string(),
+            ..InstantiateMsg::default()
+        };
+        instantiate(deps.as_mut(), env.clone(), info, msg).unwrap();
+
+        let bids = [
+            (ADDR2, 2_000_000u128),
+            (ADDR3, 3_000_000),
+            (ADDR2, 2_000_000),
+            (ADDR4, 5_000_000),
+        ];
+        for (bidder, amount) in bids {
+            let execute_info = mock_info(bidder, &[coin(amount, DENOM)]);
+            execute(
+                deps.as_mut(),
+                env.clone(),
+                execute_info,
+                ExecuteMsg::Bid { auction_id: 1 },
+            )
+            .unwrap();
+        }
+
+        let list_bids = |start_after: Option<&str>, order: Option<OrderBy>| {
+            let query_msg = QueryMsg::ListBids {
+                auction_id: 1,
+                start_after: start_after.map(str::to_string),
+                limit: Some(2),
+                order,
+            };
+            let res: ListBidsResponse =
+                from_json(query(deps.as_ref(), env.clone(), query_msg).unwrap()).unwrap();
+            res.bids
+                .into_iter()
+                .map(|bid| (bid.bidder, bid.total_bid.u128()))
+                .collect::<Vec<_>>()
+        };
+
+        // highest total bids first, two at a time
+        assert_eq!(
+            list_bids(None, None),
+            vec![
+                (ADDR4.to_string(), 4_995_000),
+                (ADDR2.to_string(), 3_996_000)
+            ]
+        );
+        assert_eq!(
+            list_bids(Some(ADDR2), None),
+            vec![
+                (ADDR3.to_string(), 2_997_000),
+                (ADDR1.to_string(), 1_000_000)
+            ]
+        );
+
+        // lowest total bids first
+        assert_eq!(
+            list_bids(None, Some(OrderBy::Ascending)),
+            vec![
+                (ADDR1.to_string(), 1_000_000),
+                (ADDR3.to_string(), 2_997_000)
+            ]
+        );
+
+        // every bid is logged in order, the initial bid of the seller is not a bid
+        let query_msg = QueryMsg::BidHistory {
+            auction_id: 1,
+            start_after: Some(2),
+            limit: None,
+        };
+        let res: BidHistoryResponse =
+            from_json(query(deps.as_ref(), env.clone(), query_msg).unwrap()).unwrap();
+        let ids: Vec<u64> = res.bids.iter().map(|bid| bid.id).collect();
+        assert_eq!(ids, vec![3, 4]);
+        assert_eq!(res.bids[0].bidder, ADDR2);
+        assert_eq!(res.bids[0].gross, Uint128::from(2_000_000u128));
+        assert_eq!(res.bids[0].commission, Uint128::from(2_000u128));
+        assert_eq!(res.bids[0].net, Uint128::from(1_998_000u128));
+        assert_eq!(res.bids[0].height, env.block.height);
+        assert_eq!(res.bids[0].time, env.block.time);
+    }
+
+    #[test]
+    fn test_commission() {
+        // 1% commission, 0.5% from 5 ATOM on, paid to a fee collector
+        let instantiate_msg = |mode: CommissionMode| InstantiateMsg {
+            commodity: COMMODITY.to_string(),
+            commission: Some(Commission {
+                rate: Decimal::percent(1),
+                tiers: vec![CommissionTier {
+                    min_bid: Uint128::from(5_000_000u128),
+                    rate: Decimal::permille(5),
+                }],
+                mode,
+            }),
+            fee_collector: Some("collector".to_string()),
+            ..InstantiateMsg::default()
+        };
+        let fee = |amount: u128| {
+            CosmosMsg::Bank(BankMsg::Send {
+                to_address: "collector".to_string(),
+                amount: vec![coin(amount, DENOM)],
+            })
+        };
+
+        // every bid pays the rate of its own tier
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+        let info = mock_info(ADDR1, &[coin(1_000_000, DENOM)]);
+        let msg = instantiate_msg(CommissionMode::EveryBid);
+        instantiate(deps.as_mut(), env.clone(), info, msg).unwrap();
+
+        let bids = [
+            (ADDR2, 2_000_000u128, 20_000u128),
+            (ADDR3, 6_000_000, 30_000),
+        ];
+        for (bidder, amount, commission) in bids {
+            let execute_info = mock_info(bidder, &[coin(amount, DENOM)]);
+            let res = execute(
+                deps.as_mut(),
+                env.clone(),
+                execute_info,
+                ExecuteMsg::Bid { auction_id: 1 },
+            )
+            .unwrap();
+            assert_eq!(res.messages[0].msg, fee(commission));
+        }
+
+        // only the winning bid pays commission, once the auction is closed
+        let mut deps = mock_dependencies();
+        let info = mock_info(ADDR1, &[coin(1_000_000, DENOM)]);
+        let msg = instantiate_msg(CommissionMode::WinningBid);
+        instantiate(deps.as_mut(), env.clone(), info, msg).unwrap();
+
+        for (bidder, amount, _) in bids {
+            let execute_info = mock_info(bidder, &[coin(amount, DENOM)]);
+            let res = execute(
+                deps.as_mut(),
+                env.clone(),
+                execute_info,
+                ExecuteMsg::Bid { auction_id: 1 },
+            )
+            .unwrap();
+            assert!(res.messages.is_empty());
+        }
+
+        let execute_info = mock_info(ADDR1, &[]);
+        let res = execute(
+            deps.as_mut(),
+            env.clone(),
+            execute_info,
+            ExecuteMsg::Close { auction_id: 1 },
+        )
+        .unwrap();
+        let expected = vec![
+            fee(30_000),
+            CosmosMsg::Bank(BankMsg::Send {
+                to_address: ADDR1.to_string(),
+                amount: vec![coin(5_970_000u128, DENOM)],
+            }),
+        ];
+        let msgs: Vec<CosmosMsg> = res.messages.into_iter().map(|m| m.msg).collect();
+        assert_eq!(msgs, expected);
+
+        // outbid bidders get their whole bid back
+        let execute_info = mock_info(ADDR2, &[]);
+        let res = execute(
+            deps.as_mut(),
+            env,
+            execute_info,
+            ExecuteMsg::Retract {
+                auction_id: 1,
+                receiver: None,
+            },
+        )
+        .unwrap();
+        let bank_msg = CosmosMsg::Bank(BankMsg::Send {
+            to_address: ADDR2.to_string(),
+            amount: vec![coin(2_000_000u128, DENOM)],
+        });
+        assert_eq!(res.messages[0].msg, bank_msg);
+    }
+
+    #[test]
+    fn test_admin() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+        let info = mock_info(ADDR1, &[coin(1_000_000, DENOM)]);
+        let msg = InstantiateMsg {
+            commodity: COMMODITY.to_string(),
+            ..InstantiateMsg::default()
+        };
+        instantiate(deps.as_mut(), env.clone(), info, msg).unwrap();
+
+        // only the owner can propose a new owner
+        let propose_msg = ExecuteMsg::ProposeOwner {
+            new_owner: ADDR2.to_string(),
+        };
+        let err = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info(ADDR2, &[]),
+            propose_msg.clone(),
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::Admin(AdminError::NotAdmin {}));
+        let res = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info(ADDR1, &[]),
+            propose_msg,
+        )
+        .unwrap();
+        assert_eq!(
+            res.events,
+            vec![Event::new("auction_ownership_proposed")
+                .add_attribute("owner", ADDR1)
+                .add_attribute("pending_owner", ADDR2)]
+        );
+
+        // the owner does not change until the proposed owner accepts
+        let res: ConfigResponse =
+            from_json(query(deps.as_ref(), env.clone(), QueryMsg::Config {}).unwrap()).unwrap();
+        assert_eq!(res.contract_owner, ADDR1);
+        assert_eq!(res.pending_owner, Some(ADDR2.to_string()));
+
+        let err = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info(ADDR3, &[]),
+            ExecuteMsg::AcceptOwnership {},
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::Unauthorized {});
+        execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info(ADDR2, &[]),
+            ExecuteMsg::AcceptOwnership {},
+        )
+        .unwrap();
+
+        // the new owner can update the config, the previous one cannot anymore
+        let update_msg = ExecuteMsg::UpdateConfig {
+            fee_collector: Some(ADDR4.to_string()),
+            sellers: Some(vec![ADDR3.to_string()]),
+        };
+        let err = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info(ADDR1, &[]),
+            update_msg.clone(),
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::Admin(AdminError::NotAdmin {}));
+        execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info(ADDR2, &[]),
+            update_msg,
+        )
+        .unwrap();
+
+        let res: ConfigResponse =
+            from_json(query(deps.as_ref(), env.clone(), QueryMsg::Config {}).unwrap()).unwrap();
+        assert_eq!(
+            res,
+            ConfigResponse {
+                contract_owner: ADDR2.to_string(),
+                pending_owner: None,
+                commission: Commission {
+                    rate: COMMISSION_RATE,
+                    tiers: vec![],
+                    mode: CommissionMode::EveryBid,
+                },
+                fee_collector: ADDR4.to_string(),
+                sellers: Some(vec![ADDR3.to_string()]),
+            }
+        );
+
+        // commissions go to the new fee collector
+        let res = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info(ADDR3, &[coin(2_000_000, DENOM)]),
+            ExecuteMsg::Bid { auction_id: 1 },
+        )
+        .unwrap();
+        let bank_msg = CosmosMsg::Bank(BankMsg::Send {
+            to_address: ADDR4.to_string(),
+            amount: vec![coin(2_000u128, DENOM)],
+        });
+        assert_eq!(res.messages[0].msg, bank_msg);
+
+        // only the owner can cancel an auction
+        let err = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info(ADDR1, &[]),
+            ExecuteMsg::Cancel { auction_id: 1 },
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::Admin(AdminError::NotAdmin {}));
+        execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info(ADDR2, &[]),
+            ExecuteMsg::Cancel { auction_id: 1 },
+        )
+        .unwrap();
+
+        // a cancelled auction takes no bids, has no winner and cannot be cancelled again
+        let err = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info(ADDR4, &[coin(3_000_000, DENOM)]),
+            ExecuteMsg::Bid { auction_id: 1 },
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::BidClosed {});
+        let res: BidWinnerResponse = from_json(
+            query(
+                deps.as_ref(),
+                env.clone(),
+                QueryMsg::BidWinner { auction_id: 1 },
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(res.winner, None);
+        let err = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info(ADDR2, &[]),
+            ExecuteMsg::Cancel { auction_id: 1 },
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::BidClosed {});
+
+        // everyone gets their bid back, the highest bidder and the seller included
+        let refunds = [(ADDR3, 1_998_000u128), (ADDR1, 1_000_000)];
+        for (bidder, amount) in refunds {
+            let res = execute(
+                deps.as_mut(),
+                env.clone(),
+                mock_info(bidder, &[]),
+                ExecuteMsg::Retract {
+                    auction_id: 1,
+                    receiver: None,
+                },
+            )
+            .unwrap();
+            let bank_msg = CosmosMsg::Bank(BankMsg::Send {
+                to_address: bidder.to_string(),
+                amount: vec![coin(amount, DENOM)],
+            });
+            assert_eq!(res.messages[0].msg, bank_msg);
+        }
+
+        // the owner goes back to collecting commissions and lets anyone sell
+        let clear_msg = ExecuteMsg::ClearConfig {
+            fee_collector: true,
+            sellers: true,
+        };
+        let err = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info(ADDR1, &[]),
+            clear_msg.clone(),
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::Admin(AdminError::NotAdmin {}));
+        execute(deps.as_mut(), env.clone(), mock_info(ADDR2, &[]), clear_msg).unwrap();
+
+        let res: ConfigResponse =
+            from_json(query(deps.as_ref(), env, QueryMsg::Config {}).unwrap()).unwrap();
+        assert_eq!(res.fee_collector, ADDR2);
+        assert_eq!(res.sellers, None);
+    }
+
+    #[test]
+    fn test_buy_now() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+        let info = mock_info(ADDR1, &[coin(1_000_000, DENOM)]);
+        let msg = InstantiateMsg {
+            commodity: COMMODITY.to_string(),
+            buy_now_price: Some(Uint128::from(10_000_000u128)),
+            buy_now_cutoff: Some(Decimal::percent(50)),
+            ..InstantiateMsg::default()
+        };
+        instantiate(deps.as_mut(), env.clone(), info, msg).unwrap();
+
+        let execute_info = mock_info(ADDR2, &[coin(3_000_000, DENOM)]);
+        execute(
+            deps.as_mut(),
+            env.clone(),
+            execute_info,
+            ExecuteMsg::Bid { auction_id: 1 },
+        )
+        .unwrap();
+
+        // paying less than the buy now price is not enough
+        let execute_info = mock_info(ADDR3, &[coin(9_000_000, DENOM)]);
+        let err = execute(
+            deps.as_mut(),
+            env.clone(),
+            execute_info,
+            ExecuteMsg::BuyNow { auction_id: 1 },
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::BidTooLow {});
+
+        // ADDR2 buys now, getting their change and their previous bid back
+        let execute_info = mock_info(ADDR2, &[coin(11_000_000, DENOM)]);
+        let res = execute(
+            deps.as_mut(),
+            env.clone(),
+            execute_info,
+            ExecuteMsg::BuyNow { auction_id: 1 },
+        )
+        .unwrap();
+        let expected = vec![
+            CosmosMsg::Bank(BankMsg::Send {
+                to_address: ADDR1.to_string(),
+                amount: vec![coin(10_000u128, DENOM)],
+            }),
+            CosmosMsg::Bank(BankMsg::Send {
+                to_address: ADDR1.to_string(),
+                amount: vec![coin(9_990_000u128, DENOM)],
+            }),
+            CosmosMsg::Bank(BankMsg::Send {
+                to_address: ADDR2.to_string(),
+                amount: vec![coin(3_997_000u128, DENOM)],
+            }),
+        ];
+        let msgs: Vec<CosmosMsg> = res.messages.into_iter().map(|m| m.msg).collect();
+        assert_eq!(msgs, expected);
+
+        // the auction is closed right away
+        let res: BidWinnerResponse = from_json(
+            query(
+                deps.as_ref(),
+                env.clone(),
+                QueryMsg::BidWinner { auction_id: 1 },
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(res.winner, Some(ADDR2.to_string()));
+        let execute_info = mock_info(ADDR3, &[coin(12_000_000, DENOM)]);
+        let err = execute(
+            deps.as_mut(),
+            env.clone(),
+            execute_info,
+            ExecuteMsg::BuyNow { auction_id: 1 },
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::BidClosed {});
+
+        // the buyer has nothing left to retract, the seller gets their initial bid back
+        let retract_msg = ExecuteMsg::Retract {
+            auction_id: 1,
+            receiver: None,
+        };
+        let err = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info(ADDR2, &[]),
+            retract_msg.clone(),
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::WinnerCannotRetractBid {});
+        let res = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info(ADDR1, &[]),
+            retract_msg,
+        )
+        .unwrap();
+        let bank_msg = CosmosMsg::Bank(BankMsg::Send {
+            to_address: ADDR1.to_string(),
+            amount: vec![coin(1_000_000u128, DENOM)],
+        });
+        assert_eq!(res.messages[0].msg, bank_msg);
+
+        // buying now is disabled once the highest bid exceeds half of the buy now price
+        let execute_info = mock_info(ADDR1, &[coin(1_000_000, DENOM)]);
+        let create_msg = ExecuteMsg::CreateAuction(AuctionMsg {
+            commodity: "silver".to_string(),
+            buy_now_price: Some(Uint128::from(10_000_000u128)),
+            buy_now_cutoff: Some(Decimal::percent(50)),
+            ..AuctionMsg::default()
+        });
+        execute(deps.as_mut(), env.clone(), execute_info, create_msg).unwrap();
+
+        let execute_info = mock_info(ADDR3, &[coin(6_000_000, DENOM)]);
+        execute(
+            deps.as_mut(),
+            env.clone(),
+            execute_info,
+            ExecuteMsg::Bid { auction_id: 2 },
+        )
+        .unwrap();
+        let execute_info = mock_info(ADDR4, &[coin(10_000_000, DENOM)]);
+        let err = execute(
+            deps.as_mut(),
+            env,
+            execute_info,
+            ExecuteMsg::BuyNow { auction_id: 2 },
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::BuyNowDisabled {});
+    }
+
+    #[test]
+    fn test_bidder_allowlist() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+        let info = mock_info(ADDR1, &[coin(1_000_000, DENOM)]);
+        let msg = InstantiateMsg {
+            commodity: COMMODITY.to_string(),
+            allowlist: Some(vec![ADDR2.to_string()]),
+            max_bid: Some(Uint128::from(5_000_000u128)),
+            ..InstantiateMsg::default()
+        };
+        instantiate(deps.as_mut(), env.clone(), info, msg).unwrap();
+
+        let eligibility = |deps: &cosmwasm_std::OwnedDeps<_, _, _>, address: &str| {
+            let query_msg = QueryMsg::Eligibility {
+                auction_id: 1,
+                address: address.to_string(),
+            };
+            let res: EligibilityResponse =
+                from_json(query(deps.as_ref(), mock_env(), query_msg).unwrap()).unwrap();
+            res
+        };
+
+        // only allowlisted addresses can bid
+        let execute_info = mock_info(ADDR3, &[coin(2_000_000, DENOM)]);
+        let err = execute(
+            deps.as_mut(),
+            env.clone(),
+            execute_info,
+            ExecuteMsg::Bid { auction_id: 1 },
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::NotAllowedToBid {});
+        assert!(!eligibility(&deps, ADDR3).eligible);
+
+        // and up to the maximum bid
+        let execute_info = mock_info(ADDR2, &[coin(2_000_000, DENOM)]);
+        execute(
+            deps.as_mut(),
+            env.clone(),
+            execute_info,
+            ExecuteMsg::Bid { auction_id: 1 },
+        )
+        .unwrap();
+        assert_eq!(
+            eligibility(&deps, ADDR2),
+            EligibilityResponse {
+                eligible: true,
+                max_bid: Some(Uint128::from(5_000_000u128)),
+                remaining: Some(Uint128::from(3_002_000u128)),
+            }
+        );
+        let execute_info = mock_info(ADDR2, &[coin(4_000_000, DENOM)]);
+        let err = execute(
+            deps.as_mut(),
+            env.clone(),
+            execute_info,
+            ExecuteMsg::Bid { auction_id: 1 },
+        )
+        .unwrap_err();
+        assert_eq!(
+            err,
+            ContractError::BidAboveMax {
+                max_bid: Uint128::from(5_000_000u128)
+            }
+        );
+
+        // only the seller and the contract owner can update the allowlist
+        let update_msg = ExecuteMsg::UpdateBidders {
+            auction_id: 1,
+            add: vec![ADDR3.to_string()],
+            remove: vec![ADDR2.to_string()],
+        };
+        let err = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info(ADDR2, &[]),
+            update_msg.clone(),
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::Unauthorized {});
+        execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info(ADDR1, &[]),
+            update_msg,
+        )
+        .unwrap();
+
+        let query_msg = QueryMsg::ListBidders {
+            auction_id: 1,
+            start_after: None,
+            limit: None,
+        };
+        let res: ListBiddersResponse =
+            from_json(query(deps.as_ref(), env.clone(), query_msg).unwrap()).unwrap();
+        assert_eq!(res.bidders, vec![ADDR3.to_string()]);
+
+        // removed bidders cannot raise their bid anymore, added ones can bid
+        let execute_info = mock_info(ADDR2, &[coin(1_000_000, DENOM)]);
+        let err = execute(
+            deps.as_mut(),
+            env.clone(),
+            execute_info,
+            ExecuteMsg::Bid { auction_id: 1 },
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::NotAllowedToBid {});
+        let execute_info = mock_info(ADDR3, &[coin(3_000_000, DENOM)]);
+        execute(
+            deps.as_mut(),
+            env,
+            execute_info,
+            ExecuteMsg::Bid { auction_id: 1 },
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn test_proxy_bid() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+        let info = mock_info(ADDR1, &[coin(1_000_000, DENOM)]);
+        let msg = InstantiateMsg {
+            commodity: COMMODITY.to_string(),
+            min_increment: Some(MinIncrement::Absolute(Uint128::from(100_000u128))),
+            ..InstantiateMsg::default()
+        };
+        instantiate(deps.as_mut(), env.clone(), info, msg).unwrap();
+
+        // ADDR2 escrows 6 ATOM, 5.994 ATOM after commission, to bid up to 5 ATOM
+        let execute_info = mock_info(ADDR2, &[coin(6_000_000, DENOM)]);
+        let set_max_bid = |max: u128| ExecuteMsg::SetMaxBid {
+            auction_id: 1,
+            max: Uint128::from(max),
+        };
+        let res = execute(
+            deps.as_mut(),
+            env.clone(),
+            execute_info,
+            set_max_bid(5_000_000),
+        )
+        .unwrap();
+
+        // their proxy outbids the seller by the minimum increment right away
+        assert_eq!(
+            res.events,
+            vec![
+                Event::new("auction_bid")
+                    .add_attribute("auction_id", "1")
+                    .add_attribute("bidder", ADDR2)
+                    .add_attribute("gross", "1100000")
+                    .add_attribute("commission", "0")
+                    .add_attribute("net", "1100000")
+                    .add_attribute("total_bid", "1100000")
+                    .add_attribute("denom", DENOM)
+                    .add_attribute("previous_leader", ADDR1),
+                Event::new("auction_proxy_bid")
+                    .add_attribute("auction_id", "1")
+                    .add_attribute("bid_id", "1")
+                    .add_attribute("bidder", ADDR2)
+                    .add_attribute("raise", "1100000")
+                    .add_attribute("total_bid", "1100000"),
+            ]
+        );
+
+        // ADDR3 outbids ADDR2, whose proxy outbids ADDR3 back
+        let execute_info = mock_info(ADDR3, &[coin(2_000_000, DENOM)]);
+        execute(
+            deps.as_mut(),
+            env.clone(),
+            execute_info,
+            ExecuteMsg::Bid { auction_id: 1 },
+        )
+        .unwrap();
+
+        // the escrow has to cover the max bid
+        let execute_info = mock_info(ADDR3, &[]);
+        let err = execute(
+            deps.as_mut(),
+            env.clone(),
+            execute_info,
+            set_max_bid(10_000_000),
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::MaxBidNotCovered {});
+
+        // ADDR4's proxy goes all in at 3 ATOM, ADDR2's proxy still outbids it
+        let execute_info = mock_info(ADDR4, &[coin(4_000_000, DENOM)]);
+        execute(
+            deps.as_mut(),
+            env.clone(),
+            execute_info,
+            set_max_bid(3_000_000),
+        )
+        .unwrap();
+
+        let query_msg = QueryMsg::HighestBidder { auction_id: 1 };
+        let res: HighestBidderResponse =
+            from_json(query(deps.as_ref(), env.clone(), query_msg).unwrap()).unwrap();
+        assert_eq!(res.addr, ADDR2);
+        assert_eq!(res.total_bid, Uint128::from(3_100_000u128));
+
+        // every raise made by a proxy is part of the bid history
+        let query_msg = QueryMsg::BidHistory {
+            auction_id: 1,
+            start_after: None,
+            limit: None,
+        };
+        let res: BidHistoryResponse =
+            from_json(query(deps.as_ref(), env.clone(), query_msg).unwrap()).unwrap();
+        let history: Vec<_> = res
+            .bids
+            .iter()
+            .map(|bid| (bid.bidder.as_str(), bid.net.u128(), bid.proxy))
+            .collect();
+        assert_eq!(
+            history,
+            vec![
+                (ADDR2, 1_100_000, true),
+                (ADDR3, 1_998_000, false),
+                (ADDR2, 998_000, true),
+                (ADDR4, 3_000_000, true),
+                (ADDR2, 1_002_000, true),
+            ]
+        );
+
+        // ADDR2 wins at 3.1 ATOM
+        let execute_info = mock_info(ADDR1, &[]);
+        let res = execute(
+            deps.as_mut(),
+            env.clone(),
+            execute_info,
+            ExecuteMsg::Close { auction_id: 1 },
+        )
+        .unwrap();
+        let bank_msg = CosmosMsg::Bank(BankMsg::Send {
+            to_address: ADDR1.to_string(),
+            amount: vec![coin(3_100_000u128, DENOM)],
+        });
+        assert_eq!(res.messages[0].msg, bank_msg);
+
+        // the winner gets back what their proxy did not use, the others their whole escrow
+        let refunds = [
+            (ADDR2, 2_894_000u128),
+            (ADDR3, 1_998_000),
+            (ADDR4, 3_996_000),
+        ];
+        let retract_msg = ExecuteMsg::Retract {
+            auction_id: 1,
+            receiver: None,
+        };
+        for (bidder, amount) in refunds {
+            let execute_info = mock_info(bidder, &[]);
+            let res = execute(
+                deps.as_mut(),
+                env.clone(),
+                execute_info,
+                retract_msg.clone(),
+            )
+            .unwrap();
+            let bank_msg = CosmosMsg::Bank(BankMsg::Send {
+                to_address: bidder.to_string(),
+                amount: vec![coin(amount, DENOM)],
+            });
+            assert_eq!(res.messages[0].msg, bank_msg);
+        }
+        let execute_info = mock_info(ADDR2, &[]);
+        let err = execute(deps.as_mut(), env, execute_info, retract_msg).unwrap_err();
+        assert_eq!(err, ContractError::WinnerCannotRetractBid {});
+    }
+
+    #[test]
+    fn test_withdraw() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+        let info = mock_info(ADDR1, &[coin(1_000_000, DENOM)]);
+        let msg = InstantiateMsg {
+            commodity: COMMODITY.to_string(),
+            ..InstantiateMsg::default()
+        };
+        instantiate(deps.as_mut(), env.clone(), info, msg).unwrap();
+
+        // ADDR2 bids 1.998 ATOM and ADDR3 outbids them with 2.997 ATOM
+        for (bidder, amount) in [(ADDR2, 2_000_000u128), (ADDR3, 3_000_000)] {
+            let execute_info = mock_info(bidder, &[coin(amount, DENOM)]);
+            execute(
+                deps.as_mut(),
+                env.clone(),
+                execute_info,
+                ExecuteMsg::Bid { auction_id: 1 },
+            )
+            .unwrap();
+        }
+
+        let withdraw_msg = |amount: Option<u128>, receiver: Option<&str>| ExecuteMsg::Withdraw {
+            auction_id: 1,
+            amount: amount.map(Uint128::from),
+            receiver: receiver.map(str::to_string),
+        };
+
+        // the highest bidder cannot withdraw
+        let execute_info = mock_info(ADDR3, &[]);
+        let err = execute(
+            deps.as_mut(),
+            env.clone(),
+            execute_info,
+            withdraw_msg(None, None),
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::YouAreTheHighestBidder {});
+
+        // nor can anyone withdraw more than they bid
+        let execute_info = mock_info(ADDR2, &[]);
+        let err = execute(
+            deps.as_mut(),
+            env.clone(),
+            execute_info.clone(),
+            withdraw_msg(Some(2_000_000), None),
+        )
+        .unwrap_err();
+        assert_eq!(
+            err,
+            ContractError::WithdrawAboveBid {
+                total_bid: Uint128::from(1_998_000u128)
+            }
+        );
+
+        // ADDR2 withdraws part of their bid, and then the rest of it to ADDR4
+        let withdrawals = [
+            (Some(998_000), None, ADDR2, 998_000u128),
+            (None, Some(ADDR4), ADDR4, 1_000_000),
+        ];
+        for (amount, receiver, recipient, refund) in withdrawals {
+            let res = execute(
+                deps.as_mut(),
+                env.clone(),
+                execute_info.clone(),
+                withdraw_msg(amount, receiver),
+            )
+            .unwrap();
+            let bank_msg = CosmosMsg::Bank(BankMsg::Send {
+                to_address: recipient.to_string(),
+                amount: vec![coin(refund, DENOM)],
+            });
+            assert_eq!(res.messages[0].msg, bank_msg);
+        }
+
+        let err = execute(
+            deps.as_mut(),
+            env.clone(),
+            execute_info,
+            withdraw_msg(None, None),
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::NothingToRetract {});
+
+        let query_msg = QueryMsg::TotalBid {
+            auction_id: 1,
+            address: ADDR2.to_string(),
+        };
+        let res: TotalBidResponse =
+            from_json(query(deps.as_ref(), env.clone(), query_msg).unwrap()).unwrap();
+        assert!(res.total_bid.is_zero());
+
+        // ADDR3 still leads
+        let query_msg = QueryMsg::HighestBidder { auction_id: 1 };
+        let res: HighestBidderResponse =
+            from_json(query(deps.as_ref(), env.clone(), query_msg).unwrap()).unwrap();
+        assert_eq!(res.addr, ADDR3);
+        assert_eq!(res.total_bid, Uint128::from(2_997_000u128));
+
+        // once closed, bids are retracted instead
+        let execute_info = mock_info(ADDR1, &[]);
+        execute(
+            deps.as_mut(),
+            env.clone(),
+            execute_info.clone(),
+            ExecuteMsg::Close { auction_id: 1 },
+        )
+        .unwrap();
+        let err = execute(deps.as_mut(), env, execute_info, withdraw_msg(None, None)).unwrap_err();
+        assert_eq!(err, ContractError::BidClosed {});
+    }
+
+    #[test]
+    fn test_multi_denom() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+        let info = mock_info(ADDR1, &[coin(1_000_000, DENOM)]);
+        let msg = InstantiateMsg {
+            commodity: COMMODITY.to_string(),
+            accepted_denoms: Some(vec!["uosmo".to_string()]),
+            ..InstantiateMsg::default()
+        };
+        instantiate(deps.as_mut(), env.clone(), info, msg).unwrap();
+
+        // uosmo bids cannot be valued until the contract owner sets a rate
+        let bid_msg = ExecuteMsg::Bid { auction_id: 1 };
+        let execute_info = mock_info(ADDR2, &[coin(4_000_000, "uosmo")]);
+        execute(
+            deps.as_mut(),
+            env.clone(),
+            execute_info.clone(),
+            bid_msg.clone(),
+        )
+        .unwrap_err();
+
+        let set_rate_msg = ExecuteMsg::SetRate {
+            denom: "uosmo".to_string(),
+            reference: DENOM.to_string(),
+            rate: Some(Decimal::percent(50)),
+        };
+        let err = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info(ADDR2, &[]),
+            set_rate_msg.clone(),
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::Admin(AdminError::NotAdmin {}));
+        execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info(ADDR1, &[]),
+            set_rate_msg,
+        )
+        .unwrap();
+
+        let query_msg = QueryMsg::Rate {
+            auction_id: 1,
+            denom: "uosmo".to_string(),
+        };
+        let res: RateResponse =
+            from_json(query(deps.as_ref(), env.clone(), query_msg).unwrap()).unwrap();
+        assert_eq!(res.rate, Decimal::percent(50));
+
+        // ADDR2 bids 4 OSMO, worth 2 ATOM, and pays commission in OSMO
+        let res = execute(deps.as_mut(), env.clone(), execute_info, bid_msg.clone()).unwrap();
+        let bank_msg = CosmosMsg::Bank(BankMsg::Send {
+            to_address: ADDR1.to_string(),
+            amount: vec![coin(4_000, "uosmo")],
+        });
+        assert_eq!(res.messages[0].msg, bank_msg);
+
+        let query_msg = QueryMsg::HighestBidder { auction_id: 1 };
+        let res: HighestBidderResponse =
+            from_json(query(deps.as_ref(), env.clone(), query_msg).unwrap()).unwrap();
+        assert_eq!(res.addr, ADDR2);
+        assert_eq!(res.total_bid, Uint128::from(1_998_000u128));
+
+        // ADDR3 outbids them in ATOM, and ADDR2 outbids ADDR3 back in ATOM as well
+        for (bidder, amount) in [(ADDR3, 3_000_000u128), (ADDR2, 2_000_000)] {
+            let execute_info = mock_info(bidder, &[coin(amount, DENOM)]);
+            execute(deps.as_mut(), env.clone(), execute_info, bid_msg.clone()).unwrap();
+        }
+
+        let query_msg = QueryMsg::Deposits {
+            auction_id: 1,
+            address: ADDR2.to_string(),
+        };
+        let res: DepositsResponse =
+            from_json(query(deps.as_ref(), env.clone(), query_msg).unwrap()).unwrap();
+        assert_eq!(
+            res.deposits,
+            vec![coin(1_998_000, DENOM), coin(3_996_000, "uosmo")]
+        );
+
+        // only accepted denoms can be bid
+        let execute_info = mock_info(ADDR3, &[coin(1_000_000, "ujuno")]);
+        let err = execute(deps.as_mut(), env.clone(), execute_info, bid_msg).unwrap_err();
+        assert_eq!(err, ContractError::WrongDenom {});
+
+        // and neither max bids nor partial withdrawals can work across denoms
+        let execute_info = mock_info(ADDR3, &[]);
+        let err = execute(
+            deps.as_mut(),
+            env.clone(),
+            execute_info.clone(),
+            ExecuteMsg::SetMaxBid {
+                auction_id: 1,
+                max: Uint128::from(5_000_000u128),
+            },
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::MaxBidMultiDenom {});
+        let err = execute(
+            deps.as_mut(),
+            env.clone(),
+            execute_info,
+            ExecuteMsg::Withdraw {
+                auction_id: 1,
+                amount: Some(Uint128::from(1_000_000u128)),
+                receiver: None,
+            },
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::PartialWithdrawMultiDenom {});
+
+        // the seller is paid in both denoms ADDR2 bid in
+        let execute_info = mock_info(ADDR1, &[]);
+        let res = execute(
+            deps.as_mut(),
+            env.clone(),
+            execute_info,
+            ExecuteMsg::Close { auction_id: 1 },
+        )
+        .unwrap();
+        let payments: Vec<_> = res.messages.into_iter().map(|msg| msg.msg).collect();
+        assert_eq!(
+            payments,
+            vec![
+                CosmosMsg::Bank(BankMsg::Send {
+                    to_address: ADDR1.to_string(),
+                    amount: vec![coin(1_998_000, DENOM)],
+                }),
+                CosmosMsg::Bank(BankMsg::Send {
+                    to_address: ADDR1.to_string(),
+                    amount: vec![coin(3_996_000, "uosmo")],
+                }),
+            ]
+        );
+
+        // and ADDR3 gets back the ATOM they bid
+        let execute_info = mock_info(ADDR3, &[]);
+        let res = execute(
+            deps.as_mut(),
+            env,
+            execute_info,
+            ExecuteMsg::Retract {
+                auction_id: 1,
+                receiver: None,
+            },
+        )
+        .unwrap();
+        let bank_msg = CosmosMsg::Bank(BankMsg::Send {
+            to_address: ADDR3.to_string(),
+            amount: vec![coin(2_997_000, DENOM)],
+        });
+        assert_eq!(res.messages[0].msg, bank_msg);
+    }
+
+    #[test]
+    fn test_proxy_bid_without_opening_bid() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+        let info = mock_info(ADDR1, &[coin(1_000_000, DENOM)]);
+        let msg = InstantiateMsg {
+            commodity: COMMODITY.to_string(),
+            ..InstantiateMsg::default()
+        };
+        instantiate(deps.as_mut(), env.clone(), info, msg).unwrap();
+
+        // ADDR2 escrows an nft, so the auction starts at 0 and 10% of it is nothing
+        let execute_info = mock_info("nft", &[]);
+        let deposit = ExecuteMsg::ReceiveNft(Cw721ReceiveMsg {
+            sender: ADDR2.to_string(),
+            token_id: "1".to_string(),
+            msg: to_json_binary(&DepositMsg::CreateAuction(AuctionMsg {
+                commodity: "punk".to_string(),
+                native_denom: Some(DENOM.to_string()),
+                min_increment: Some(MinIncrement::Percentage(Decimal::percent(10))),
+                ..AuctionMsg::default()
+            }))
+            .unwrap(),
+        });
+        execute(deps.as_mut(), env.clone(), execute_info, deposit).unwrap();
+
+        // a max bid of 0 cannot outbid anything
+        let set_max_bid = |max: u128| ExecuteMsg::SetMaxBid {
+            auction_id: 2,
+            max: Uint128::from(max),
+        };
+        let execute_info = mock_info(ADDR3, &[]);
+        let err = execute(deps.as_mut(), env.clone(), execute_info, set_max_bid(0)).unwrap_err();
+        assert_eq!(
+            err,
+            ContractError::BidBelowMinIncrement {
+                min_bid: Uint128::one()
+            }
+        );
+
+        // ADDR3's proxy still has to raise the bid by at least 1
+        let execute_info = mock_info(ADDR3, &[coin(2_000, DENOM)]);
+        execute(deps.as_mut(), env.clone(), execute_info, set_max_bid(1_000)).unwrap();
+
+        let query_msg = QueryMsg::HighestBidder { auction_id: 2 };
+        let res: HighestBidderResponse =
+            from_json(query(deps.as_ref(), env.clone(), query_msg).unwrap()).unwrap();
+        assert_eq!(res.addr, ADDR3);
+        assert_eq!(res.total_bid, Uint128::one());
+
+        // so the nft is paid for when it goes to ADDR3
+        let execute_info = mock_info(ADDR2, &[]);
+        let res = execute(
+            deps.as_mut(),
+            env,
+            execute_info,
+            ExecuteMsg::Close { auction_id: 2 },
+        )
+        .unwrap();
+        let expected: Vec<CosmosMsg> = vec![
+            WasmMsg::Execute {
+                contract_addr: "nft".to_string(),
+                msg: to_json_binary(&Cw721ExecuteMsg::TransferNft {
+                    recipient: ADDR3.to_string(),
+                    token_id: "1".to_string(),
+                })
+                .unwrap(),
+                funds: vec![],
+            }
+            .into(),
+            CosmosMsg::Bank(BankMsg::Send {
+                to_address: ADDR2.to_string(),
+                amount: vec![coin(1u128, DENOM)],
+            }),
+        ];
+        let msgs: Vec<CosmosMsg> = res.messages.into_iter().map(|m| m.msg).collect();
+        assert_eq!(msgs, expected);
+    }
+
+    #[test]
+    fn test_proxy_bid_removed_bidder() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+        let info = mock_info(ADDR1, &[coin(1_000_000, DENOM)]);
+        let msg = InstantiateMsg {
+            commodity: COMMODITY.to_string(),
+            allowlist: Some(vec![ADDR2.to_string(), ADDR3.to_string()]),
+            ..InstantiateMsg::default()
+        };
+        instantiate(deps.as_mut(), env.clone(), info, msg).unwrap();
+
+        // ADDR2's proxy can bid up to 5 ATOM, and outbids the seller right away
+        let execute_info = mock_info(ADDR2, &[coin(6_000_000, DENOM)]);
+        execute(
+            deps.as_mut(),
+            env.clone(),
+            execute_info,
+            ExecuteMsg::SetMaxBid {
+                auction_id: 1,
+                max: Uint128::from(5_000_000u128),
+            },
+        )
+        .unwrap();
+
+        // then ADDR2 is removed from the allowlist
+        execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info(ADDR1, &[]),
+            ExecuteMsg::UpdateBidders {
+                auction_id: 1,
+                add: vec![],
+                remove: vec![ADDR2.to_string()],
+            },
+        )
+        .unwrap();
+
+        // so their proxy does not outbid ADDR3 back
+        let execute_info = mock_info(ADDR3, &[coin(2_000_000, DENOM)]);
+        let res = execute(
+            deps.as_mut(),
+            env.clone(),
+            execute_info,
+            ExecuteMsg::Bid { auction_id: 1 },
+        )
+        .unwrap();
+        assert!(res.events.iter().all(|e| e.ty != "auction_proxy_bid"));
+
+        let query_msg = QueryMsg::HighestBidder { auction_id: 1 };
+        let res: HighestBidderResponse =
+            from_json(query(deps.as_ref(), env, query_msg).unwrap()).unwrap();
+        assert_eq!(res.addr, ADDR3);
+        assert_eq!(res.total_bid, Uint128::from(1_998_000u128));
+    }
+
+    #[test]
+    fn test_proxy_bid_used_up() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+        let info = mock_info(ADDR1, &[coin(1_000_000, DENOM)]);
+        let msg = InstantiateMsg {
+            commodity: COMMODITY.to_string(),
+            min_increment: Some(MinIncrement::Absolute(Uint128::from(100_000u128))),
+            commission: Some(Commission {
+                rate: COMMISSION_RATE,
+                tiers: vec![],
+                mode: CommissionMode::WinningBid,
+            }),
+            ..InstantiateMsg::default()
+        };
+        instantiate(deps.as_mut(), env.clone(), info, msg).unwrap();
+
+        // ADDR2 escrows exactly their max bid, ADDR3 more than theirs
+        for (bidder, max, escrow) in [
+            (ADDR2, 2_000_000u128, 2_000_000u128),
+            (ADDR3, 5_000_000, 6_000_000),
+        ] {
+            let execute_info = mock_info(bidder, &[coin(escrow, DENOM)]);
+            execute(
+                deps.as_mut(),
+                env.clone(),
+                execute_info,
+                ExecuteMsg::SetMaxBid {
+                    auction_id: 1,
+                    max: Uint128::from(max),
+                },
+            )
+            .unwrap();
+        }
+
+        let query_msg = QueryMsg::HighestBidder { auction_id: 1 };
+        let res: HighestBidderResponse =
+            from_json(query(deps.as_ref(), env.clone(), query_msg).unwrap()).unwrap();
+        assert_eq!(res.addr, ADDR3);
+        assert_eq!(res.total_bid, Uint128::from(2_100_000u128));
+
+        // ADDR2's proxy went all in and has nothing left, so it is gone
+        let proxy = |bidder: &str| (1, Addr::unchecked(bidder));
+        assert!(!proxy_bids().has(&deps.storage, proxy(ADDR2)));
+        assert!(proxy_bids().has(&deps.storage, proxy(ADDR3)));
+
+        // and ADDR2 withdraws the bid it placed
+        let execute_info = mock_info(ADDR2, &[]);
+        let res = execute(
+            deps.as_mut(),
+            env,
+            execute_info,
+            ExecuteMsg::Withdraw {
+                auction_id: 1,
+                amount: None,
+                receiver: None,
+            },
+        )
+        .unwrap();
+        let bank_msg = CosmosMsg::Bank(BankMsg::Send {
+            to_address: ADDR2.to_string(),
+            amount: vec![coin(2_000_000u128, DENOM)],
+        });
+        assert_eq!(res.messages[0].msg, bank_msg);
+    }
+
+    #[test]
+    fn test_multi_denom_commission() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+        let info = mock_info(ADDR1, &[coin(1_000_000, DENOM)]);
+        let msg = InstantiateMsg {
+            commodity: COMMODITY.to_string(),
+            accepted_denoms: Some(vec!["uosmo".to_string()]),
+            commission: Some(Commission {
+                rate: COMMISSION_RATE,
+                tiers: vec![],
+                mode: CommissionMode::WinningBid,
+            }),
+            ..InstantiateMsg::default()
+        };
+        instantiate(deps.as_mut(), env.clone(), info, msg).unwrap();
+        execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info(ADDR1, &[]),
+            ExecuteMsg::SetRate {
+                denom: "uosmo".to_string(),
+                reference: DENOM.to_string(),
+                rate: Some(Decimal::percent(50)),
+            },
+        )
+        .unwrap();
+
+        // ADDR2 bids 1.500999 ATOM worth of OSMO, and as much ATOM on top
+        for funds in [coin(3_001_998, "uosmo"), coin(1_500_999, DENOM)] {
+            let execute_info = mock_info(ADDR2, &[funds]);
+            execute(
+                deps.as_mut(),
+                env.clone(),
+                execute_info,
+                ExecuteMsg::Bid { auction_id: 1 },
+            )
+            .unwrap();
+        }
+
+        // commission is charged on each denom, and reported as the sum of what was charged
+        let execute_info = mock_info(ADDR1, &[]);
+        let res = execute(
+            deps.as_mut(),
+            env,
+            execute_info,
+            ExecuteMsg::Close { auction_id: 1 },
+        )
+        .unwrap();
+        let payments: Vec<_> = res.messages.into_iter().map(|msg| msg.msg).collect();
+        assert_eq!(
+            payments,
+            vec![
+                CosmosMsg::Bank(BankMsg::Send {
+                    to_address: ADDR1.to_string(),
+                    amount: vec![coin(1_500, DENOM)],
+                }),
+                CosmosMsg::Bank(BankMsg::Send {
+                    to_address: ADDR1.to_string(),
+                    amount: vec![coin(1_499_499, DENOM)],
+                }),
+                CosmosMsg::Bank(BankMsg::Send {
+                    to_address: ADDR1.to_string(),
+                    amount: vec![coin(3_001, "uosmo")],
+                }),
+                CosmosMsg::Bank(BankMsg::Send {
+                    to_address: ADDR1.to_string(),
+                    amount: vec![coin(2_998_997, "uosmo")],
+                }),
+            ]
+        );
+        assert_eq!(
+            res.events,
+            vec![Event::new("auction_closed")
+                .add_attribute("auction_id", "1")
+                .add_attribute("winner", ADDR2)
+                .add_attribute("gross", "3001998")
+                .add_attribute("commission", "3000")
+                .add_attribute("net", "2998998")
+                .add_attribute("denom", DENOM)]
+        );
+    }
+
+    #[test]
+    fn test_withdraw_proxy_bid() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+        let info = mock_info(ADDR1, &[coin(1_000_000, DENOM)]);
+        let msg = InstantiateMsg {
+            commodity: COMMODITY.to_string(),
+            min_increment: Some(MinIncrement::Absolute(Uint128::from(100_000u128))),
+            commission: Some(Commission {
+                rate: COMMISSION_RATE,
+                tiers: vec![],
+                mode: CommissionMode::WinningBid,
+            }),
+            ..InstantiateMsg::default()
+        };
+        instantiate(deps.as_mut(), env.clone(), info, msg).unwrap();
+
+        // ADDR2's proxy stops at 2 ATOM with 1 ATOM of escrow left, ADDR3's proxy outbids it
+        for (bidder, max, escrow) in [
+            (ADDR2, 2_000_000u128, 3_000_000u128),
+            (ADDR3, 5_000_000, 6_000_000),
+        ] {
+            let execute_info = mock_info(bidder, &[coin(escrow, DENOM)]);
+            execute(
+                deps.as_mut(),
+                env.clone(),
+                execute_info,
+                ExecuteMsg::SetMaxBid {
+                    auction_id: 1,
+                    max: Uint128::from(max),
+                },
+            )
+            .unwrap();
+        }
+
+        // a partial withdrawal would leave the proxy short of its max bid
+        let execute_info = mock_info(ADDR2, &[]);
+        let withdraw_msg = |amount: Option<u128>| ExecuteMsg::Withdraw {
+            auction_id: 1,
+            amount: amount.map(Uint128::from),
+            receiver: None,
+        };
+        let err = execute(
+            deps.as_mut(),
+            env.clone(),
+            execute_info.clone(),
+            withdraw_msg(Some(1_000_000)),
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::PartialWithdrawProxy {});
+
+        // withdrawing everything also returns the escrow
+        let res = execute(deps.as_mut(), env, execute_info, withdraw_msg(None)).unwrap();
+        let bank_msg = CosmosMsg::Bank(BankMsg::Send {
+            to_address: ADDR2.to_string(),
+            amount: vec![coin(3_000_000u128, DENOM)],
+        });
+        assert_eq!(res.messages[0].msg, bank_msg);
+        assert!(!proxy_bids().has(&deps.storage, (1, Addr::unchecked(ADDR2))));
+    }
+
+    #[test]
+    fn test_outstanding_refunds_unrevealed() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+        let info = mock_info(ADDR1, &[coin(1_000_000, DENOM)]);
+        let msg = InstantiateMsg {
+            commodity: COMMODITY.to_string(),
+            ..InstantiateMsg::default()
+        };
+        instantiate(deps.as_mut(), env.clone(), info, msg).unwrap();
+
+        // ADDR2 sells silver in a sealed auction giving unrevealed deposits back to the bidders
+        let create_msg = ExecuteMsg::CreateAuction(AuctionMsg {
+            commodity: "silver".to_string(),
+            duration: Some(Duration::Time(100)),
+            sealed: Some(SealedBidMsg {
+                reveal_period: Duration::Time(100),
+                pricing: Pricing::FirstPrice,
+                forfeit: Forfeit::Bidder,
+            }),
+            ..AuctionMsg::default()
+        });
+        let execute_info = mock_info(ADDR2, &[coin(1_000_000, DENOM)]);
+        execute(deps.as_mut(), env.clone(), execute_info, create_msg).unwrap();
+
+        // ADDR3 commits to 2 ATOM and reveals it, ADDR4 never reveals
+        for (bidder, deposit) in [(ADDR3, 3_000_000u128), (ADDR4, 1_500_000)] {
+            let execute_info = mock_info(bidder, &[coin(deposit, DENOM)]);
+            let commitment = bid_commitment(
+                2,
+                &Addr::unchecked(bidder),
+                Uint128::from(deposit - 1_000_000),
+                "salt",
+            );
+            execute(
+                deps.as_mut(),
+                env.clone(),
+                execute_info,
+                ExecuteMsg::Commit {
+                    auction_id: 2,
+                    commitment,
+                },
+            )
+            .unwrap();
+        }
+        let mut reveal_env = env.clone();
+        reveal_env.block.time = env.block.time.plus_seconds(150);
+        let execute_info = mock_info(ADDR3, &[]);
+        execute(
+            deps.as_mut(),
+            reveal_env,
+            execute_info,
+            ExecuteMsg::Reveal {
+                auction_id: 2,
+                amount: Uint128::from(2_000_000u128),
+                salt: "salt".to_string(),
+            },
+        )
+        .unwrap();
+
+        let mut closed_env = env.clone();
+        closed_env.block.time = env.block.time.plus_seconds(250);
+        let execute_info = mock_info(ADDR4, &[]);
+        execute(
+            deps.as_mut(),
+            closed_env.clone(),
+            execute_info,
+            ExecuteMsg::Finalize { auction_id: 2 },
+        )
+        .unwrap();
+
+        // the winner has the rest of their deposit left, ADDR4 their whole unrevealed deposit
+        let query_msg = QueryMsg::OutstandingRefunds {
+            auction_id: 2,
+            start_after: None,
+            limit: None,
+        };
+        let res: OutstandingRefundsResponse =
+            from_json(query(deps.as_ref(), closed_env, query_msg).unwrap()).unwrap();
+        let refunds: Vec<_> = res
+            .refunds
+            .iter()
+            .map(|refund| (refund.bidder.as_str(), refund.amount.u128()))
+            .collect();
+        assert_eq!(
+            refunds,
+            vec![(ADDR2, 1_000_000), (ADDR3, 1_000_000), (ADDR4, 1_500_000)]
+        );
     }
 }
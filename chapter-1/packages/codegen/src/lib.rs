@@ -0,0 +1,210 @@
+/*!
+Generates typed clients for contracts from the JSON schemas of their messages.
+
+Every variant of an execute message gets a builder, and every query, tied to its response
+by `QueryResponses`, a function returning that response, so frontends do not have to
+duplicate the message types by hand.
+
+```ignore
+Client::new(env!("CARGO_PKG_NAME"))
+    .message::<ExecuteMsg>("ExecuteMsg")
+    .query::<QueryMsg>("QueryMsg")
+    .write_if_requested(&out_dir)
+    .unwrap();
+```
+*/
+
+mod typescript;
+
+use std::collections::BTreeMap;
+use std::env::args;
+use std::fs::write;
+use std::io;
+use std::path::Path;
+
+use cosmwasm_schema::QueryResponses;
+use schemars::schema::{RootSchema, Schema};
+use schemars::{schema_for, JsonSchema};
+
+/// Messages and queries of a contract, rendered as a TypeScript client.
+pub struct Client {
+    name: String,
+    // every named type, messages and responses included
+    definitions: BTreeMap<String, Schema>,
+    messages: Vec<String>,
+    query: Option<Query>,
+}
+
+struct Query {
+    title: String,
+    // response type name of each query, by query name
+    responses: BTreeMap<String, String>,
+}
+
+impl Client {
+    /// `name` is the contract name, usually the package name
+    pub fn new(name: &str) -> Self {
+        Client {
+            name: name.to_string(),
+            definitions: BTreeMap::new(),
+            messages: vec![],
+            query: None,
+        }
+    }
+
+    /// Adds a message type, with a builder for each variant if it is an enum
+    pub fn message<M: JsonSchema>(mut self, title: &str) -> Self {
+        self.define(title, schema_for!(M));
+        self.messages.push(title.to_string());
+        self
+    }
+
+    /// Adds the query message, with a function per query returning its response type.
+    ///
+    /// Panics if the queries do not match their responses, as `write_api!` does.
+    pub fn query<Q: QueryResponses>(mut self, title: &str) -> Self {
+        let responses = Q::response_schemas()
+            .unwrap_or_else(|err| panic!("Invalid responses for {}: {}", title, err));
+        self.define(title, schema_for!(Q));
+
+        let responses = responses
+            .into_iter()
+            .map(|(query, response)| {
+                let name = response
+                    .schema
+                    .metadata
+                    .as_ref()
+                    .and_then(|metadata| metadata.title.clone())
+                    .unwrap_or_else(|| format!("{}Response", typescript::pascal_case(&query)));
+                self.define(&name, response);
+                (query, name)
+            })
+            .collect();
+        self.query = Some(Query {
+            title: title.to_string(),
+            responses,
+        });
+        self
+    }
+
+    fn define(&mut self, title: &str, root: RootSchema) {
+        self.definitions.extend(root.definitions);
+        self.definitions
+            .insert(title.to_string(), Schema::Object(root.schema));
+    }
+
+    /// Renders the client as a TypeScript module
+    pub fn typescript(&self) -> String {
+        typescript::render(self)
+    }
+
+    /// Writes the TypeScript client to `<name>.client.ts` under `out_dir`
+    pub fn write(&self, out_dir: &Path) -> io::Result<()> {
+        let path = out_dir.join(format!("{}.client.ts", self.name));
+        write(&path, self.typescript())?;
+        println!("Created {}", path.display());
+        Ok(())
+    }
+
+    /// Writes the TypeScript client like `write`, if the binary was run with `--client`
+    pub fn write_if_requested(&self, out_dir: &Path) -> io::Result<()> {
+        if args().any(|arg| arg == "--client") {
+            self.write(out_dir)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use cosmwasm_schema::{cw_serde, QueryResponses};
+
+    #[cw_serde]
+    enum ExecuteMsg {
+        /// Bids the sent funds
+        Bid {
+            auction_id: u64,
+        },
+        Close {},
+        Pause,
+    }
+
+    #[cw_serde]
+    #[derive(QueryResponses)]
+    enum QueryMsg {
+        #[returns(TotalBidResponse)]
+        TotalBid {
+            auction_id: u64,
+            address: Option<String>,
+        },
+        #[returns(Vec<String>)]
+        Bidders {},
+    }
+
+    #[cw_serde]
+    struct TotalBidResponse {
+        /// Sum of every bid
+        total_bid: String,
+    }
+
+    fn client() -> String {
+        Client::new("bidding-platform")
+            .message::<ExecuteMsg>("ExecuteMsg")
+            .query::<QueryMsg>("QueryMsg")
+            .typescript()
+    }
+
+    #[test]
+    fn message_builders() {
+        let client = client();
+        assert!(client.contains("export const ExecuteMsg = {\n"));
+        assert!(client.contains(
+            "  /**\n   * Bids the sent funds\n   */\n  bid: (args: {\n    auction_id: number;\n  }): ExecuteMsg => ({ bid: args }),\n"
+        ));
+        assert!(client.contains("  close: (): ExecuteMsg => ({ close: {} }),\n"));
+        assert!(client.contains("  pause: (): ExecuteMsg => \"pause\",\n"));
+    }
+
+    #[test]
+    fn query_functions() {
+        let client = client();
+        assert!(client.contains("export type SmartQuery = <T>(msg: QueryMsg) => Promise<T>;\n"));
+        assert!(
+            client.contains("export const biddingPlatformQueries = (query: SmartQuery) => ({\n")
+        );
+        assert!(client.contains(
+            "  totalBid: (args: {\n    address?: string | null;\n    auction_id: number;\n  }): Promise<TotalBidResponse> =>\n    query<TotalBidResponse>({ total_bid: args }),\n"
+        ));
+        assert!(client.contains(
+            "  bidders: (): Promise<Array_of_String> =>\n    query<Array_of_String>({ bidders: {} }),\n"
+        ));
+    }
+
+    #[test]
+    fn response_types() {
+        let client = client();
+        assert!(client.contains("export type Array_of_String = string[];\n"));
+        assert!(client.contains(
+            "export type TotalBidResponse = {\n  /**\n   * Sum of every bid\n   */\n  total_bid: string;\n};\n"
+        ));
+    }
+
+    #[test]
+    #[should_panic(expected = "Invalid responses for QueryMsg")]
+    fn inconsistent_responses() {
+        #[cw_serde]
+        enum QueryMsg {
+            Bidders {},
+        }
+
+        impl QueryResponses for QueryMsg {
+            fn response_schemas_impl() -> BTreeMap<String, RootSchema> {
+                BTreeMap::new()
+            }
+        }
+
+        Client::new("bidding-platform").query::<QueryMsg>("QueryMsg");
+    }
+}
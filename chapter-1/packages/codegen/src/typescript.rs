@@ -0,0 +1,319 @@
+use schemars::schema::{InstanceType, ObjectValidation, Schema, SchemaObject, SingleOrVec};
+
+use crate::Client;
+
+const INDENT: &str = "  ";
+
+pub fn render(client: &Client) -> String {
+    let mut out = format!(
+        "// Generated from the {} schemas, do not edit.\n",
+        client.name
+    );
+
+    for (name, schema) in &client.definitions {
+        out.push('\n');
+        out.push_str(&doc(description(schema), 0));
+        out.push_str(&format!("export type {} = {};\n", name, type_of(schema, 0)));
+    }
+
+    for title in &client.messages {
+        let variants = variants(&client.definitions[title]);
+        if variants.is_empty() {
+            continue;
+        }
+        out.push_str(&format!("\nexport const {} = {{\n", title));
+        for variant in variants {
+            out.push_str(&doc(variant.description, 1));
+            let builder = match variant.args {
+                None => format!("(): {} => \"{}\"", title, variant.name),
+                Some(args) if is_empty(args) => {
+                    format!("(): {} => ({{ {}: {{}} }})", title, variant.name)
+                }
+                Some(args) => format!(
+                    "(args: {}): {} => ({{ {}: args }})",
+                    type_of(args, 1),
+                    title,
+                    variant.name
+                ),
+            };
+            out.push_str(&format!(
+                "{}{}: {},\n",
+                INDENT,
+                camel_case(&variant.name),
+                builder
+            ));
+        }
+        out.push_str("};\n");
+    }
+
+    if let Some(query) = &client.query {
+        out.push_str(&format!(
+            "\n/**\n * Sends a smart query to the contract, e.g. with `CosmWasmClient.queryContractSmart`\n */\nexport type SmartQuery = <T>(msg: {}) => Promise<T>;\n",
+            query.title
+        ));
+        out.push_str(&format!(
+            "\nexport const {}Queries = (query: SmartQuery) => ({{\n",
+            camel_case(&client.name)
+        ));
+        for variant in variants(&client.definitions[&query.title]) {
+            let response = &query.responses[&variant.name];
+            let (params, msg) = match variant.args {
+                None => (String::new(), format!("\"{}\"", variant.name)),
+                Some(args) if is_empty(args) => {
+                    (String::new(), format!("{{ {}: {{}} }}", variant.name))
+                }
+                Some(args) => (
+                    format!("args: {}", type_of(args, 1)),
+                    format!("{{ {}: args }}", variant.name),
+                ),
+            };
+            out.push_str(&doc(variant.description, 1));
+            out.push_str(&format!(
+                "{}{}: ({}): Promise<{}> =>\n{}{}query<{}>({}),\n",
+                INDENT,
+                camel_case(&variant.name),
+                params,
+                response,
+                INDENT,
+                INDENT,
+                response,
+                msg
+            ));
+        }
+        out.push_str("});\n");
+    }
+
+    out
+}
+
+// TypeScript type of a schema, referring to definitions by name
+fn type_of(schema: &Schema, indent: usize) -> String {
+    let schema = match schema {
+        Schema::Bool(true) => return "unknown".to_string(),
+        Schema::Bool(false) => return "never".to_string(),
+        Schema::Object(schema) => schema,
+    };
+
+    if let Some(reference) = &schema.reference {
+        return reference.trim_start_matches("#/definitions/").to_string();
+    }
+    if let Some(values) = &schema.enum_values {
+        return union(values.iter().map(|value| value.to_string()));
+    }
+    if let Some(subschemas) = &schema.subschemas {
+        if let Some(all_of) = &subschemas.all_of {
+            return all_of
+                .iter()
+                .map(|schema| type_of(schema, indent))
+                .collect::<Vec<_>>()
+                .join(" & ");
+        }
+        if let Some(one_of) = subschemas.any_of.as_ref().or(subschemas.one_of.as_ref()) {
+            return union(one_of.iter().map(|schema| type_of(schema, indent)));
+        }
+    }
+
+    match &schema.instance_type {
+        None => "unknown".to_string(),
+        Some(SingleOrVec::Single(instance)) => instance_type(schema, instance, indent),
+        Some(SingleOrVec::Vec(instances)) => union(
+            instances
+                .iter()
+                .map(|instance| instance_type(schema, instance, indent)),
+        ),
+    }
+}
+
+fn instance_type(schema: &SchemaObject, instance: &InstanceType, indent: usize) -> String {
+    match instance {
+        InstanceType::Null => "null".to_string(),
+        InstanceType::Boolean => "boolean".to_string(),
+        InstanceType::Integer | InstanceType::Number => "number".to_string(),
+        InstanceType::String => "string".to_string(),
+        InstanceType::Array => match schema.array.as_ref().and_then(|array| array.items.as_ref()) {
+            Some(SingleOrVec::Single(item)) => {
+                let item = type_of(item, indent);
+                if item.contains(' ') {
+                    format!("({})[]", item)
+                } else {
+                    format!("{}[]", item)
+                }
+            }
+            Some(SingleOrVec::Vec(items)) => format!(
+                "[{}]",
+                items
+                    .iter()
+                    .map(|item| type_of(item, indent))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+            None => "unknown[]".to_string(),
+        },
+        InstanceType::Object => match &schema.object {
+            Some(object) => object_type(object, indent),
+            None => "Record<string, unknown>".to_string(),
+        },
+    }
+}
+
+fn object_type(object: &ObjectValidation, indent: usize) -> String {
+    if object.properties.is_empty() {
+        return match &object.additional_properties {
+            Some(values) if matches!(**values, Schema::Object(_)) => {
+                format!("Record<string, {}>", type_of(values, indent))
+            }
+            _ => "{}".to_string(),
+        };
+    }
+
+    let mut out = "{\n".to_string();
+    for (name, schema) in &object.properties {
+        let optional = if object.required.contains(name) {
+            ""
+        } else {
+            "?"
+        };
+        out.push_str(&doc(description(schema), indent + 1));
+        out.push_str(&format!(
+            "{}{}{}: {};\n",
+            INDENT.repeat(indent + 1),
+            name,
+            optional,
+            type_of(schema, indent + 1)
+        ));
+    }
+    out.push_str(&INDENT.repeat(indent));
+    out.push('}');
+    out
+}
+
+fn union(types: impl Iterator<Item = String>) -> String {
+    let mut union: Vec<String> = vec![];
+    for ty in types {
+        if !union.contains(&ty) {
+            union.push(ty);
+        }
+    }
+    union.join(" | ")
+}
+
+fn is_empty(schema: &Schema) -> bool {
+    match schema {
+        Schema::Object(schema) => {
+            schema.reference.is_none()
+                && schema
+                    .object
+                    .as_ref()
+                    .map_or(true, |object| object.properties.is_empty())
+        }
+        Schema::Bool(_) => false,
+    }
+}
+
+// a variant of an externally tagged enum, without arguments if it is a unit variant
+struct Variant<'a> {
+    name: String,
+    args: Option<&'a Schema>,
+    description: Option<&'a str>,
+}
+
+fn variants(schema: &Schema) -> Vec<Variant<'_>> {
+    let schema = match schema {
+        Schema::Object(schema) => schema,
+        Schema::Bool(_) => return vec![],
+    };
+    let one_of = schema
+        .subschemas
+        .as_ref()
+        .and_then(|subschemas| subschemas.one_of.as_ref());
+    match one_of {
+        Some(one_of) => one_of
+            .iter()
+            .flat_map(|variant| match variant {
+                Schema::Object(variant) => variants_of(variant, description_of(variant)),
+                Schema::Bool(_) => vec![],
+            })
+            .collect(),
+        // enums with unit variants only are plain string enums
+        None if schema.enum_values.is_some() => variants_of(schema, None),
+        None => vec![],
+    }
+}
+
+fn variants_of<'a>(schema: &'a SchemaObject, description: Option<&'a str>) -> Vec<Variant<'a>> {
+    // unit variants are serialized as strings
+    if let Some(values) = &schema.enum_values {
+        return values
+            .iter()
+            .filter_map(|value| value.as_str())
+            .map(|name| Variant {
+                name: name.to_string(),
+                args: None,
+                description,
+            })
+            .collect();
+    }
+
+    match &schema.object {
+        Some(object) if object.properties.len() == 1 => object
+            .properties
+            .iter()
+            .map(|(name, args)| Variant {
+                name: name.clone(),
+                args: Some(args),
+                description,
+            })
+            .collect(),
+        _ => vec![],
+    }
+}
+
+fn description(schema: &Schema) -> Option<&str> {
+    match schema {
+        Schema::Object(schema) => description_of(schema),
+        Schema::Bool(_) => None,
+    }
+}
+
+fn description_of(schema: &SchemaObject) -> Option<&str> {
+    schema
+        .metadata
+        .as_ref()
+        .and_then(|metadata| metadata.description.as_deref())
+}
+
+fn doc(description: Option<&str>, indent: usize) -> String {
+    let indent = INDENT.repeat(indent);
+    match description {
+        Some(description) => {
+            let mut out = format!("{}/**\n", indent);
+            for line in description.lines() {
+                out.push_str(&format!("{} * {}\n", indent, line).replace(" * \n", " *\n"));
+            }
+            out.push_str(&format!("{} */\n", indent));
+            out
+        }
+        None => String::new(),
+    }
+}
+
+pub fn pascal_case(name: &str) -> String {
+    name.split(['_', '-'])
+        .map(|word| {
+            let mut chars = word.chars();
+            match chars.next() {
+                Some(first) => first.to_uppercase().chain(chars).collect(),
+                None => String::new(),
+            }
+        })
+        .collect()
+}
+
+pub fn camel_case(name: &str) -> String {
+    let pascal = pascal_case(name);
+    let mut chars = pascal.chars();
+    match chars.next() {
+        Some(first) => first.to_lowercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
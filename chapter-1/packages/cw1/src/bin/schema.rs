@@ -14,4 +14,12 @@ fn main() {
     export_schema_with_title(&schema_for!(Cw1ExecuteMsg), &out_dir, "ExecuteMsg");
     export_schema_with_title(&schema_for!(Cw1QueryMsg), &out_dir, "QueryMsg");
     export_schema(&schema_for!(CanExecuteResponse), &out_dir);
+
+    // `cargo schema --features cw-codegen -- --client` also writes a typed TypeScript client
+    #[cfg(feature = "cw-codegen")]
+    cw_codegen::Client::new(env!("CARGO_PKG_NAME"))
+        .message::<Cw1ExecuteMsg>("ExecuteMsg")
+        .query::<Cw1QueryMsg>("QueryMsg")
+        .write_if_requested(&out_dir)
+        .unwrap();
 }
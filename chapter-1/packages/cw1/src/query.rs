@@ -1,7 +1,9 @@
+use schemars::schema::RootSchema;
 use schemars::JsonSchema;
+use std::collections::BTreeMap;
 use std::fmt;
 
-use cosmwasm_schema::cw_serde;
+use cosmwasm_schema::{cw_serde, schema_for, QueryResponses};
 use cosmwasm_std::{CosmosMsg, Empty};
 
 #[cw_serde]
@@ -15,6 +17,16 @@ where
     CanExecute { sender: String, msg: CosmosMsg<T> },
 }
 
+// implemented by hand as the derive does not support generic messages
+impl<T> QueryResponses for Cw1QueryMsg<T>
+where
+    T: Clone + fmt::Debug + PartialEq + JsonSchema,
+{
+    fn response_schemas_impl() -> BTreeMap<String, RootSchema> {
+        BTreeMap::from([("can_execute".to_string(), schema_for!(CanExecuteResponse))])
+    }
+}
+
 #[cw_serde]
 pub struct CanExecuteResponse {
     pub can_execute: bool,
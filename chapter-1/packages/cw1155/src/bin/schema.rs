@@ -19,4 +19,14 @@ fn main() {
     export_schema(&schema_for!(cw1155::IsApprovedForAllResponse), &out_dir);
     export_schema(&schema_for!(cw1155::TokenInfoResponse), &out_dir);
     export_schema(&schema_for!(cw1155::TokensResponse), &out_dir);
+
+    // `cargo schema --features cw-codegen -- --client` also writes a typed TypeScript client
+    #[cfg(feature = "cw-codegen")]
+    cw_codegen::Client::new(env!("CARGO_PKG_NAME"))
+        .message::<cw1155::Cw1155ExecuteMsg>("Cw1155ExecuteMsg")
+        .query::<cw1155::Cw1155QueryMsg>("Cw1155QueryMsg")
+        .message::<cw1155::Cw1155ReceiveMsg>("Cw1155ReceiveMsg")
+        .message::<cw1155::Cw1155BatchReceiveMsg>("Cw1155BatchReceiveMsg")
+        .write_if_requested(&out_dir)
+        .unwrap();
 }
@@ -26,4 +26,13 @@ fn main() {
     export_schema(&schema_for!(MarketingInfoResponse), &out_dir);
     export_schema(&schema_for!(AllAllowancesResponse), &out_dir);
     export_schema(&schema_for!(AllAccountsResponse), &out_dir);
+
+    // `cargo schema --features cw-codegen -- --client` also writes a typed TypeScript client
+    #[cfg(feature = "cw-codegen")]
+    cw_codegen::Client::new(env!("CARGO_PKG_NAME"))
+        .message::<Cw20ExecuteMsg>("Cw20ExecuteMsg")
+        .query::<Cw20QueryMsg>("Cw20QueryMsg")
+        .message::<Cw20ReceiveMsg>("Cw20ReceiveMsg")
+        .write_if_requested(&out_dir)
+        .unwrap();
 }
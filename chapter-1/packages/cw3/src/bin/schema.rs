@@ -25,4 +25,12 @@ fn main() {
     export_schema(&schema_for!(VoterDetail), &out_dir);
     export_schema(&schema_for!(VoterListResponse), &out_dir);
     export_schema(&schema_for!(ThresholdResponse), &out_dir);
+
+    // `cargo schema --features cw-codegen -- --client` also writes a typed TypeScript client
+    #[cfg(feature = "cw-codegen")]
+    cw_codegen::Client::new(env!("CARGO_PKG_NAME"))
+        .message::<Cw3ExecuteMsg>("ExecuteMsg")
+        .query::<Cw3QueryMsg>("QueryMsg")
+        .write_if_requested(&out_dir)
+        .unwrap();
 }
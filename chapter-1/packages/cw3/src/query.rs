@@ -2,28 +2,33 @@ use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use std::fmt;
 
-use cosmwasm_schema::cw_serde;
+use cosmwasm_schema::{cw_serde, QueryResponses};
 use cosmwasm_std::{CosmosMsg, Empty};
 use cw_utils::{Expiration, ThresholdResponse};
 
 use crate::msg::Vote;
 
 #[cw_serde]
+#[derive(QueryResponses)]
 pub enum Cw3QueryMsg {
     /// Returns the threshold rules that would be used for a new proposal that was
     /// opened right now. The threshold rules do not change often, but the `total_weight`
     /// in the response may easily differ from that used in previously opened proposals.
     /// Returns ThresholdResponse.
+    #[returns(ThresholdResponse)]
     Threshold {},
     /// Returns details of the proposal state. Returns ProposalResponse.
+    #[returns(ProposalResponse)]
     Proposal { proposal_id: u64 },
     /// Iterate over details of all proposals from oldest to newest. Returns ProposalListResponse
+    #[returns(ProposalListResponse)]
     ListProposals {
         start_after: Option<u64>,
         limit: Option<u32>,
     },
     /// Iterate reverse over details of all proposals, this is useful to easily query
     /// only the most recent proposals (to get updates). Returns ProposalListResponse
+    #[returns(ProposalListResponse)]
     ReverseProposals {
         start_before: Option<u64>,
         limit: Option<u32>,
@@ -31,18 +36,22 @@ pub enum Cw3QueryMsg {
     /// Query the vote made by the given voter on `proposal_id`. This should
     /// return an error if there is no such proposal. It will return a None value
     /// if the proposal exists but the voter did not vote. Returns VoteResponse
+    #[returns(VoteResponse)]
     Vote { proposal_id: u64, voter: String },
     /// Iterate (with pagination) over all votes for this proposal. The ordering is arbitrary,
     /// unlikely to be sorted by address. But ordering is consistent and pagination from the end
     /// of each page will cover all votes for the proposal. Returns VoteListResponse
+    #[returns(VoteListResponse)]
     ListVotes {
         proposal_id: u64,
         start_after: Option<String>,
         limit: Option<u32>,
     },
     /// Voter extension: Returns VoterResponse
+    #[returns(VoterResponse)]
     Voter { address: String },
     /// ListVoters extension: Returns VoterListResponse
+    #[returns(VoterListResponse)]
     ListVoters {
         start_after: Option<String>,
         limit: Option<u32>,
@@ -21,4 +21,13 @@ fn main() {
     export_schema(&schema_for!(MemberResponse), &out_dir);
     export_schema(&schema_for!(TotalWeightResponse), &out_dir);
     export_schema(&schema_for!(MemberChangedHookMsg), &out_dir);
+
+    // `cargo schema --features cw-codegen -- --client` also writes a typed TypeScript client
+    #[cfg(feature = "cw-codegen")]
+    cw_codegen::Client::new(env!("CARGO_PKG_NAME"))
+        .message::<Cw4ExecuteMsg>("Cw4ExecuteMsg")
+        .query::<Cw4QueryMsg>("Cw4QueryMsg")
+        .message::<MemberChangedHookMsg>("MemberChangedHookMsg")
+        .write_if_requested(&out_dir)
+        .unwrap();
 }
@@ -1,23 +1,28 @@
-use cosmwasm_schema::cw_serde;
+use cosmwasm_schema::{cw_serde, QueryResponses};
 
 #[cw_serde]
-
+#[derive(QueryResponses)]
 pub enum Cw4QueryMsg {
     /// Return AdminResponse
+    #[returns(AdminResponse)]
     Admin {},
     /// Return TotalWeightResponse
+    #[returns(TotalWeightResponse)]
     TotalWeight { at_height: Option<u64> },
     /// Returns MembersListResponse
+    #[returns(MemberListResponse)]
     ListMembers {
         start_after: Option<String>,
         limit: Option<u32>,
     },
     /// Returns MemberResponse
+    #[returns(MemberResponse)]
     Member {
         addr: String,
         at_height: Option<u64>,
     },
     /// Shows all registered hooks. Returns HooksResponse.
+    #[returns(HooksResponse)]
     Hooks {},
 }
 
@@ -0,0 +1,152 @@
+// Generated from the cw1 schemas, do not edit.
+
+/**
+ * The message types of the bank module.
+ *
+ * See https://github.com/cosmos/cosmos-sdk/blob/v0.40.0/proto/cosmos/bank/v1beta1/tx.proto
+ */
+export type BankMsg = {
+  send: {
+    amount: Coin[];
+    to_address: string;
+  };
+} | {
+  burn: {
+    amount: Coin[];
+  };
+};
+
+/**
+ * Binary is a wrapper around Vec<u8> to add base64 de/serialization with serde. It also adds some helper methods to help encode inline.
+ *
+ * This is only needed as serde-json-{core,wasm} has a horrible encoding for Vec<u8>
+ */
+export type Binary = string;
+
+export type CanExecuteResponse = {
+  can_execute: boolean;
+};
+
+export type Coin = {
+  amount: Uint128;
+  denom: string;
+};
+
+export type CosmosMsg_for_Empty = {
+  bank: BankMsg;
+} | {
+  custom: Empty;
+} | {
+  wasm: WasmMsg;
+};
+
+/**
+ * An empty struct that serves as a placeholder in different places, such as contracts that don't set a custom message.
+ *
+ * It is designed to be expressable in correct JSON and JSON Schema but contains no meaningful data. Previously we used enums without cases, but those cannot represented as valid JSON Schema (https://github.com/CosmWasm/cosmwasm/issues/451)
+ */
+export type Empty = {};
+
+export type ExecuteMsg = {
+  execute: {
+    msgs: CosmosMsg_for_Empty[];
+  };
+};
+
+export type QueryMsg = {
+  can_execute: {
+    msg: CosmosMsg_for_Empty;
+    sender: string;
+  };
+};
+
+/**
+ * A thin wrapper around u128 that is using strings for JSON encoding/decoding, such that the full u128 range can be used for clients that convert JSON numbers to floats, like JavaScript and jq.
+ *
+ * # Examples
+ *
+ * Use `from` to create instances of this and `u128` to get the value out:
+ *
+ * ``` # use cosmwasm_std::Uint128; let a = Uint128::from(123u128); assert_eq!(a.u128(), 123);
+ *
+ * let b = Uint128::from(42u64); assert_eq!(b.u128(), 42);
+ *
+ * let c = Uint128::from(70u32); assert_eq!(c.u128(), 70); ```
+ */
+export type Uint128 = string;
+
+/**
+ * The message types of the wasm module.
+ *
+ * See https://github.com/CosmWasm/wasmd/blob/v0.14.0/x/wasm/internal/types/tx.proto
+ */
+export type WasmMsg = {
+  execute: {
+    contract_addr: string;
+    funds: Coin[];
+    /**
+     * msg is the json-encoded ExecuteMsg struct (as raw Binary)
+     */
+    msg: Binary;
+  };
+} | {
+  instantiate: {
+    admin?: string | null;
+    code_id: number;
+    funds: Coin[];
+    /**
+     * A human-readbale label for the contract
+     */
+    label: string;
+    /**
+     * msg is the JSON-encoded InstantiateMsg struct (as raw Binary)
+     */
+    msg: Binary;
+  };
+} | {
+  migrate: {
+    contract_addr: string;
+    /**
+     * msg is the json-encoded MigrateMsg struct that will be passed to the new code
+     */
+    msg: Binary;
+    /**
+     * the code_id of the new logic to place in the given contract
+     */
+    new_code_id: number;
+  };
+} | {
+  update_admin: {
+    admin: string;
+    contract_addr: string;
+  };
+} | {
+  clear_admin: {
+    contract_addr: string;
+  };
+};
+
+export const ExecuteMsg = {
+  /**
+   * Execute requests the contract to re-dispatch all these messages with the contract's address as sender. Every implementation has it's own logic to determine in
+   */
+  execute: (args: {
+    msgs: CosmosMsg_for_Empty[];
+  }): ExecuteMsg => ({ execute: args }),
+};
+
+/**
+ * Sends a smart query to the contract, e.g. with `CosmWasmClient.queryContractSmart`
+ */
+export type SmartQuery = <T>(msg: QueryMsg) => Promise<T>;
+
+export const cw1Queries = (query: SmartQuery) => ({
+  /**
+   * Checks permissions of the caller on this proxy. If CanExecute returns true then a call to `Execute` with the same message, from the given sender, before any further state changes, should also succeed.
+   */
+  canExecute: (args: {
+    msg: CosmosMsg_for_Empty;
+    sender: string;
+  }): Promise<CanExecuteResponse> =>
+    query<CanExecuteResponse>({ can_execute: args }),
+});
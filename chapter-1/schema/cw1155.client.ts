@@ -0,0 +1,404 @@
+// Generated from the cw1155 schemas, do not edit.
+
+export type Approval = {
+  /**
+   * When the Approval expires (maybe Expiration::never)
+   */
+  expires: Expiration;
+  /**
+   * Account that can transfer/send the token
+   */
+  spender: string;
+};
+
+export type ApprovedForAllResponse = {
+  operators: Approval[];
+};
+
+export type BalanceResponse = {
+  balance: Uint128;
+};
+
+export type BatchBalanceResponse = {
+  balances: Uint128[];
+};
+
+/**
+ * Binary is a wrapper around Vec<u8> to add base64 de/serialization with serde. It also adds some helper methods to help encode inline.
+ *
+ * This is only needed as serde-json-{core,wasm} has a horrible encoding for Vec<u8>
+ */
+export type Binary = string;
+
+/**
+ * Cw1155BatchReceiveMsg should be de/serialized under `BatchReceive()` variant in a ExecuteMsg
+ */
+export type Cw1155BatchReceiveMsg = {
+  batch: ([string, Uint128])[];
+  from?: string | null;
+  msg: Binary;
+  operator: string;
+};
+
+export type Cw1155ExecuteMsg = {
+  send_from: {
+    from: string;
+    /**
+     * `None` means don't call the receiver interface
+     */
+    msg?: Binary | null;
+    /**
+     * If `to` is not contract, `msg` should be `None`
+     */
+    to: string;
+    token_id: string;
+    value: Uint128;
+  };
+} | {
+  batch_send_from: {
+    batch: ([string, Uint128])[];
+    from: string;
+    /**
+     * `None` means don't call the receiver interface
+     */
+    msg?: Binary | null;
+    /**
+     * if `to` is not contract, `msg` should be `None`
+     */
+    to: string;
+  };
+} | {
+  mint: {
+    /**
+     * `None` means don't call the receiver interface
+     */
+    msg?: Binary | null;
+    /**
+     * If `to` is not contract, `msg` should be `None`
+     */
+    to: string;
+    token_id: string;
+    value: Uint128;
+  };
+} | {
+  batch_mint: {
+    batch: ([string, Uint128])[];
+    /**
+     * `None` means don't call the receiver interface
+     */
+    msg?: Binary | null;
+    /**
+     * If `to` is not contract, `msg` should be `None`
+     */
+    to: string;
+  };
+} | {
+  burn: {
+    from: string;
+    token_id: string;
+    value: Uint128;
+  };
+} | {
+  batch_burn: {
+    batch: ([string, Uint128])[];
+    from: string;
+  };
+} | {
+  approve_all: {
+    expires?: Expiration | null;
+    operator: string;
+  };
+} | {
+  revoke_all: {
+    operator: string;
+  };
+};
+
+export type Cw1155QueryMsg = {
+  balance: {
+    owner: string;
+    token_id: string;
+  };
+} | {
+  batch_balance: {
+    owner: string;
+    token_ids: string[];
+  };
+} | {
+  approved_for_all: {
+    /**
+     * unset or false will filter out expired approvals, you must set to true to see them
+     */
+    include_expired?: boolean | null;
+    limit?: number | null;
+    owner: string;
+    start_after?: string | null;
+  };
+} | {
+  is_approved_for_all: {
+    operator: string;
+    owner: string;
+  };
+} | {
+  token_info: {
+    token_id: string;
+  };
+} | {
+  tokens: {
+    limit?: number | null;
+    owner: string;
+    start_after?: string | null;
+  };
+} | {
+  all_tokens: {
+    limit?: number | null;
+    start_after?: string | null;
+  };
+};
+
+/**
+ * Cw1155ReceiveMsg should be de/serialized under `Receive()` variant in a ExecuteMsg
+ */
+export type Cw1155ReceiveMsg = {
+  amount: Uint128;
+  /**
+   * The account that the token transfered from
+   */
+  from?: string | null;
+  msg: Binary;
+  /**
+   * The account that executed the send message
+   */
+  operator: string;
+  token_id: string;
+};
+
+/**
+ * Expiration represents a point in time when some event happens. It can compare with a BlockInfo and will return is_expired() == true once the condition is hit (and for every block in the future)
+ */
+export type Expiration = {
+  at_height: number;
+} | {
+  at_time: Timestamp;
+} | {
+  never: {};
+};
+
+export type IsApprovedForAllResponse = {
+  approved: boolean;
+};
+
+/**
+ * A point in time in nanosecond precision.
+ *
+ * This type can represent times from 1970-01-01T00:00:00Z to 2554-07-21T23:34:33Z.
+ *
+ * ## Examples
+ *
+ * ``` # use cosmwasm_std::Timestamp; let ts = Timestamp::from_nanos(1_000_000_202); assert_eq!(ts.nanos(), 1_000_000_202); assert_eq!(ts.seconds(), 1); assert_eq!(ts.subsec_nanos(), 202);
+ *
+ * let ts = ts.plus_seconds(2); assert_eq!(ts.nanos(), 3_000_000_202); assert_eq!(ts.seconds(), 3); assert_eq!(ts.subsec_nanos(), 202); ```
+ */
+export type Timestamp = Uint64;
+
+export type TokenInfoResponse = {
+  /**
+   * Should be a url point to a json file
+   */
+  url: string;
+};
+
+export type TokensResponse = {
+  /**
+   * Contains all token_ids in lexicographical ordering If there are more than `limit`, use `start_from` in future queries to achieve pagination.
+   */
+  tokens: string[];
+};
+
+/**
+ * A thin wrapper around u128 that is using strings for JSON encoding/decoding, such that the full u128 range can be used for clients that convert JSON numbers to floats, like JavaScript and jq.
+ *
+ * # Examples
+ *
+ * Use `from` to create instances of this and `u128` to get the value out:
+ *
+ * ``` # use cosmwasm_std::Uint128; let a = Uint128::from(123u128); assert_eq!(a.u128(), 123);
+ *
+ * let b = Uint128::from(42u64); assert_eq!(b.u128(), 42);
+ *
+ * let c = Uint128::from(70u32); assert_eq!(c.u128(), 70); ```
+ */
+export type Uint128 = string;
+
+/**
+ * A thin wrapper around u64 that is using strings for JSON encoding/decoding, such that the full u64 range can be used for clients that convert JSON numbers to floats, like JavaScript and jq.
+ *
+ * # Examples
+ *
+ * Use `from` to create instances of this and `u64` to get the value out:
+ *
+ * ``` # use cosmwasm_std::Uint64; let a = Uint64::from(42u64); assert_eq!(a.u64(), 42);
+ *
+ * let b = Uint64::from(70u32); assert_eq!(b.u64(), 70); ```
+ */
+export type Uint64 = string;
+
+export const Cw1155ExecuteMsg = {
+  /**
+   * SendFrom is a base message to move tokens, if `env.sender` is the owner or has sufficient pre-approval.
+   */
+  sendFrom: (args: {
+    from: string;
+    /**
+     * `None` means don't call the receiver interface
+     */
+    msg?: Binary | null;
+    /**
+     * If `to` is not contract, `msg` should be `None`
+     */
+    to: string;
+    token_id: string;
+    value: Uint128;
+  }): Cw1155ExecuteMsg => ({ send_from: args }),
+  /**
+   * BatchSendFrom is a base message to move multiple types of tokens in batch, if `env.sender` is the owner or has sufficient pre-approval.
+   */
+  batchSendFrom: (args: {
+    batch: ([string, Uint128])[];
+    from: string;
+    /**
+     * `None` means don't call the receiver interface
+     */
+    msg?: Binary | null;
+    /**
+     * if `to` is not contract, `msg` should be `None`
+     */
+    to: string;
+  }): Cw1155ExecuteMsg => ({ batch_send_from: args }),
+  /**
+   * Mint is a base message to mint tokens.
+   */
+  mint: (args: {
+    /**
+     * `None` means don't call the receiver interface
+     */
+    msg?: Binary | null;
+    /**
+     * If `to` is not contract, `msg` should be `None`
+     */
+    to: string;
+    token_id: string;
+    value: Uint128;
+  }): Cw1155ExecuteMsg => ({ mint: args }),
+  /**
+   * BatchMint is a base message to mint multiple types of tokens in batch.
+   */
+  batchMint: (args: {
+    batch: ([string, Uint128])[];
+    /**
+     * `None` means don't call the receiver interface
+     */
+    msg?: Binary | null;
+    /**
+     * If `to` is not contract, `msg` should be `None`
+     */
+    to: string;
+  }): Cw1155ExecuteMsg => ({ batch_mint: args }),
+  /**
+   * Burn is a base message to burn tokens.
+   */
+  burn: (args: {
+    from: string;
+    token_id: string;
+    value: Uint128;
+  }): Cw1155ExecuteMsg => ({ burn: args }),
+  /**
+   * BatchBurn is a base message to burn multiple types of tokens in batch.
+   */
+  batchBurn: (args: {
+    batch: ([string, Uint128])[];
+    from: string;
+  }): Cw1155ExecuteMsg => ({ batch_burn: args }),
+  /**
+   * Allows operator to transfer / send any token from the owner's account. If expiration is set, then this allowance has a time/height limit
+   */
+  approveAll: (args: {
+    expires?: Expiration | null;
+    operator: string;
+  }): Cw1155ExecuteMsg => ({ approve_all: args }),
+  /**
+   * Remove previously granted ApproveAll permission
+   */
+  revokeAll: (args: {
+    operator: string;
+  }): Cw1155ExecuteMsg => ({ revoke_all: args }),
+};
+
+/**
+ * Sends a smart query to the contract, e.g. with `CosmWasmClient.queryContractSmart`
+ */
+export type SmartQuery = <T>(msg: Cw1155QueryMsg) => Promise<T>;
+
+export const cw1155Queries = (query: SmartQuery) => ({
+  /**
+   * Returns the current balance of the given address, 0 if unset.
+   */
+  balance: (args: {
+    owner: string;
+    token_id: string;
+  }): Promise<BalanceResponse> =>
+    query<BalanceResponse>({ balance: args }),
+  /**
+   * Returns the current balance of the given address for a batch of tokens, 0 if unset.
+   */
+  batchBalance: (args: {
+    owner: string;
+    token_ids: string[];
+  }): Promise<BatchBalanceResponse> =>
+    query<BatchBalanceResponse>({ batch_balance: args }),
+  /**
+   * List all operators that can access all of the owner's tokens.
+   */
+  approvedForAll: (args: {
+    /**
+     * unset or false will filter out expired approvals, you must set to true to see them
+     */
+    include_expired?: boolean | null;
+    limit?: number | null;
+    owner: string;
+    start_after?: string | null;
+  }): Promise<ApprovedForAllResponse> =>
+    query<ApprovedForAllResponse>({ approved_for_all: args }),
+  /**
+   * Query approved status `owner` granted to `operator`.
+   */
+  isApprovedForAll: (args: {
+    operator: string;
+    owner: string;
+  }): Promise<IsApprovedForAllResponse> =>
+    query<IsApprovedForAllResponse>({ is_approved_for_all: args }),
+  /**
+   * With MetaData Extension. Query metadata of token
+   */
+  tokenInfo: (args: {
+    token_id: string;
+  }): Promise<TokenInfoResponse> =>
+    query<TokenInfoResponse>({ token_info: args }),
+  /**
+   * With Enumerable extension. Returns all tokens owned by the given address, [] if unset.
+   */
+  tokens: (args: {
+    limit?: number | null;
+    owner: string;
+    start_after?: string | null;
+  }): Promise<TokensResponse> =>
+    query<TokensResponse>({ tokens: args }),
+  /**
+   * With Enumerable extension. Requires pagination. Lists all token_ids controlled by the contract.
+   */
+  allTokens: (args: {
+    limit?: number | null;
+    start_after?: string | null;
+  }): Promise<TokensResponse> =>
+    query<TokensResponse>({ all_tokens: args }),
+});
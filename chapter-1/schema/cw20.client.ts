@@ -0,0 +1,431 @@
+// Generated from the cw20 schemas, do not edit.
+
+/**
+ * A human readable address.
+ *
+ * In Cosmos, this is typically bech32 encoded. But for multi-chain smart contracts no assumptions should be made other than being UTF-8 encoded and of reasonable length.
+ *
+ * This type represents a validated address. It can be created in the following ways 1. Use `Addr::unchecked(input)` 2. Use `let checked: Addr = deps.api.addr_validate(input)?` 3. Use `let checked: Addr = deps.api.addr_humanize(canonical_addr)?` 4. Deserialize from JSON. This must only be done from JSON that was validated before such as a contract's state. `Addr` must not be used in messages sent by the user because this would result in unvalidated instances.
+ *
+ * This type is immutable. If you really need to mutate it (Really? Are you sure?), create a mutable copy using `let mut mutable = Addr::to_string()` and operate on that `String` instance.
+ */
+export type Addr = string;
+
+export type AllAccountsResponse = {
+  accounts: string[];
+};
+
+export type AllAllowancesResponse = {
+  allowances: AllowanceInfo[];
+};
+
+export type AllowanceInfo = {
+  allowance: Uint128;
+  expires: Expiration;
+  spender: string;
+};
+
+export type AllowanceResponse = {
+  allowance: Uint128;
+  expires: Expiration;
+};
+
+export type BalanceResponse = {
+  balance: Uint128;
+};
+
+/**
+ * Binary is a wrapper around Vec<u8> to add base64 de/serialization with serde. It also adds some helper methods to help encode inline.
+ *
+ * This is only needed as serde-json-{core,wasm} has a horrible encoding for Vec<u8>
+ */
+export type Binary = string;
+
+export type Cw20ExecuteMsg = {
+  transfer: {
+    amount: Uint128;
+    recipient: string;
+  };
+} | {
+  burn: {
+    amount: Uint128;
+  };
+} | {
+  send: {
+    amount: Uint128;
+    contract: string;
+    msg: Binary;
+  };
+} | {
+  increase_allowance: {
+    amount: Uint128;
+    expires?: Expiration | null;
+    spender: string;
+  };
+} | {
+  decrease_allowance: {
+    amount: Uint128;
+    expires?: Expiration | null;
+    spender: string;
+  };
+} | {
+  transfer_from: {
+    amount: Uint128;
+    owner: string;
+    recipient: string;
+  };
+} | {
+  send_from: {
+    amount: Uint128;
+    contract: string;
+    msg: Binary;
+    owner: string;
+  };
+} | {
+  burn_from: {
+    amount: Uint128;
+    owner: string;
+  };
+} | {
+  mint: {
+    amount: Uint128;
+    recipient: string;
+  };
+} | {
+  update_minter: {
+    new_minter?: string | null;
+  };
+} | {
+  update_marketing: {
+    /**
+     * A longer description of the token and it's utility. Designed for tooltips or such
+     */
+    description?: string | null;
+    /**
+     * The address (if any) who can update this data structure
+     */
+    marketing?: string | null;
+    /**
+     * A URL pointing to the project behind this token.
+     */
+    project?: string | null;
+  };
+} | {
+  upload_logo: Logo;
+};
+
+export type Cw20QueryMsg = {
+  balance: {
+    address: string;
+  };
+} | {
+  token_info: {};
+} | {
+  allowance: {
+    owner: string;
+    spender: string;
+  };
+} | {
+  minter: {};
+} | {
+  marketing_info: {};
+} | {
+  download_logo: {};
+} | {
+  all_allowances: {
+    limit?: number | null;
+    owner: string;
+    start_after?: string | null;
+  };
+} | {
+  all_accounts: {
+    limit?: number | null;
+    start_after?: string | null;
+  };
+};
+
+/**
+ * Cw20ReceiveMsg should be de/serialized under `Receive()` variant in a ExecuteMsg
+ */
+export type Cw20ReceiveMsg = {
+  amount: Uint128;
+  msg: Binary;
+  sender: string;
+};
+
+/**
+ * When we download an embedded logo, we get this response type. We expect a SPA to be able to accept this info and display it.
+ */
+export type DownloadLogoResponse = {
+  data: Binary;
+  mime_type: string;
+};
+
+/**
+ * This is used to store the logo on the blockchain in an accepted format. Enforce maximum size of 5KB on all variants.
+ */
+export type EmbeddedLogo = {
+  svg: Binary;
+} | {
+  png: Binary;
+};
+
+/**
+ * Expiration represents a point in time when some event happens. It can compare with a BlockInfo and will return is_expired() == true once the condition is hit (and for every block in the future)
+ */
+export type Expiration = {
+  at_height: number;
+} | {
+  at_time: Timestamp;
+} | {
+  never: {};
+};
+
+/**
+ * This is used for uploading logo data, or setting it in InstantiateData
+ */
+export type Logo = {
+  url: string;
+} | {
+  embedded: EmbeddedLogo;
+};
+
+/**
+ * This is used to display logo info, provide a link or inform there is one that can be downloaded from the blockchain itself
+ */
+export type LogoInfo = "embedded" | {
+  url: string;
+};
+
+export type MarketingInfoResponse = {
+  /**
+   * A longer description of the token and it's utility. Designed for tooltips or such
+   */
+  description?: string | null;
+  /**
+   * A link to the logo, or a comment there is an on-chain logo stored
+   */
+  logo?: LogoInfo | null;
+  /**
+   * The address (if any) who can update this data structure
+   */
+  marketing?: Addr | null;
+  /**
+   * A URL pointing to the project behind this token.
+   */
+  project?: string | null;
+};
+
+export type MinterResponse = {
+  /**
+   * cap is a hard cap on total supply that can be achieved by minting. Note that this refers to total_supply. If None, there is unlimited cap.
+   */
+  cap?: Uint128 | null;
+  minter: string;
+};
+
+/**
+ * A point in time in nanosecond precision.
+ *
+ * This type can represent times from 1970-01-01T00:00:00Z to 2554-07-21T23:34:33Z.
+ *
+ * ## Examples
+ *
+ * ``` # use cosmwasm_std::Timestamp; let ts = Timestamp::from_nanos(1_000_000_202); assert_eq!(ts.nanos(), 1_000_000_202); assert_eq!(ts.seconds(), 1); assert_eq!(ts.subsec_nanos(), 202);
+ *
+ * let ts = ts.plus_seconds(2); assert_eq!(ts.nanos(), 3_000_000_202); assert_eq!(ts.seconds(), 3); assert_eq!(ts.subsec_nanos(), 202); ```
+ */
+export type Timestamp = Uint64;
+
+export type TokenInfoResponse = {
+  decimals: number;
+  name: string;
+  symbol: string;
+  total_supply: Uint128;
+};
+
+/**
+ * A thin wrapper around u128 that is using strings for JSON encoding/decoding, such that the full u128 range can be used for clients that convert JSON numbers to floats, like JavaScript and jq.
+ *
+ * # Examples
+ *
+ * Use `from` to create instances of this and `u128` to get the value out:
+ *
+ * ``` # use cosmwasm_std::Uint128; let a = Uint128::from(123u128); assert_eq!(a.u128(), 123);
+ *
+ * let b = Uint128::from(42u64); assert_eq!(b.u128(), 42);
+ *
+ * let c = Uint128::from(70u32); assert_eq!(c.u128(), 70); ```
+ */
+export type Uint128 = string;
+
+/**
+ * A thin wrapper around u64 that is using strings for JSON encoding/decoding, such that the full u64 range can be used for clients that convert JSON numbers to floats, like JavaScript and jq.
+ *
+ * # Examples
+ *
+ * Use `from` to create instances of this and `u64` to get the value out:
+ *
+ * ``` # use cosmwasm_std::Uint64; let a = Uint64::from(42u64); assert_eq!(a.u64(), 42);
+ *
+ * let b = Uint64::from(70u32); assert_eq!(b.u64(), 70); ```
+ */
+export type Uint64 = string;
+
+export const Cw20ExecuteMsg = {
+  /**
+   * Transfer is a base message to move tokens to another account without triggering actions
+   */
+  transfer: (args: {
+    amount: Uint128;
+    recipient: string;
+  }): Cw20ExecuteMsg => ({ transfer: args }),
+  /**
+   * Burn is a base message to destroy tokens forever
+   */
+  burn: (args: {
+    amount: Uint128;
+  }): Cw20ExecuteMsg => ({ burn: args }),
+  /**
+   * Send is a base message to transfer tokens to a contract and trigger an action on the receiving contract.
+   */
+  send: (args: {
+    amount: Uint128;
+    contract: string;
+    msg: Binary;
+  }): Cw20ExecuteMsg => ({ send: args }),
+  /**
+   * Only with "approval" extension. Allows spender to access an additional amount tokens from the owner's (env.sender) account. If expires is Some(), overwrites current allowance expiration with this one.
+   */
+  increaseAllowance: (args: {
+    amount: Uint128;
+    expires?: Expiration | null;
+    spender: string;
+  }): Cw20ExecuteMsg => ({ increase_allowance: args }),
+  /**
+   * Only with "approval" extension. Lowers the spender's access of tokens from the owner's (env.sender) account by amount. If expires is Some(), overwrites current allowance expiration with this one.
+   */
+  decreaseAllowance: (args: {
+    amount: Uint128;
+    expires?: Expiration | null;
+    spender: string;
+  }): Cw20ExecuteMsg => ({ decrease_allowance: args }),
+  /**
+   * Only with "approval" extension. Transfers amount tokens from owner -> recipient if `env.sender` has sufficient pre-approval.
+   */
+  transferFrom: (args: {
+    amount: Uint128;
+    owner: string;
+    recipient: string;
+  }): Cw20ExecuteMsg => ({ transfer_from: args }),
+  /**
+   * Only with "approval" extension. Sends amount tokens from owner -> contract if `env.sender` has sufficient pre-approval.
+   */
+  sendFrom: (args: {
+    amount: Uint128;
+    contract: string;
+    msg: Binary;
+    owner: string;
+  }): Cw20ExecuteMsg => ({ send_from: args }),
+  /**
+   * Only with "approval" extension. Destroys tokens forever
+   */
+  burnFrom: (args: {
+    amount: Uint128;
+    owner: string;
+  }): Cw20ExecuteMsg => ({ burn_from: args }),
+  /**
+   * Only with the "mintable" extension. If authorized, creates amount new tokens and adds to the recipient balance.
+   */
+  mint: (args: {
+    amount: Uint128;
+    recipient: string;
+  }): Cw20ExecuteMsg => ({ mint: args }),
+  /**
+   * Only with the "mintable" extension. The current minter may set a new minter. Setting the minter to None will remove the token's minter forever.
+   */
+  updateMinter: (args: {
+    new_minter?: string | null;
+  }): Cw20ExecuteMsg => ({ update_minter: args }),
+  /**
+   * Only with the "marketing" extension. If authorized, updates marketing metadata. Setting None/null for any of these will leave it unchanged. Setting Some("") will clear this field on the contract storage
+   */
+  updateMarketing: (args: {
+    /**
+     * A longer description of the token and it's utility. Designed for tooltips or such
+     */
+    description?: string | null;
+    /**
+     * The address (if any) who can update this data structure
+     */
+    marketing?: string | null;
+    /**
+     * A URL pointing to the project behind this token.
+     */
+    project?: string | null;
+  }): Cw20ExecuteMsg => ({ update_marketing: args }),
+  /**
+   * If set as the "marketing" role on the contract, upload a new URL, SVG, or PNG for the token
+   */
+  uploadLogo: (args: Logo): Cw20ExecuteMsg => ({ upload_logo: args }),
+};
+
+/**
+ * Sends a smart query to the contract, e.g. with `CosmWasmClient.queryContractSmart`
+ */
+export type SmartQuery = <T>(msg: Cw20QueryMsg) => Promise<T>;
+
+export const cw20Queries = (query: SmartQuery) => ({
+  /**
+   * Returns the current balance of the given address, 0 if unset. Return type: BalanceResponse.
+   */
+  balance: (args: {
+    address: string;
+  }): Promise<BalanceResponse> =>
+    query<BalanceResponse>({ balance: args }),
+  /**
+   * Returns metadata on the contract - name, decimals, supply, etc. Return type: TokenInfoResponse.
+   */
+  tokenInfo: (): Promise<TokenInfoResponse> =>
+    query<TokenInfoResponse>({ token_info: {} }),
+  /**
+   * Only with "allowance" extension. Returns how much spender can use from owner account, 0 if unset. Return type: AllowanceResponse.
+   */
+  allowance: (args: {
+    owner: string;
+    spender: string;
+  }): Promise<AllowanceResponse> =>
+    query<AllowanceResponse>({ allowance: args }),
+  /**
+   * Only with "mintable" extension. Returns who can mint and the hard cap on maximum tokens after minting. Return type: MinterResponse.
+   */
+  minter: (): Promise<MinterResponse> =>
+    query<MinterResponse>({ minter: {} }),
+  /**
+   * Only with "marketing" extension Returns more metadata on the contract to display in the client: - description, logo, project url, etc. Return type: MarketingInfoResponse.
+   */
+  marketingInfo: (): Promise<MarketingInfoResponse> =>
+    query<MarketingInfoResponse>({ marketing_info: {} }),
+  /**
+   * Only with "marketing" extension Downloads the embedded logo data (if stored on chain). Errors if no logo data stored for this contract. Return type: DownloadLogoResponse.
+   */
+  downloadLogo: (): Promise<DownloadLogoResponse> =>
+    query<DownloadLogoResponse>({ download_logo: {} }),
+  /**
+   * Only with "enumerable" extension (and "allowances") Returns all allowances this owner has approved. Supports pagination. Return type: AllAllowancesResponse.
+   */
+  allAllowances: (args: {
+    limit?: number | null;
+    owner: string;
+    start_after?: string | null;
+  }): Promise<AllAllowancesResponse> =>
+    query<AllAllowancesResponse>({ all_allowances: args }),
+  /**
+   * Only with "enumerable" extension Returns all accounts that have balances. Supports pagination. Return type: AllAccountsResponse.
+   */
+  allAccounts: (args: {
+    limit?: number | null;
+    start_after?: string | null;
+  }): Promise<AllAccountsResponse> =>
+    query<AllAccountsResponse>({ all_accounts: args }),
+});
@@ -0,0 +1,381 @@
+// Generated from the cw3 schemas, do not edit.
+
+/**
+ * The message types of the bank module.
+ *
+ * See https://github.com/cosmos/cosmos-sdk/blob/v0.40.0/proto/cosmos/bank/v1beta1/tx.proto
+ */
+export type BankMsg = {
+  send: {
+    amount: Coin[];
+    to_address: string;
+  };
+} | {
+  burn: {
+    amount: Coin[];
+  };
+};
+
+/**
+ * Binary is a wrapper around Vec<u8> to add base64 de/serialization with serde. It also adds some helper methods to help encode inline.
+ *
+ * This is only needed as serde-json-{core,wasm} has a horrible encoding for Vec<u8>
+ */
+export type Binary = string;
+
+export type Coin = {
+  amount: Uint128;
+  denom: string;
+};
+
+export type CosmosMsg_for_Empty = {
+  bank: BankMsg;
+} | {
+  custom: Empty;
+} | {
+  wasm: WasmMsg;
+};
+
+/**
+ * A fixed-point decimal value with 18 fractional digits, i.e. Decimal(1_000_000_000_000_000_000) == 1.0
+ *
+ * The greatest possible value that can be represented is 340282366920938463463.374607431768211455 (which is (2^128 - 1) / 10^18)
+ */
+export type Decimal = string;
+
+/**
+ * An empty struct that serves as a placeholder in different places, such as contracts that don't set a custom message.
+ *
+ * It is designed to be expressable in correct JSON and JSON Schema but contains no meaningful data. Previously we used enums without cases, but those cannot represented as valid JSON Schema (https://github.com/CosmWasm/cosmwasm/issues/451)
+ */
+export type Empty = {};
+
+export type ExecuteMsg = {
+  propose: {
+    description: string;
+    earliest?: Expiration | null;
+    latest?: Expiration | null;
+    msgs: CosmosMsg_for_Empty[];
+    title: string;
+  };
+} | {
+  vote: {
+    proposal_id: number;
+    vote: Vote;
+  };
+} | {
+  execute: {
+    proposal_id: number;
+  };
+} | {
+  close: {
+    proposal_id: number;
+  };
+};
+
+/**
+ * Expiration represents a point in time when some event happens. It can compare with a BlockInfo and will return is_expired() == true once the condition is hit (and for every block in the future)
+ */
+export type Expiration = {
+  at_height: number;
+} | {
+  at_time: Timestamp;
+} | {
+  never: {};
+};
+
+export type ProposalListResponse = {
+  proposals: ProposalResponse_for_Empty[];
+};
+
+/**
+ * Note, if you are storing custom messages in the proposal, the querier needs to know what possible custom message types those are in order to parse the response
+ */
+export type ProposalResponse_for_Empty = {
+  description: string;
+  expires: Expiration;
+  id: number;
+  msgs: CosmosMsg_for_Empty[];
+  status: Status;
+  /**
+   * This is the threshold that is applied to this proposal. Both the rules of the voting contract, as well as the total_weight of the voting group may have changed since this time. That means that the generic `Threshold{}` query does not provide valid information for existing proposals.
+   */
+  threshold: ThresholdResponse;
+  title: string;
+};
+
+export type QueryMsg = {
+  threshold: {};
+} | {
+  proposal: {
+    proposal_id: number;
+  };
+} | {
+  list_proposals: {
+    limit?: number | null;
+    start_after?: number | null;
+  };
+} | {
+  reverse_proposals: {
+    limit?: number | null;
+    start_before?: number | null;
+  };
+} | {
+  vote: {
+    proposal_id: number;
+    voter: string;
+  };
+} | {
+  list_votes: {
+    limit?: number | null;
+    proposal_id: number;
+    start_after?: string | null;
+  };
+} | {
+  voter: {
+    address: string;
+  };
+} | {
+  list_voters: {
+    limit?: number | null;
+    start_after?: string | null;
+  };
+};
+
+export type Status = "pending" | "open" | "rejected" | "passed" | "executed";
+
+/**
+ * This defines the different ways tallies can happen. Every contract should support a subset of these, ideally all.
+ *
+ * The total_weight used for calculating success as well as the weights of each individual voter used in tallying should be snapshotted at the beginning of the block at which the proposal starts (this is likely the responsibility of a correct cw4 implementation).
+ */
+export type ThresholdResponse = {
+  absolute_count: {
+    total_weight: number;
+    weight: number;
+  };
+} | {
+  absolute_percentage: {
+    percentage: Decimal;
+    total_weight: number;
+  };
+} | {
+  threshold_quorum: {
+    quorum: Decimal;
+    threshold: Decimal;
+    total_weight: number;
+  };
+};
+
+/**
+ * A point in time in nanosecond precision.
+ *
+ * This type can represent times from 1970-01-01T00:00:00Z to 2554-07-21T23:34:33Z.
+ *
+ * ## Examples
+ *
+ * ``` # use cosmwasm_std::Timestamp; let ts = Timestamp::from_nanos(1_000_000_202); assert_eq!(ts.nanos(), 1_000_000_202); assert_eq!(ts.seconds(), 1); assert_eq!(ts.subsec_nanos(), 202);
+ *
+ * let ts = ts.plus_seconds(2); assert_eq!(ts.nanos(), 3_000_000_202); assert_eq!(ts.seconds(), 3); assert_eq!(ts.subsec_nanos(), 202); ```
+ */
+export type Timestamp = Uint64;
+
+/**
+ * A thin wrapper around u128 that is using strings for JSON encoding/decoding, such that the full u128 range can be used for clients that convert JSON numbers to floats, like JavaScript and jq.
+ *
+ * # Examples
+ *
+ * Use `from` to create instances of this and `u128` to get the value out:
+ *
+ * ``` # use cosmwasm_std::Uint128; let a = Uint128::from(123u128); assert_eq!(a.u128(), 123);
+ *
+ * let b = Uint128::from(42u64); assert_eq!(b.u128(), 42);
+ *
+ * let c = Uint128::from(70u32); assert_eq!(c.u128(), 70); ```
+ */
+export type Uint128 = string;
+
+/**
+ * A thin wrapper around u64 that is using strings for JSON encoding/decoding, such that the full u64 range can be used for clients that convert JSON numbers to floats, like JavaScript and jq.
+ *
+ * # Examples
+ *
+ * Use `from` to create instances of this and `u64` to get the value out:
+ *
+ * ``` # use cosmwasm_std::Uint64; let a = Uint64::from(42u64); assert_eq!(a.u64(), 42);
+ *
+ * let b = Uint64::from(70u32); assert_eq!(b.u64(), 70); ```
+ */
+export type Uint64 = string;
+
+export type Vote = "yes" | "no" | "abstain" | "veto";
+
+/**
+ * Returns the vote (opinion as well as weight counted) as well as the address of the voter who submitted it
+ */
+export type VoteInfo = {
+  proposal_id: number;
+  vote: Vote;
+  voter: string;
+  weight: number;
+};
+
+export type VoteListResponse = {
+  votes: VoteInfo[];
+};
+
+export type VoteResponse = {
+  vote?: VoteInfo | null;
+};
+
+export type VoterDetail = {
+  addr: string;
+  weight: number;
+};
+
+export type VoterListResponse = {
+  voters: VoterDetail[];
+};
+
+export type VoterResponse = {
+  weight?: number | null;
+};
+
+/**
+ * The message types of the wasm module.
+ *
+ * See https://github.com/CosmWasm/wasmd/blob/v0.14.0/x/wasm/internal/types/tx.proto
+ */
+export type WasmMsg = {
+  execute: {
+    contract_addr: string;
+    funds: Coin[];
+    /**
+     * msg is the json-encoded ExecuteMsg struct (as raw Binary)
+     */
+    msg: Binary;
+  };
+} | {
+  instantiate: {
+    admin?: string | null;
+    code_id: number;
+    funds: Coin[];
+    /**
+     * A human-readbale label for the contract
+     */
+    label: string;
+    /**
+     * msg is the JSON-encoded InstantiateMsg struct (as raw Binary)
+     */
+    msg: Binary;
+  };
+} | {
+  migrate: {
+    contract_addr: string;
+    /**
+     * msg is the json-encoded MigrateMsg struct that will be passed to the new code
+     */
+    msg: Binary;
+    /**
+     * the code_id of the new logic to place in the given contract
+     */
+    new_code_id: number;
+  };
+} | {
+  update_admin: {
+    admin: string;
+    contract_addr: string;
+  };
+} | {
+  clear_admin: {
+    contract_addr: string;
+  };
+};
+
+export const ExecuteMsg = {
+  propose: (args: {
+    description: string;
+    earliest?: Expiration | null;
+    latest?: Expiration | null;
+    msgs: CosmosMsg_for_Empty[];
+    title: string;
+  }): ExecuteMsg => ({ propose: args }),
+  vote: (args: {
+    proposal_id: number;
+    vote: Vote;
+  }): ExecuteMsg => ({ vote: args }),
+  execute: (args: {
+    proposal_id: number;
+  }): ExecuteMsg => ({ execute: args }),
+  close: (args: {
+    proposal_id: number;
+  }): ExecuteMsg => ({ close: args }),
+};
+
+/**
+ * Sends a smart query to the contract, e.g. with `CosmWasmClient.queryContractSmart`
+ */
+export type SmartQuery = <T>(msg: QueryMsg) => Promise<T>;
+
+export const cw3Queries = (query: SmartQuery) => ({
+  /**
+   * Returns the threshold rules that would be used for a new proposal that was opened right now. The threshold rules do not change often, but the `total_weight` in the response may easily differ from that used in previously opened proposals. Returns ThresholdResponse.
+   */
+  threshold: (): Promise<ThresholdResponse> =>
+    query<ThresholdResponse>({ threshold: {} }),
+  /**
+   * Returns details of the proposal state. Returns ProposalResponse.
+   */
+  proposal: (args: {
+    proposal_id: number;
+  }): Promise<ProposalResponse_for_Empty> =>
+    query<ProposalResponse_for_Empty>({ proposal: args }),
+  /**
+   * Iterate over details of all proposals from oldest to newest. Returns ProposalListResponse
+   */
+  listProposals: (args: {
+    limit?: number | null;
+    start_after?: number | null;
+  }): Promise<ProposalListResponse> =>
+    query<ProposalListResponse>({ list_proposals: args }),
+  /**
+   * Iterate reverse over details of all proposals, this is useful to easily query only the most recent proposals (to get updates). Returns ProposalListResponse
+   */
+  reverseProposals: (args: {
+    limit?: number | null;
+    start_before?: number | null;
+  }): Promise<ProposalListResponse> =>
+    query<ProposalListResponse>({ reverse_proposals: args }),
+  /**
+   * Query the vote made by the given voter on `proposal_id`. This should return an error if there is no such proposal. It will return a None value if the proposal exists but the voter did not vote. Returns VoteResponse
+   */
+  vote: (args: {
+    proposal_id: number;
+    voter: string;
+  }): Promise<VoteResponse> =>
+    query<VoteResponse>({ vote: args }),
+  /**
+   * Iterate (with pagination) over all votes for this proposal. The ordering is arbitrary, unlikely to be sorted by address. But ordering is consistent and pagination from the end of each page will cover all votes for the proposal. Returns VoteListResponse
+   */
+  listVotes: (args: {
+    limit?: number | null;
+    proposal_id: number;
+    start_after?: string | null;
+  }): Promise<VoteListResponse> =>
+    query<VoteListResponse>({ list_votes: args }),
+  /**
+   * Voter extension: Returns VoterResponse
+   */
+  voter: (args: {
+    address: string;
+  }): Promise<VoterResponse> =>
+    query<VoterResponse>({ voter: args }),
+  /**
+   * ListVoters extension: Returns VoterListResponse
+   */
+  listVoters: (args: {
+    limit?: number | null;
+    start_after?: string | null;
+  }): Promise<VoterListResponse> =>
+    query<VoterListResponse>({ list_voters: args }),
+});
@@ -0,0 +1,141 @@
+// Generated from the cw4 schemas, do not edit.
+
+export type AdminResponse = {
+  admin?: string | null;
+};
+
+export type Cw4ExecuteMsg = {
+  update_admin: {
+    admin?: string | null;
+  };
+} | {
+  add_hook: {
+    addr: string;
+  };
+} | {
+  remove_hook: {
+    addr: string;
+  };
+};
+
+export type Cw4QueryMsg = {
+  admin: {};
+} | {
+  total_weight: {
+    at_height?: number | null;
+  };
+} | {
+  list_members: {
+    limit?: number | null;
+    start_after?: string | null;
+  };
+} | {
+  member: {
+    addr: string;
+    at_height?: number | null;
+  };
+} | {
+  hooks: {};
+};
+
+export type HooksResponse = {
+  hooks: string[];
+};
+
+/**
+ * A group member has a weight associated with them. This may all be equal, or may have meaning in the app that makes use of the group (eg. voting power)
+ */
+export type Member = {
+  addr: string;
+  weight: number;
+};
+
+/**
+ * MemberChangedHookMsg should be de/serialized under `MemberChangedHook()` variant in a ExecuteMsg. This contains a list of all diffs on the given transaction.
+ */
+export type MemberChangedHookMsg = {
+  diffs: MemberDiff[];
+};
+
+/**
+ * MemberDiff shows the old and new states for a given cw4 member They cannot both be None. old = None, new = Some -> Insert old = Some, new = Some -> Update old = Some, new = None -> Delete
+ */
+export type MemberDiff = {
+  key: string;
+  new?: number | null;
+  old?: number | null;
+};
+
+export type MemberListResponse = {
+  members: Member[];
+};
+
+export type MemberResponse = {
+  weight?: number | null;
+};
+
+export type TotalWeightResponse = {
+  weight: number;
+};
+
+export const Cw4ExecuteMsg = {
+  /**
+   * Change the admin
+   */
+  updateAdmin: (args: {
+    admin?: string | null;
+  }): Cw4ExecuteMsg => ({ update_admin: args }),
+  /**
+   * Add a new hook to be informed of all membership changes. Must be called by Admin
+   */
+  addHook: (args: {
+    addr: string;
+  }): Cw4ExecuteMsg => ({ add_hook: args }),
+  /**
+   * Remove a hook. Must be called by Admin
+   */
+  removeHook: (args: {
+    addr: string;
+  }): Cw4ExecuteMsg => ({ remove_hook: args }),
+};
+
+/**
+ * Sends a smart query to the contract, e.g. with `CosmWasmClient.queryContractSmart`
+ */
+export type SmartQuery = <T>(msg: Cw4QueryMsg) => Promise<T>;
+
+export const cw4Queries = (query: SmartQuery) => ({
+  /**
+   * Return AdminResponse
+   */
+  admin: (): Promise<AdminResponse> =>
+    query<AdminResponse>({ admin: {} }),
+  /**
+   * Return TotalWeightResponse
+   */
+  totalWeight: (args: {
+    at_height?: number | null;
+  }): Promise<TotalWeightResponse> =>
+    query<TotalWeightResponse>({ total_weight: args }),
+  /**
+   * Returns MembersListResponse
+   */
+  listMembers: (args: {
+    limit?: number | null;
+    start_after?: string | null;
+  }): Promise<MemberListResponse> =>
+    query<MemberListResponse>({ list_members: args }),
+  /**
+   * Returns MemberResponse
+   */
+  member: (args: {
+    addr: string;
+    at_height?: number | null;
+  }): Promise<MemberResponse> =>
+    query<MemberResponse>({ member: args }),
+  /**
+   * Shows all registered hooks. Returns HooksResponse.
+   */
+  hooks: (): Promise<HooksResponse> =>
+    query<HooksResponse>({ hooks: {} }),
+});
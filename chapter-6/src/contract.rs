@@ -1,32 +1,101 @@
-use crate::state::COUNTER;
-use cosmwasm_std::{DepsMut, Response, StdResult};
+use crate::state::{COUNTER, OWNER};
+use cosmwasm_std::{DepsMut, MessageInfo, Response, StdResult};
 
-pub fn instantiate(deps: DepsMut) -> StdResult<Response> {
+pub fn instantiate(deps: DepsMut, info: MessageInfo) -> StdResult<Response> {
     COUNTER.save(deps.storage, &0)?;
+    OWNER.save(deps.storage, &info.sender)?;
     Ok(Response::new())
 }
 
 pub mod execute {
-    use crate::state::COUNTER;
-    use cosmwasm_std::{DepsMut, MessageInfo, Response, StdResult};
+    use crate::state::{COUNTER, COUNTS, OWNER, TOP_COUNTERS};
+    use cosmwasm_std::{DepsMut, Empty, MessageInfo, Response, StdError, StdResult};
+
     pub fn increment(deps: DepsMut, info: MessageInfo) -> StdResult<Response> {
         let counter = COUNTER.load(deps.storage)? + 1;
         COUNTER.save(deps.storage, &counter)?;
+
+        let count = COUNTS
+            .may_load(deps.storage, &info.sender)?
+            .unwrap_or_default();
+        COUNTS.save(deps.storage, &info.sender, &(count + 1))?;
+        TOP_COUNTERS.remove(deps.storage, (count, &info.sender));
+        TOP_COUNTERS.save(deps.storage, (count + 1, &info.sender), &Empty {})?;
+
         let res: Response = Response::new()
             .add_attribute("action", "incerment")
             .add_attribute("sender", info.sender.as_str())
             .add_attribute("counter", counter.to_string());
         Ok(res)
     }
+
+    // only the global counter is reset, senders keep their own counts
+    pub fn reset(deps: DepsMut, info: MessageInfo, value: u64) -> StdResult<Response> {
+        let owner = OWNER.load(deps.storage)?;
+        if info.sender != owner {
+            return Err(StdError::generic_err(
+                "Unauthorized - only the owner can reset",
+            ));
+        }
+
+        COUNTER.save(deps.storage, &value)?;
+        let res: Response = Response::new()
+            .add_attribute("action", "reset")
+            .add_attribute("sender", info.sender.as_str())
+            .add_attribute("counter", value.to_string());
+        Ok(res)
+    }
 }
 
 pub mod query {
-    use crate::msg::ValueResponse;
-    use crate::state::COUNTER;
-    use cosmwasm_std::{Deps, StdResult};
+    use crate::msg::{Counter, TopCountersResponse, ValueResponse};
+    use crate::state::{COUNTER, COUNTS, TOP_COUNTERS};
+    use cosmwasm_std::{Deps, Order, StdResult};
+    use cw_storage_plus::Bound;
+
+    const DEFAULT_LIMIT: u32 = 10;
+    const MAX_LIMIT: u32 = 30;
 
     pub fn value(deps: Deps) -> StdResult<ValueResponse> {
         let counter = COUNTER.load(deps.storage)?;
         Ok(ValueResponse { value: counter })
     }
+
+    pub fn value_of(deps: Deps, addr: String) -> StdResult<ValueResponse> {
+        let addr = deps.api.addr_validate(&addr)?;
+        let count = COUNTS.may_load(deps.storage, &addr)?.unwrap_or_default();
+        Ok(ValueResponse { value: count })
+    }
+
+    // senders with the most increments first, then by descending address
+    pub fn top_counters(
+        deps: Deps,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    ) -> StdResult<TopCountersResponse> {
+        let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+        let start_after = start_after
+            .map(|addr| deps.api.addr_validate(&addr))
+            .transpose()?;
+        let max = match &start_after {
+            Some(addr) => {
+                let count = COUNTS.may_load(deps.storage, addr)?.unwrap_or_default();
+                Some(Bound::exclusive((count, addr)))
+            }
+            None => None,
+        };
+
+        let counters = TOP_COUNTERS
+            .keys(deps.storage, None, max, Order::Descending)
+            .take(limit)
+            .map(|key| {
+                let (count, address) = key?;
+                Ok(Counter {
+                    address: address.into_string(),
+                    count,
+                })
+            })
+            .collect::<StdResult<_>>()?;
+        Ok(TopCountersResponse { counters })
+    }
 }
@@ -1,5 +1,6 @@
 use cosmwasm_std::{
-    entry_point, to_binary, Binary, Deps, DepsMut, Empty, Env, MessageInfo, Response, StdResult,
+    entry_point, to_json_binary, Binary, Deps, DepsMut, Empty, Env, MessageInfo, Response,
+    StdResult,
 };
 
 mod contract;
@@ -10,10 +11,10 @@ mod state;
 pub fn instantiate(
     deps: DepsMut,
     _env: Env,
-    _info: MessageInfo,
+    info: MessageInfo,
     _msg: Empty,
 ) -> StdResult<Response> {
-    contract::instantiate(deps)?;
+    contract::instantiate(deps, info)?;
     Ok(Response::new())
 }
 
@@ -28,6 +29,7 @@ pub fn execute(
     use msg::ExecuteMsg::*;
     match msg {
         Increment {} => execute::increment(deps, info),
+        Reset { value } => execute::reset(deps, info, value),
     }
 }
 
@@ -36,7 +38,11 @@ pub fn query(deps: Deps, _env: Env, msg: msg::QueryMsg) -> StdResult<Binary> {
     use contract::query;
     use msg::QueryMsg::*;
     match msg {
-        Value {} => to_binary(&query::value(deps)?),
+        Value {} => to_json_binary(&query::value(deps)?),
+        ValueOf { addr } => to_json_binary(&query::value_of(deps, addr)?),
+        TopCounters { start_after, limit } => {
+            to_json_binary(&query::top_counters(deps, start_after, limit)?)
+        }
     }
 }
 
@@ -45,7 +51,7 @@ mod test {
     use cosmwasm_std::{Addr, Empty};
     use cw_multi_test::{App, Contract, ContractWrapper, Executor};
 
-    use crate::msg::{ExecuteMsg, QueryMsg, ValueResponse};
+    use crate::msg::{Counter, ExecuteMsg, QueryMsg, TopCountersResponse, ValueResponse};
     use crate::{execute, instantiate, query};
 
     fn counting_contract() -> Box<dyn Contract<Empty>> {
@@ -110,4 +116,209 @@ mod test {
 
         assert_eq!(resp, ValueResponse { value: 1 });
     }
+
+    #[test]
+    fn value_of() {
+        let sender = Addr::unchecked("sender");
+        let other = Addr::unchecked("other");
+        let mut app = App::default();
+        let contract_id = app.store_code(counting_contract());
+        let contract_addr = app
+            .instantiate_contract(
+                contract_id,
+                sender.clone(),
+                &Empty {},
+                &[],
+                "Counting contract",
+                None,
+            )
+            .unwrap();
+
+        for incrementer in [&sender, &sender, &other] {
+            app.execute_contract(
+                incrementer.clone(),
+                contract_addr.clone(),
+                &ExecuteMsg::Increment {},
+                &[],
+            )
+            .unwrap();
+        }
+
+        let resp: ValueResponse = app
+            .wrap()
+            .query_wasm_smart(contract_addr.clone(), &QueryMsg::Value {})
+            .unwrap();
+        assert_eq!(resp, ValueResponse { value: 3 });
+
+        let resp: ValueResponse = app
+            .wrap()
+            .query_wasm_smart(
+                contract_addr.clone(),
+                &QueryMsg::ValueOf {
+                    addr: sender.to_string(),
+                },
+            )
+            .unwrap();
+        assert_eq!(resp, ValueResponse { value: 2 });
+
+        let resp: ValueResponse = app
+            .wrap()
+            .query_wasm_smart(
+                contract_addr.clone(),
+                &QueryMsg::ValueOf {
+                    addr: other.to_string(),
+                },
+            )
+            .unwrap();
+        assert_eq!(resp, ValueResponse { value: 1 });
+
+        let resp: ValueResponse = app
+            .wrap()
+            .query_wasm_smart(
+                contract_addr,
+                &QueryMsg::ValueOf {
+                    addr: "stranger".to_string(),
+                },
+            )
+            .unwrap();
+        assert_eq!(resp, ValueResponse { value: 0 });
+    }
+
+    #[test]
+    fn reset() {
+        let owner = Addr::unchecked("owner");
+        let sender = Addr::unchecked("sender");
+        let mut app = App::default();
+        let contract_id = app.store_code(counting_contract());
+        let contract_addr = app
+            .instantiate_contract(
+                contract_id,
+                owner.clone(),
+                &Empty {},
+                &[],
+                "Counting contract",
+                None,
+            )
+            .unwrap();
+
+        app.execute_contract(
+            sender.clone(),
+            contract_addr.clone(),
+            &ExecuteMsg::Increment {},
+            &[],
+        )
+        .unwrap();
+
+        let err = app
+            .execute_contract(
+                sender.clone(),
+                contract_addr.clone(),
+                &ExecuteMsg::Reset { value: 10 },
+                &[],
+            )
+            .unwrap_err();
+        assert!(err.root_cause().to_string().contains("Unauthorized"));
+
+        app.execute_contract(
+            owner,
+            contract_addr.clone(),
+            &ExecuteMsg::Reset { value: 10 },
+            &[],
+        )
+        .unwrap();
+
+        let resp: ValueResponse = app
+            .wrap()
+            .query_wasm_smart(contract_addr.clone(), &QueryMsg::Value {})
+            .unwrap();
+        assert_eq!(resp, ValueResponse { value: 10 });
+
+        // the sender keeps their own count
+        let resp: ValueResponse = app
+            .wrap()
+            .query_wasm_smart(
+                contract_addr,
+                &QueryMsg::ValueOf {
+                    addr: sender.to_string(),
+                },
+            )
+            .unwrap();
+        assert_eq!(resp, ValueResponse { value: 1 });
+    }
+
+    #[test]
+    fn top_counters() {
+        let mut app = App::default();
+        let contract_id = app.store_code(counting_contract());
+        let contract_addr = app
+            .instantiate_contract(
+                contract_id,
+                Addr::unchecked("owner"),
+                &Empty {},
+                &[],
+                "Counting contract",
+                None,
+            )
+            .unwrap();
+
+        for (incrementer, increments) in [("alex", 3), ("ann", 1), ("bob", 2), ("carl", 2)] {
+            for _ in 0..increments {
+                app.execute_contract(
+                    Addr::unchecked(incrementer),
+                    contract_addr.clone(),
+                    &ExecuteMsg::Increment {},
+                    &[],
+                )
+                .unwrap();
+            }
+        }
+
+        let resp: TopCountersResponse = app
+            .wrap()
+            .query_wasm_smart(
+                contract_addr.clone(),
+                &QueryMsg::TopCounters {
+                    start_after: None,
+                    limit: Some(2),
+                },
+            )
+            .unwrap();
+        assert_eq!(
+            resp.counters,
+            vec![
+                Counter {
+                    address: "alex".to_string(),
+                    count: 3
+                },
+                Counter {
+                    address: "carl".to_string(),
+                    count: 2
+                },
+            ]
+        );
+
+        let resp: TopCountersResponse = app
+            .wrap()
+            .query_wasm_smart(
+                contract_addr,
+                &QueryMsg::TopCounters {
+                    start_after: Some("carl".to_string()),
+                    limit: None,
+                },
+            )
+            .unwrap();
+        assert_eq!(
+            resp.counters,
+            vec![
+                Counter {
+                    address: "bob".to_string(),
+                    count: 2
+                },
+                Counter {
+                    address: "ann".to_string(),
+                    count: 1
+                },
+            ]
+        );
+    }
 }
@@ -4,12 +4,20 @@ use serde::{Deserialize, Serialize};
 #[serde(rename_all = "snake_case")]
 pub enum QueryMsg {
     Value {},
+    ValueOf {
+        addr: String,
+    },
+    TopCounters {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
 }
 
 #[derive(Deserialize, Serialize, Clone, Debug, PartialEq, Eq)]
 #[serde(rename_all = "snake_case")]
 pub enum ExecuteMsg {
     Increment {},
+    Reset { value: u64 },
 }
 
 #[derive(Deserialize, Serialize, Clone, Debug, PartialEq, Eq)]
@@ -17,3 +25,16 @@ pub enum ExecuteMsg {
 pub struct ValueResponse {
     pub value: u64,
 }
+
+#[derive(Deserialize, Serialize, Clone, Debug, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub struct Counter {
+    pub address: String,
+    pub count: u64,
+}
+
+#[derive(Deserialize, Serialize, Clone, Debug, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub struct TopCountersResponse {
+    pub counters: Vec<Counter>,
+}
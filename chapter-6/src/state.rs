@@ -1,3 +1,8 @@
-use cw_storage_plus::Item;
+use cosmwasm_std::{Addr, Empty};
+use cw_storage_plus::{Item, Map};
 
 pub const COUNTER: Item<u64> = Item::new("counter");
+pub const OWNER: Item<Addr> = Item::new("owner");
+pub const COUNTS: Map<&Addr, u64> = Map::new("counts");
+// senders by their increment count, so that the top ones can be listed
+pub const TOP_COUNTERS: Map<(u64, &Addr), Empty> = Map::new("top_counters");
@@ -1,44 +1,254 @@
 use crate::msg::InstantiateMsg;
-use crate::state::{COUNTER, MIN_DONATION};
-use cosmwasm_std::{DepsMut, Response, StdResult};
+use crate::state::{BENEFICIARY, COUNTER, MIN_DONATION, OWNER};
+use cosmwasm_std::{DepsMut, MessageInfo, Response, StdResult};
+use cw20::Denom;
 
-pub fn instantiate(deps: DepsMut, msg: InstantiateMsg) -> StdResult<Response> {
+pub fn instantiate(deps: DepsMut, info: MessageInfo, msg: InstantiateMsg) -> StdResult<Response> {
+    if let Denom::Cw20(token) = &msg.min_donation.denom {
+        deps.api.addr_validate(token.as_str())?;
+    }
     COUNTER.save(deps.storage, &0)?;
     MIN_DONATION.save(deps.storage, &msg.min_donation)?;
+    OWNER.save(deps.storage, &info.sender)?;
+    if let Some(beneficiary) = msg.beneficiary {
+        let beneficiary = deps.api.addr_validate(&beneficiary)?;
+        BENEFICIARY.save(deps.storage, &beneficiary)?;
+    }
 
     Ok(Response::new())
 }
 
 pub mod execute {
-    use crate::state::{COUNTER, MIN_DONATION};
-    use cosmwasm_std::{DepsMut, MessageInfo, Response, StdResult};
-    pub fn donate(deps: DepsMut, info: MessageInfo) -> StdResult<Response> {
+    use crate::error::ContractError;
+    use crate::msg::{MinDonation, ReceiveMsg};
+    use crate::state::{BENEFICIARY, COUNTER, DONATIONS, MIN_DONATION, OWNER, TOP_DONORS};
+    use cosmwasm_std::{
+        from_json, to_json_binary, Addr, BankMsg, Coin, DepsMut, Empty, Env, MessageInfo, Response,
+        Storage, Uint128, WasmMsg,
+    };
+    use cw20::{BalanceResponse, Cw20Coin, Cw20ExecuteMsg, Cw20QueryMsg, Cw20ReceiveMsg, Denom};
+
+    pub fn donate(deps: DepsMut, info: MessageInfo) -> Result<Response, ContractError> {
         let min_donation = MIN_DONATION.load(deps.storage)?;
+        let donated = match &min_donation.denom {
+            Denom::Native(denom) => info
+                .funds
+                .iter()
+                .filter(|coin| coin.denom == *denom)
+                .map(|coin| coin.amount)
+                .sum(),
+            Denom::Cw20(_) => Uint128::zero(),
+        };
+        let mut res = count_donation(deps.storage, &info.sender, donated, min_donation)?;
 
-        let counter = COUNTER.load(deps.storage)?;
-        if info
-            .funds
-            .iter()
-            .any(|coin| coin.denom == min_donation.denom && coin.amount >= min_donation.amount)
-        {
-            COUNTER.save(deps.storage, &(counter + 1))?;
-        } else {
+        // donations go straight to the beneficiary when there is one, and there is something to
+        // send, as banks refuse empty transfers
+        let beneficiary = BENEFICIARY.may_load(deps.storage)?;
+        if let Some(beneficiary) = beneficiary.filter(|_| !info.funds.is_empty()) {
+            res = res
+                .add_message(BankMsg::Send {
+                    to_address: beneficiary.to_string(),
+                    amount: info.funds,
+                })
+                .add_attribute("beneficiary", beneficiary.as_str());
+        }
+        Ok(res)
+    }
+
+    // cw20 donations, `info.sender` being the token contract
+    pub fn receive(
+        deps: DepsMut,
+        info: MessageInfo,
+        msg: Cw20ReceiveMsg,
+    ) -> Result<Response, ContractError> {
+        let ReceiveMsg::Donate {} = from_json(&msg.msg)?;
+
+        let min_donation = MIN_DONATION.load(deps.storage)?;
+        // tokens of any other contract than the donation one are refused
+        if min_donation.denom != Denom::Cw20(info.sender.clone()) {
+            return Err(ContractError::ForeignToken {
+                token: info.sender.to_string(),
+            });
         }
+        let donor = deps.api.addr_validate(&msg.sender)?;
+        let mut res = count_donation(deps.storage, &donor, msg.amount, min_donation)?;
+
+        if let Some(beneficiary) = BENEFICIARY.may_load(deps.storage)? {
+            res = res
+                .add_message(WasmMsg::Execute {
+                    contract_addr: info.sender.to_string(),
+                    msg: to_json_binary(&Cw20ExecuteMsg::Transfer {
+                        recipient: beneficiary.to_string(),
+                        amount: msg.amount,
+                    })?,
+                    funds: vec![],
+                })
+                .add_attribute("beneficiary", beneficiary.as_str());
+        }
+        Ok(res)
+    }
+
+    fn count_donation(
+        storage: &mut dyn Storage,
+        donor: &Addr,
+        donated: Uint128,
+        min_donation: MinDonation,
+    ) -> Result<Response, ContractError> {
+        if donated < min_donation.amount {
+            return Err(ContractError::DonationTooLow { min_donation });
+        }
+
+        let counter = COUNTER.load(storage)?;
+        COUNTER.save(storage, &(counter + 1))?;
+
+        let donations = DONATIONS.may_load(storage, donor)?.unwrap_or_default();
+        DONATIONS.save(storage, donor, &(donations + 1))?;
+        TOP_DONORS.remove(storage, (donations, donor));
+        TOP_DONORS.save(storage, (donations + 1, donor), &Empty {})?;
+
         let res: Response = Response::new()
             .add_attribute("action", "incerment")
-            .add_attribute("sender", info.sender.as_str())
+            .add_attribute("sender", donor.as_str())
             .add_attribute("counter", counter.to_string());
         Ok(res)
     }
+
+    // only the global counter is reset, donors keep their own counts
+    pub fn reset(deps: DepsMut, info: MessageInfo, value: u64) -> Result<Response, ContractError> {
+        let owner = OWNER.load(deps.storage)?;
+        if info.sender != owner {
+            return Err(ContractError::Unauthorized {
+                owner: owner.into_string(),
+            });
+        }
+
+        COUNTER.save(deps.storage, &value)?;
+        let res: Response = Response::new()
+            .add_attribute("action", "reset")
+            .add_attribute("sender", info.sender.as_str())
+            .add_attribute("counter", value.to_string());
+        Ok(res)
+    }
+
+    // sends the given funds, or everything collected when none are given, to the receiver
+    pub fn withdraw(
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        receiver: String,
+        funds: Vec<Coin>,
+        tokens: Vec<Cw20Coin>,
+        all: bool,
+    ) -> Result<Response, ContractError> {
+        let owner = OWNER.load(deps.storage)?;
+        if info.sender != owner {
+            return Err(ContractError::Unauthorized {
+                owner: owner.into_string(),
+            });
+        }
+        if all && !(funds.is_empty() && tokens.is_empty()) {
+            return Err(ContractError::WithdrawAllWithFunds {});
+        }
+
+        let receiver = deps.api.addr_validate(&receiver)?;
+        let mut res: Response = Response::new()
+            .add_attribute("action", "withdraw")
+            .add_attribute("sender", info.sender.as_str())
+            .add_attribute("receiver", receiver.as_str());
+
+        let (funds, tokens) = if all {
+            // cw20 donations are withdrawn too when taking everything
+            let mut tokens = vec![];
+            if let Denom::Cw20(token) = MIN_DONATION.load(deps.storage)?.denom {
+                let balance: BalanceResponse = deps.querier.query_wasm_smart(
+                    &token,
+                    &Cw20QueryMsg::Balance {
+                        address: env.contract.address.to_string(),
+                    },
+                )?;
+                if !balance.balance.is_zero() {
+                    tokens.push(Cw20Coin {
+                        address: token.into_string(),
+                        amount: balance.balance,
+                    });
+                }
+            }
+            let funds = deps.querier.query_all_balances(&env.contract.address)?;
+            (funds, tokens)
+        } else {
+            (funds, tokens)
+        };
+
+        if !funds.is_empty() {
+            res = res.add_message(BankMsg::Send {
+                to_address: receiver.to_string(),
+                amount: funds,
+            });
+        }
+        for token in tokens {
+            let contract_addr = deps.api.addr_validate(&token.address)?;
+            res = res.add_message(WasmMsg::Execute {
+                contract_addr: contract_addr.into_string(),
+                msg: to_json_binary(&Cw20ExecuteMsg::Transfer {
+                    recipient: receiver.to_string(),
+                    amount: token.amount,
+                })?,
+                funds: vec![],
+            });
+        }
+        Ok(res)
+    }
 }
 
 pub mod query {
-    use crate::msg::ValueResponse;
-    use crate::state::COUNTER;
-    use cosmwasm_std::{Deps, StdResult};
+    use crate::msg::{Donor, TopDonorsResponse, ValueResponse};
+    use crate::state::{COUNTER, DONATIONS, TOP_DONORS};
+    use cosmwasm_std::{Deps, Order, StdResult};
+    use cw_storage_plus::Bound;
+
+    const DEFAULT_LIMIT: u32 = 10;
+    const MAX_LIMIT: u32 = 30;
 
     pub fn value(deps: Deps) -> StdResult<ValueResponse> {
         let counter = COUNTER.load(deps.storage)?;
         Ok(ValueResponse { value: counter })
     }
+
+    pub fn value_of(deps: Deps, addr: String) -> StdResult<ValueResponse> {
+        let addr = deps.api.addr_validate(&addr)?;
+        let donations = DONATIONS.may_load(deps.storage, &addr)?.unwrap_or_default();
+        Ok(ValueResponse { value: donations })
+    }
+
+    // donors with the most donations first, then by descending address
+    pub fn top_donors(
+        deps: Deps,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    ) -> StdResult<TopDonorsResponse> {
+        let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+        let start_after = start_after
+            .map(|addr| deps.api.addr_validate(&addr))
+            .transpose()?;
+        let max = match &start_after {
+            Some(addr) => {
+                let donations = DONATIONS.may_load(deps.storage, addr)?.unwrap_or_default();
+                Some(Bound::exclusive((donations, addr)))
+            }
+            None => None,
+        };
+
+        let donors = TOP_DONORS
+            .keys(deps.storage, None, max, Order::Descending)
+            .take(limit)
+            .map(|key| {
+                let (donations, address) = key?;
+                Ok(Donor {
+                    address: address.into_string(),
+                    donations,
+                })
+            })
+            .collect::<StdResult<_>>()?;
+        Ok(TopDonorsResponse { donors })
+    }
 }
@@ -0,0 +1,18 @@
+use cosmwasm_std::StdError;
+use thiserror::Error;
+
+use crate::msg::MinDonation;
+
+#[derive(Error, Debug, PartialEq)]
+pub enum ContractError {
+    #[error("{0}")]
+    Std(#[from] StdError),
+    #[error("Unauthorized - only {owner} can call it")]
+    Unauthorized { owner: String },
+    #[error("Donation below the minimum of {min_donation}")]
+    DonationTooLow { min_donation: MinDonation },
+    #[error("Tokens of {token} are not accepted")]
+    ForeignToken { token: String },
+    #[error("Funds cannot be given when withdrawing everything")]
+    WithdrawAllWithFunds {},
+}
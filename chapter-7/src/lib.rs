@@ -1,8 +1,9 @@
 use cosmwasm_std::{
-    entry_point, to_binary, Binary, Deps, DepsMut, Env, MessageInfo, Response, StdResult,
+    entry_point, to_json_binary, Binary, Deps, DepsMut, Env, MessageInfo, Response, StdResult,
 };
 
 mod contract;
+pub mod error;
 pub mod msg;
 mod state;
 
@@ -10,24 +11,32 @@ mod state;
 pub fn instantiate(
     deps: DepsMut,
     _env: Env,
-    _info: MessageInfo,
+    info: MessageInfo,
     msg: msg::InstantiateMsg,
 ) -> StdResult<Response> {
-    contract::instantiate(deps, msg)?;
+    contract::instantiate(deps, info, msg)?;
     Ok(Response::new())
 }
 
 #[entry_point]
 pub fn execute(
     deps: DepsMut,
-    _env: Env,
+    env: Env,
     info: MessageInfo,
     msg: msg::ExecuteMsg,
-) -> StdResult<Response> {
+) -> Result<Response, error::ContractError> {
     use contract::execute;
     use msg::ExecuteMsg::*;
     match msg {
         Donate {} => execute::donate(deps, info),
+        Receive(msg) => execute::receive(deps, info, msg),
+        Reset { value } => execute::reset(deps, info, value),
+        Withdraw {
+            receiver,
+            funds,
+            tokens,
+            all,
+        } => execute::withdraw(deps, env, info, receiver, funds, tokens, all),
     }
 }
 
@@ -36,16 +45,25 @@ pub fn query(deps: Deps, _env: Env, msg: msg::QueryMsg) -> StdResult<Binary> {
     use contract::query;
     use msg::QueryMsg::*;
     match msg {
-        Value {} => to_binary(&query::value(deps)?),
+        Value {} => to_json_binary(&query::value(deps)?),
+        ValueOf { addr } => to_json_binary(&query::value_of(deps, addr)?),
+        TopDonors { start_after, limit } => {
+            to_json_binary(&query::top_donors(deps, start_after, limit)?)
+        }
     }
 }
 
 #[cfg(test)]
 mod test {
-    use cosmwasm_std::{coins, Addr, Coin, Empty};
+    use cosmwasm_std::{coins, to_json_binary, Addr, Coin, Empty, Event, Uint128};
+    use cw20::{BalanceResponse, Cw20Coin, Cw20ExecuteMsg, Cw20QueryMsg, Denom};
     use cw_multi_test::{App, AppBuilder, Contract, ContractWrapper, Executor};
 
-    use crate::msg::{ExecuteMsg, InstantiateMsg, QueryMsg, ValueResponse};
+    use crate::error::ContractError;
+    use crate::msg::{
+        Donor, ExecuteMsg, InstantiateMsg, MinDonation, QueryMsg, ReceiveMsg, TopDonorsResponse,
+        ValueResponse,
+    };
     use crate::{execute, instantiate, query};
 
     fn counting_contract() -> Box<dyn Contract<Empty>> {
@@ -53,6 +71,109 @@ mod test {
         Box::new(contract)
     }
 
+    fn cw20_contract() -> Box<dyn Contract<Empty>> {
+        let contract = ContractWrapper::new(
+            cw20_base::contract::execute,
+            cw20_base::contract::instantiate,
+            cw20_base::contract::query,
+        );
+        Box::new(contract)
+    }
+
+    fn native_min_donation() -> MinDonation {
+        MinDonation {
+            denom: Denom::Native("bol".to_string()),
+            amount: Uint128::new(10),
+        }
+    }
+
+    fn cw20_min_donation(token: &Addr) -> MinDonation {
+        MinDonation {
+            denom: Denom::Cw20(token.clone()),
+            amount: Uint128::new(10),
+        }
+    }
+
+    fn instantiate_token(app: &mut App, symbol: &str, balances: &[(&Addr, u128)]) -> Addr {
+        let cw20_id = app.store_code(cw20_contract());
+        app.instantiate_contract(
+            cw20_id,
+            Addr::unchecked("minter"),
+            &cw20_base::msg::InstantiateMsg {
+                name: "Bol token".to_string(),
+                symbol: symbol.to_string(),
+                decimals: 6,
+                initial_balances: balances
+                    .iter()
+                    .map(|(address, amount)| Cw20Coin {
+                        address: address.to_string(),
+                        amount: Uint128::new(*amount),
+                    })
+                    .collect(),
+                mint: None,
+                marketing: None,
+            },
+            &[],
+            "Cw20 token",
+            None,
+        )
+        .unwrap()
+    }
+
+    fn instantiate_counter(
+        app: &mut App,
+        min_donation: MinDonation,
+        beneficiary: Option<&Addr>,
+    ) -> Addr {
+        let contract_id = app.store_code(counting_contract());
+        app.instantiate_contract(
+            contract_id,
+            Addr::unchecked("owner"),
+            &InstantiateMsg {
+                min_donation,
+                beneficiary: beneficiary.map(Addr::to_string),
+            },
+            &[],
+            "Counting contract",
+            None,
+        )
+        .unwrap()
+    }
+
+    fn donate_cw20(
+        app: &mut App,
+        sender: &Addr,
+        token: &Addr,
+        counter: &Addr,
+        amount: u128,
+    ) -> Result<(), ContractError> {
+        app.execute_contract(
+            sender.clone(),
+            token.clone(),
+            &Cw20ExecuteMsg::Send {
+                contract: counter.to_string(),
+                amount: Uint128::new(amount),
+                msg: to_json_binary(&ReceiveMsg::Donate {}).unwrap(),
+            },
+            &[],
+        )
+        .map(|_| ())
+        .map_err(|err| err.downcast().unwrap())
+    }
+
+    fn token_balance(app: &App, token: &Addr, address: &Addr) -> Uint128 {
+        let resp: BalanceResponse = app
+            .wrap()
+            .query_wasm_smart(
+                token,
+                &Cw20QueryMsg::Balance {
+                    address: address.to_string(),
+                },
+            )
+            .unwrap();
+        resp.balance
+    }
+
     #[test]
     fn query_value() {
         let mut app = App::default();
@@ -64,7 +185,8 @@ mod test {
                 contract_id,
                 Addr::unchecked("sender"),
                 &InstantiateMsg {
-                    min_donation: Coin::new(10, "bol"),
+                    min_donation: native_min_donation(),
+                    beneficiary: None,
                 },
                 &[],
                 "Counting contract",
@@ -90,7 +212,57 @@ mod test {
                 contract_id,
                 sender.clone(),
                 &InstantiateMsg {
-                    min_donation: Coin::new(10, "bol"),
+                    min_donation: native_min_donation(),
+                    beneficiary: None,
+                },
+                &[],
+                "Counting contract",
+                None,
+            )
+            .unwrap();
+
+        let err = app
+            .execute_contract(
+                sender.clone(),
+                contract_addr.clone(),
+                &ExecuteMsg::Donate {},
+                &[],
+            )
+            .unwrap_err();
+
+        assert_eq!(
+            err.downcast::<ContractError>().unwrap(),
+            ContractError::DonationTooLow {
+                min_donation: native_min_donation()
+            }
+        );
+
+        let resp: ValueResponse = app
+            .wrap()
+            .query_wasm_smart(contract_addr.clone(), &QueryMsg::Value {})
+            .unwrap();
+
+        assert_eq!(resp, ValueResponse { value: 0 });
+    }
+
+    #[test]
+    fn donate_with_funds() {
+        let sender = Addr::unchecked("sender");
+        let mut app = AppBuilder::new().build(|router, _api, storage| {
+            router
+                .bank
+                .init_balance(storage, &sender, coins(10, "bol"))
+                .unwrap();
+        });
+        let contract_id = app.store_code(counting_contract());
+        let sender = Addr::unchecked("sender");
+        let contract_addr = app
+            .instantiate_contract(
+                contract_id,
+                sender.clone(),
+                &InstantiateMsg {
+                    min_donation: native_min_donation(),
+                    beneficiary: None,
                 },
                 &[],
                 "Counting contract",
@@ -102,7 +274,7 @@ mod test {
             sender.clone(),
             contract_addr.clone(),
             &ExecuteMsg::Donate {},
-            &[],
+            &[Coin::new(10, "bol")],
         )
         .unwrap();
 
@@ -111,11 +283,96 @@ mod test {
             .query_wasm_smart(contract_addr.clone(), &QueryMsg::Value {})
             .unwrap();
 
+        assert_eq!(resp, ValueResponse { value: 1 });
+        assert_eq!(app.wrap().query_all_balances(sender).unwrap(), vec![]);
+        assert_eq!(
+            app.wrap().query_all_balances(contract_addr).unwrap(),
+            coins(10, "bol")
+        );
+    }
+
+    #[test]
+    fn value_of() {
+        let sender = Addr::unchecked("sender");
+        let other = Addr::unchecked("other");
+        let mut app = AppBuilder::new().build(|router, _api, storage| {
+            router
+                .bank
+                .init_balance(storage, &sender, coins(20, "bol"))
+                .unwrap();
+            router
+                .bank
+                .init_balance(storage, &other, coins(10, "bol"))
+                .unwrap();
+        });
+        let contract_id = app.store_code(counting_contract());
+        let contract_addr = app
+            .instantiate_contract(
+                contract_id,
+                sender.clone(),
+                &InstantiateMsg {
+                    min_donation: native_min_donation(),
+                    beneficiary: None,
+                },
+                &[],
+                "Counting contract",
+                None,
+            )
+            .unwrap();
+
+        for donor in [&sender, &sender, &other] {
+            app.execute_contract(
+                donor.clone(),
+                contract_addr.clone(),
+                &ExecuteMsg::Donate {},
+                &[Coin::new(10, "bol")],
+            )
+            .unwrap();
+        }
+
+        let resp: ValueResponse = app
+            .wrap()
+            .query_wasm_smart(contract_addr.clone(), &QueryMsg::Value {})
+            .unwrap();
+        assert_eq!(resp, ValueResponse { value: 3 });
+
+        let resp: ValueResponse = app
+            .wrap()
+            .query_wasm_smart(
+                contract_addr.clone(),
+                &QueryMsg::ValueOf {
+                    addr: sender.to_string(),
+                },
+            )
+            .unwrap();
+        assert_eq!(resp, ValueResponse { value: 2 });
+
+        let resp: ValueResponse = app
+            .wrap()
+            .query_wasm_smart(
+                contract_addr.clone(),
+                &QueryMsg::ValueOf {
+                    addr: other.to_string(),
+                },
+            )
+            .unwrap();
+        assert_eq!(resp, ValueResponse { value: 1 });
+
+        let resp: ValueResponse = app
+            .wrap()
+            .query_wasm_smart(
+                contract_addr,
+                &QueryMsg::ValueOf {
+                    addr: "stranger".to_string(),
+                },
+            )
+            .unwrap();
         assert_eq!(resp, ValueResponse { value: 0 });
     }
 
     #[test]
-    fn donate_with_funds() {
+    fn reset() {
+        let owner = Addr::unchecked("owner");
         let sender = Addr::unchecked("sender");
         let mut app = AppBuilder::new().build(|router, _api, storage| {
             router
@@ -124,13 +381,316 @@ mod test {
                 .unwrap();
         });
         let contract_id = app.store_code(counting_contract());
+        let contract_addr = app
+            .instantiate_contract(
+                contract_id,
+                owner.clone(),
+                &InstantiateMsg {
+                    min_donation: native_min_donation(),
+                    beneficiary: None,
+                },
+                &[],
+                "Counting contract",
+                None,
+            )
+            .unwrap();
+
+        app.execute_contract(
+            sender.clone(),
+            contract_addr.clone(),
+            &ExecuteMsg::Donate {},
+            &[Coin::new(10, "bol")],
+        )
+        .unwrap();
+
+        let err = app
+            .execute_contract(
+                sender.clone(),
+                contract_addr.clone(),
+                &ExecuteMsg::Reset { value: 10 },
+                &[],
+            )
+            .unwrap_err();
+        assert_eq!(
+            err.downcast::<ContractError>().unwrap(),
+            ContractError::Unauthorized {
+                owner: owner.to_string()
+            }
+        );
+
+        app.execute_contract(
+            owner,
+            contract_addr.clone(),
+            &ExecuteMsg::Reset { value: 10 },
+            &[],
+        )
+        .unwrap();
+
+        let resp: ValueResponse = app
+            .wrap()
+            .query_wasm_smart(contract_addr.clone(), &QueryMsg::Value {})
+            .unwrap();
+        assert_eq!(resp, ValueResponse { value: 10 });
+
+        // the donor keeps their own count
+        let resp: ValueResponse = app
+            .wrap()
+            .query_wasm_smart(
+                contract_addr,
+                &QueryMsg::ValueOf {
+                    addr: sender.to_string(),
+                },
+            )
+            .unwrap();
+        assert_eq!(resp, ValueResponse { value: 1 });
+    }
+
+    #[test]
+    fn top_donors() {
+        let donors = [("alex", 3), ("ann", 1), ("bob", 2), ("carl", 2)];
+        let mut app = AppBuilder::new().build(|router, _api, storage| {
+            for (donor, donations) in donors {
+                router
+                    .bank
+                    .init_balance(
+                        storage,
+                        &Addr::unchecked(donor),
+                        coins(donations * 10, "bol"),
+                    )
+                    .unwrap();
+            }
+        });
+        let contract_id = app.store_code(counting_contract());
+        let contract_addr = app
+            .instantiate_contract(
+                contract_id,
+                Addr::unchecked("owner"),
+                &InstantiateMsg {
+                    min_donation: native_min_donation(),
+                    beneficiary: None,
+                },
+                &[],
+                "Counting contract",
+                None,
+            )
+            .unwrap();
+
+        for (donor, donations) in donors {
+            for _ in 0..donations {
+                app.execute_contract(
+                    Addr::unchecked(donor),
+                    contract_addr.clone(),
+                    &ExecuteMsg::Donate {},
+                    &[Coin::new(10, "bol")],
+                )
+                .unwrap();
+            }
+        }
+
+        let resp: TopDonorsResponse = app
+            .wrap()
+            .query_wasm_smart(
+                contract_addr.clone(),
+                &QueryMsg::TopDonors {
+                    start_after: None,
+                    limit: Some(2),
+                },
+            )
+            .unwrap();
+        assert_eq!(
+            resp.donors,
+            vec![
+                Donor {
+                    address: "alex".to_string(),
+                    donations: 3
+                },
+                Donor {
+                    address: "carl".to_string(),
+                    donations: 2
+                },
+            ]
+        );
+
+        let resp: TopDonorsResponse = app
+            .wrap()
+            .query_wasm_smart(
+                contract_addr,
+                &QueryMsg::TopDonors {
+                    start_after: Some("carl".to_string()),
+                    limit: None,
+                },
+            )
+            .unwrap();
+        assert_eq!(
+            resp.donors,
+            vec![
+                Donor {
+                    address: "bob".to_string(),
+                    donations: 2
+                },
+                Donor {
+                    address: "ann".to_string(),
+                    donations: 1
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn withdraw() {
+        let owner = Addr::unchecked("owner");
         let sender = Addr::unchecked("sender");
+        let receiver = Addr::unchecked("receiver");
+        let mut app = AppBuilder::new().build(|router, _api, storage| {
+            router
+                .bank
+                .init_balance(
+                    storage,
+                    &sender,
+                    vec![Coin::new(30, "bol"), Coin::new(5, "atom")],
+                )
+                .unwrap();
+        });
+        let contract_id = app.store_code(counting_contract());
         let contract_addr = app
             .instantiate_contract(
                 contract_id,
+                owner.clone(),
+                &InstantiateMsg {
+                    min_donation: native_min_donation(),
+                    beneficiary: None,
+                },
+                &[],
+                "Counting contract",
+                None,
+            )
+            .unwrap();
+
+        app.execute_contract(
+            sender.clone(),
+            contract_addr.clone(),
+            &ExecuteMsg::Donate {},
+            &[Coin::new(30, "bol"), Coin::new(5, "atom")],
+        )
+        .unwrap();
+
+        let err = app
+            .execute_contract(
                 sender.clone(),
+                contract_addr.clone(),
+                &ExecuteMsg::Withdraw {
+                    receiver: sender.to_string(),
+                    funds: vec![],
+                    tokens: vec![],
+                    all: true,
+                },
+                &[],
+            )
+            .unwrap_err();
+        assert_eq!(
+            err.downcast::<ContractError>().unwrap(),
+            ContractError::Unauthorized {
+                owner: owner.to_string()
+            }
+        );
+
+        app.execute_contract(
+            owner.clone(),
+            contract_addr.clone(),
+            &ExecuteMsg::Withdraw {
+                receiver: receiver.to_string(),
+                funds: coins(10, "bol"),
+                tokens: vec![],
+                all: false,
+            },
+            &[],
+        )
+        .unwrap();
+
+        assert_eq!(
+            app.wrap().query_all_balances(&receiver).unwrap(),
+            coins(10, "bol")
+        );
+        assert_eq!(
+            app.wrap().query_all_balances(&contract_addr).unwrap(),
+            vec![Coin::new(5, "atom"), Coin::new(20, "bol")]
+        );
+
+        // funds cannot be given along with withdrawing everything
+        let err = app
+            .execute_contract(
+                owner.clone(),
+                contract_addr.clone(),
+                &ExecuteMsg::Withdraw {
+                    receiver: owner.to_string(),
+                    funds: coins(10, "bol"),
+                    tokens: vec![],
+                    all: true,
+                },
+                &[],
+            )
+            .unwrap_err();
+        assert_eq!(
+            err.downcast::<ContractError>().unwrap(),
+            ContractError::WithdrawAllWithFunds {}
+        );
+
+        // withdrawing no funds does nothing
+        app.execute_contract(
+            owner.clone(),
+            contract_addr.clone(),
+            &ExecuteMsg::Withdraw {
+                receiver: owner.to_string(),
+                funds: vec![],
+                tokens: vec![],
+                all: false,
+            },
+            &[],
+        )
+        .unwrap();
+        assert_eq!(app.wrap().query_all_balances(&owner).unwrap(), vec![]);
+
+        app.execute_contract(
+            owner.clone(),
+            contract_addr.clone(),
+            &ExecuteMsg::Withdraw {
+                receiver: owner.to_string(),
+                funds: vec![],
+                tokens: vec![],
+                all: true,
+            },
+            &[],
+        )
+        .unwrap();
+
+        assert_eq!(
+            app.wrap().query_all_balances(&owner).unwrap(),
+            vec![Coin::new(5, "atom"), Coin::new(20, "bol")]
+        );
+        assert_eq!(
+            app.wrap().query_all_balances(&contract_addr).unwrap(),
+            vec![]
+        );
+    }
+
+    #[test]
+    fn donate_to_beneficiary() {
+        let sender = Addr::unchecked("sender");
+        let beneficiary = Addr::unchecked("beneficiary");
+        let mut app = AppBuilder::new().build(|router, _api, storage| {
+            router
+                .bank
+                .init_balance(storage, &sender, coins(10, "bol"))
+                .unwrap();
+        });
+        let contract_id = app.store_code(counting_contract());
+        let contract_addr = app
+            .instantiate_contract(
+                contract_id,
+                Addr::unchecked("owner"),
                 &InstantiateMsg {
-                    min_donation: Coin::new(10, "bol"),
+                    min_donation: native_min_donation(),
+                    beneficiary: Some(beneficiary.to_string()),
                 },
                 &[],
                 "Counting contract",
@@ -152,10 +712,214 @@ mod test {
             .unwrap();
 
         assert_eq!(resp, ValueResponse { value: 1 });
-        assert_eq!(app.wrap().query_all_balances(sender).unwrap(), vec![]);
         assert_eq!(
-            app.wrap().query_all_balances(contract_addr).unwrap(),
+            app.wrap().query_all_balances(beneficiary).unwrap(),
             coins(10, "bol")
         );
+        assert_eq!(
+            app.wrap().query_all_balances(contract_addr).unwrap(),
+            vec![]
+        );
+    }
+
+    #[test]
+    fn donate_nothing_to_beneficiary() {
+        let sender = Addr::unchecked("sender");
+        let beneficiary = Addr::unchecked("beneficiary");
+        let mut app = App::default();
+        let contract_addr = instantiate_counter(
+            &mut app,
+            MinDonation {
+                denom: Denom::Native("bol".to_string()),
+                amount: Uint128::zero(),
+            },
+            Some(&beneficiary),
+        );
+
+        // without a minimum, donations without funds count and nothing is forwarded
+        let res = app
+            .execute_contract(
+                sender.clone(),
+                contract_addr.clone(),
+                &ExecuteMsg::Donate {},
+                &[],
+            )
+            .unwrap();
+        assert!(!res.has_event(&Event::new("transfer")));
+
+        let resp: ValueResponse = app
+            .wrap()
+            .query_wasm_smart(contract_addr, &QueryMsg::Value {})
+            .unwrap();
+        assert_eq!(resp, ValueResponse { value: 1 });
+    }
+
+    #[test]
+    fn cw20_donation() {
+        let sender = Addr::unchecked("sender");
+        let mut app = App::default();
+        let token = instantiate_token(&mut app, "BOL", &[(&sender, 30)]);
+        let contract_addr = instantiate_counter(&mut app, cw20_min_donation(&token), None);
+
+        donate_cw20(&mut app, &sender, &token, &contract_addr, 10).unwrap();
+        donate_cw20(&mut app, &sender, &token, &contract_addr, 15).unwrap();
+
+        let resp: ValueResponse = app
+            .wrap()
+            .query_wasm_smart(
+                contract_addr.clone(),
+                &QueryMsg::ValueOf {
+                    addr: sender.to_string(),
+                },
+            )
+            .unwrap();
+        assert_eq!(resp, ValueResponse { value: 2 });
+        assert_eq!(
+            token_balance(&app, &token, &contract_addr),
+            Uint128::new(25)
+        );
+        assert_eq!(token_balance(&app, &token, &sender), Uint128::new(5));
+    }
+
+    #[test]
+    fn cw20_donation_too_low() {
+        let sender = Addr::unchecked("sender");
+        let mut app = AppBuilder::new().build(|router, _api, storage| {
+            router
+                .bank
+                .init_balance(storage, &sender, coins(10, "bol"))
+                .unwrap();
+        });
+        let token = instantiate_token(&mut app, "BOL", &[(&sender, 30)]);
+        let contract_addr = instantiate_counter(&mut app, cw20_min_donation(&token), None);
+
+        let expected = ContractError::DonationTooLow {
+            min_donation: cw20_min_donation(&token),
+        };
+
+        let err = donate_cw20(&mut app, &sender, &token, &contract_addr, 5).unwrap_err();
+        assert_eq!(err, expected);
+
+        // native coins are not counted
+        let err = app
+            .execute_contract(
+                sender.clone(),
+                contract_addr.clone(),
+                &ExecuteMsg::Donate {},
+                &[Coin::new(10, "bol")],
+            )
+            .unwrap_err();
+        assert_eq!(err.downcast::<ContractError>().unwrap(), expected);
+
+        let resp: ValueResponse = app
+            .wrap()
+            .query_wasm_smart(contract_addr.clone(), &QueryMsg::Value {})
+            .unwrap();
+        assert_eq!(resp, ValueResponse { value: 0 });
+        assert_eq!(token_balance(&app, &token, &sender), Uint128::new(30));
+    }
+
+    #[test]
+    fn cw20_donation_foreign_token() {
+        let sender = Addr::unchecked("sender");
+        let mut app = App::default();
+        let token = instantiate_token(&mut app, "BOL", &[(&sender, 30)]);
+        let foreign_token = instantiate_token(&mut app, "OTHER", &[(&sender, 30)]);
+
+        // even without a minimum, only the donation token is accepted
+        let min_donation = MinDonation {
+            denom: Denom::Cw20(token),
+            amount: Uint128::zero(),
+        };
+        let contract_addr = instantiate_counter(&mut app, min_donation, None);
+
+        let err = donate_cw20(&mut app, &sender, &foreign_token, &contract_addr, 10).unwrap_err();
+        assert_eq!(
+            err,
+            ContractError::ForeignToken {
+                token: foreign_token.to_string()
+            }
+        );
+
+        let resp: ValueResponse = app
+            .wrap()
+            .query_wasm_smart(contract_addr, &QueryMsg::Value {})
+            .unwrap();
+        assert_eq!(resp, ValueResponse { value: 0 });
+        assert_eq!(
+            token_balance(&app, &foreign_token, &sender),
+            Uint128::new(30)
+        );
+    }
+
+    #[test]
+    fn cw20_donation_to_beneficiary() {
+        let sender = Addr::unchecked("sender");
+        let beneficiary = Addr::unchecked("beneficiary");
+        let mut app = App::default();
+        let token = instantiate_token(&mut app, "BOL", &[(&sender, 30)]);
+        let contract_addr =
+            instantiate_counter(&mut app, cw20_min_donation(&token), Some(&beneficiary));
+
+        donate_cw20(&mut app, &sender, &token, &contract_addr, 10).unwrap();
+
+        let resp: ValueResponse = app
+            .wrap()
+            .query_wasm_smart(contract_addr.clone(), &QueryMsg::Value {})
+            .unwrap();
+        assert_eq!(resp, ValueResponse { value: 1 });
+        assert_eq!(token_balance(&app, &token, &beneficiary), Uint128::new(10));
+        assert_eq!(token_balance(&app, &token, &contract_addr), Uint128::zero());
+    }
+
+    #[test]
+    fn withdraw_cw20() {
+        let sender = Addr::unchecked("sender");
+        let owner = Addr::unchecked("owner");
+        let receiver = Addr::unchecked("receiver");
+        let mut app = App::default();
+        let token = instantiate_token(&mut app, "BOL", &[(&sender, 30)]);
+        let contract_addr = instantiate_counter(&mut app, cw20_min_donation(&token), None);
+
+        donate_cw20(&mut app, &sender, &token, &contract_addr, 20).unwrap();
+
+        // cw20 tokens are withdrawn by amount like native coins
+        app.execute_contract(
+            owner.clone(),
+            contract_addr.clone(),
+            &ExecuteMsg::Withdraw {
+                receiver: receiver.to_string(),
+                funds: vec![],
+                tokens: vec![Cw20Coin {
+                    address: token.to_string(),
+                    amount: Uint128::new(5),
+                }],
+                all: false,
+            },
+            &[],
+        )
+        .unwrap();
+        assert_eq!(token_balance(&app, &token, &receiver), Uint128::new(5));
+        assert_eq!(
+            token_balance(&app, &token, &contract_addr),
+            Uint128::new(15)
+        );
+
+        // and along with everything else
+        app.execute_contract(
+            owner,
+            contract_addr.clone(),
+            &ExecuteMsg::Withdraw {
+                receiver: receiver.to_string(),
+                funds: vec![],
+                tokens: vec![],
+                all: true,
+            },
+            &[],
+        )
+        .unwrap();
+
+        assert_eq!(token_balance(&app, &token, &receiver), Uint128::new(20));
+        assert_eq!(token_balance(&app, &token, &contract_addr), Uint128::zero());
     }
 }
@@ -1,22 +1,69 @@
-use cosmwasm_std::Coin;
+use std::fmt;
+
+use cosmwasm_std::{Coin, Uint128};
+use cw20::{Cw20Coin, Cw20ReceiveMsg, Denom};
 use serde::{Deserialize, Serialize};
 
-#[derive(Deserialize, Serialize, Clone, Debug, PartialEq, Eq)]
+#[derive(Deserialize, Serialize, Clone, Debug, PartialEq)]
 #[serde(rename_all = "snake_case")]
 pub struct InstantiateMsg {
-    pub min_donation: Coin,
+    pub min_donation: MinDonation,
+    // donations are forwarded to the beneficiary instead of being kept by the contract
+    pub beneficiary: Option<String>,
 }
 
 #[derive(Deserialize, Serialize, Clone, Debug, PartialEq, Eq)]
 #[serde(rename_all = "snake_case")]
 pub enum QueryMsg {
     Value {},
+    ValueOf {
+        addr: String,
+    },
+    TopDonors {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
 }
 
-#[derive(Deserialize, Serialize, Clone, Debug, PartialEq, Eq)]
+// donations are counted in a single denom, native coins or tokens of a cw20 contract
+#[derive(Deserialize, Serialize, Clone, Debug, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub struct MinDonation {
+    pub denom: Denom,
+    pub amount: Uint128,
+}
+
+impl fmt::Display for MinDonation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match &self.denom {
+            Denom::Native(denom) => write!(f, "{}{}", self.amount, denom),
+            Denom::Cw20(token) => write!(f, "{} of cw20 {}", self.amount, token),
+        }
+    }
+}
+
+#[derive(Deserialize, Serialize, Clone, Debug, PartialEq)]
 #[serde(rename_all = "snake_case")]
 pub enum ExecuteMsg {
     Donate {},
+    // cw20 donations, sent with a `ReceiveMsg` through the token contract
+    Receive(Cw20ReceiveMsg),
+    Reset {
+        value: u64,
+    },
+    // withdraws `funds` and `tokens` of cw20 contracts, or everything the contract holds with `all`
+    Withdraw {
+        receiver: String,
+        funds: Vec<Coin>,
+        tokens: Vec<Cw20Coin>,
+        all: bool,
+    },
+}
+
+#[derive(Deserialize, Serialize, Clone, Debug, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ReceiveMsg {
+    Donate {},
 }
 
 #[derive(Deserialize, Serialize, Clone, Debug, PartialEq, Eq)]
@@ -24,3 +71,16 @@ pub enum ExecuteMsg {
 pub struct ValueResponse {
     pub value: u64,
 }
+
+#[derive(Deserialize, Serialize, Clone, Debug, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub struct Donor {
+    pub address: String,
+    pub donations: u64,
+}
+
+#[derive(Deserialize, Serialize, Clone, Debug, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub struct TopDonorsResponse {
+    pub donors: Vec<Donor>,
+}
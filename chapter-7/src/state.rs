@@ -1,5 +1,12 @@
-use cosmwasm_std::Coin;
-use cw_storage_plus::Item;
+use cosmwasm_std::{Addr, Empty};
+use cw_storage_plus::{Item, Map};
+
+use crate::msg::MinDonation;
 
 pub const COUNTER: Item<u64> = Item::new("counter");
-pub const MIN_DONATION: Item<Coin> = Item::new("min_donation");
+pub const MIN_DONATION: Item<MinDonation> = Item::new("min_donation");
+pub const OWNER: Item<Addr> = Item::new("owner");
+pub const BENEFICIARY: Item<Addr> = Item::new("beneficiary");
+pub const DONATIONS: Map<&Addr, u64> = Map::new("donations");
+// donors by their donation count, so that the top ones can be listed
+pub const TOP_DONORS: Map<(u64, &Addr), Empty> = Map::new("top_donors");